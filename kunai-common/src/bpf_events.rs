@@ -2,6 +2,7 @@ use crate::buffer::Buffer;
 use crate::errors::ProbeError;
 use crate::macros::test_flag;
 use crate::macros::{bpf_target_code, not_bpf_target_code};
+use crate::string::String;
 use crate::uuid::{ProcUuid, Uuid};
 use kunai_macros::{BpfError, StrEnum};
 
@@ -22,6 +23,8 @@ pub const COMM_DEFAULT: [i8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
 
 pub type Nodename = Buffer<65>;
 
+pub const TTY_NAME_SIZE: usize = 64;
+
 #[derive(BpfError, Clone, Copy)]
 pub enum Error {
     #[error("flags field is missing")]
@@ -42,6 +45,8 @@ pub enum Error {
     CommMissing,
     #[error("failed to get mnt_namespace")]
     MntNamespaceFailure,
+    #[error("capability field is missing")]
+    CapFieldMissing,
 }
 
 impl From<Error> for ProbeError {
@@ -76,6 +81,16 @@ pub enum Type {
     Kill,
     #[str("ptrace")]
     Ptrace,
+    #[str("io_uring_setup")]
+    IoUringSetup,
+    #[str("process_memory_access")]
+    ProcessMemoryAccess,
+    #[str("seccomp")]
+    Seccomp,
+    #[str("execve_failed")]
+    ExecveFailed,
+    #[str("shell_command")]
+    ShellCommand,
 
     // stuff loaded in kernel
     #[str("init_module")]
@@ -84,6 +99,14 @@ pub enum Type {
     BpfProgLoad,
     #[str("bpf_socket_filter")]
     BpfSocketFilter,
+    #[str("delete_module")]
+    DeleteModule,
+    #[str("kexec_load")]
+    KexecLoad,
+    #[str("reboot")]
+    Reboot,
+    #[str("sysctl_write")]
+    SysctlWrite,
 
     // memory stuffs
     #[str("mprotect_exec")]
@@ -98,9 +121,16 @@ pub enum Type {
     DnsQuery,
     #[str("send_data")]
     SendData,
+    #[str("socket_create")]
+    SocketCreate,
+    #[str("tls_client_hello")]
+    TlsClientHello,
+    #[str("http_request")]
+    HttpRequest,
 
     // filesystem events
-    // mount event was id=80
+    #[str("mount")]
+    Mount = 80,
     #[str("read")]
     Read = 81,
     #[str("read_config")]
@@ -117,11 +147,29 @@ pub enum Type {
     WriteClose,
     #[str("file_create")]
     FileCreate,
+    #[str("umount")]
+    Umount,
+    #[str("file_chmod")]
+    FileChmod,
+    #[str("file_chown")]
+    FileChown,
+    #[str("file_setxattr")]
+    FileSetxattr,
+    #[str("file_removexattr")]
+    FileRemovexattr,
+    #[str("file_utimes")]
+    FileUtimes,
+    #[str("file_link")]
+    FileLink,
+    #[str("file_symlink")]
+    FileSymlink,
 
     // specific userland events
     // those should never be used in eBPF
     #[str("file_scan")]
     FileScan = 500,
+    #[str("encrypted_dns")]
+    EncryptedDns,
 
     // Materialize the end of configurable events
     #[str("end_configurable")]
@@ -161,6 +209,14 @@ pub enum Type {
     Log,
     #[str("syscore_resume")]
     SyscoreResume,
+    #[str("chunk")]
+    Chunk,
+    #[str("cgroup_mkdir")]
+    CgroupMkdir,
+    #[str("cgroup_rmdir")]
+    CgroupRmdir,
+    #[str("lsm_label_change")]
+    LsmLabelChange,
 
     // !!! all new event types must be put before max
     #[str("max")]
@@ -187,6 +243,15 @@ impl Type {
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Namespaces {
     pub mnt: u32,
+    pub net: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Capabilities {
+    pub effective: u64,
+    pub permitted: u64,
+    pub bounding: u64,
 }
 
 #[repr(C)]
@@ -207,6 +272,18 @@ pub struct TaskInfo {
     pub tg_uuid: ProcUuid,
     pub namespaces: Option<Namespaces>,
     pub start_time: u64,
+    pub caps: Capabilities,
+    pub no_new_privs: bool,
+    // SECCOMP_MODE_* value
+    pub seccomp_mode: u32,
+    // audit login uid, u32::MAX if unset
+    pub loginuid: u32,
+    // audit session id, u32::MAX if unset
+    pub sessionid: u32,
+    // pid of the session leader
+    pub sid: i32,
+    // name of the controlling terminal
+    pub tty: String<TTY_NAME_SIZE>,
 }
 
 impl TaskInfo {
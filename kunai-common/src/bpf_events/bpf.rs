@@ -1,3 +1,4 @@
+use super::Capabilities;
 use super::Error;
 use super::Event;
 use super::EventInfo;
@@ -9,6 +10,9 @@ use crate::co_re::task_struct;
 use crate::uuid::Uuid;
 use aya_ebpf::helpers::{bpf_get_current_task, bpf_ktime_get_ns};
 
+// PFA_NO_NEW_PRIVS is bit 0 of task_struct.atomic_flags
+const PFA_NO_NEW_PRIVS: u64 = 1 << 0;
+
 impl<T> Event<T> {
     #[inline(always)]
     pub unsafe fn init_from_current_task(&mut self, ty: Type) -> Result<(), Error> {
@@ -75,6 +79,28 @@ impl TaskInfo {
         self.uid = task.cred().ok_or(Error::CredFieldMissing)?.uid();
         self.gid = task.cred().ok_or(Error::CredFieldMissing)?.gid();
 
+        let cred = task.cred().ok_or(Error::CredFieldMissing)?;
+        self.caps = Capabilities {
+            effective: cred.cap_effective().ok_or(Error::CapFieldMissing)?,
+            permitted: cred.cap_permitted().ok_or(Error::CapFieldMissing)?,
+            bounding: cred.cap_bounding().ok_or(Error::CapFieldMissing)?,
+        };
+
+        self.no_new_privs =
+            task.atomic_flags().unwrap_or_default() & PFA_NO_NEW_PRIVS == PFA_NO_NEW_PRIVS;
+        // seccomp member is missing if kernel is built without CONFIG_SECCOMP
+        self.seccomp_mode = task.seccomp_mode().unwrap_or_default() as u32;
+
+        // loginuid and sessionid are missing if kernel is built without CONFIG_AUDIT
+        self.loginuid = task.loginuid().unwrap_or(u32::MAX);
+        self.sessionid = task.sessionid().unwrap_or(u32::MAX);
+
+        if let Some(signal) = task.signal() {
+            self.sid = signal.session_nr().unwrap_or_default();
+            // tty is null if task has no controlling terminal
+            self.tty = core_read_kernel!(signal, tty, name_str).unwrap_or_default();
+        }
+
         if let Some(nsproxy) = core_read_kernel!(task, nsproxy) {
             // it may happen that under some very specific conditions nsproxy
             // gets null (see https://github.com/kunai-project/kunai/issues/34)
@@ -82,6 +108,7 @@ impl TaskInfo {
                 self.namespaces = Some(Namespaces {
                     mnt: core_read_kernel!(nsproxy, mnt_ns, ns, inum)
                         .ok_or(Error::MntNamespaceFailure)?,
+                    net: core_read_kernel!(nsproxy, net_ns, ns, inum).unwrap_or_default(),
                 });
             }
         }
@@ -16,8 +16,14 @@ mod dns_query;
 pub use dns_query::*;
 mod send_entropy;
 pub use send_entropy::*;
+mod socket_create;
+pub use socket_create::*;
 mod init_module;
 pub use init_module::*;
+mod io_uring;
+pub use io_uring::*;
+mod kernel_tamper;
+pub use kernel_tamper::*;
 mod exit;
 pub use exit::*;
 mod fs;
@@ -34,10 +40,28 @@ pub mod log;
 pub use log::{LogData, LogEvent};
 mod syscore_resume;
 pub use syscore_resume::*;
+mod chunk;
+pub use chunk::*;
+mod cgroup;
+pub use cgroup::*;
+mod lsm_label;
+pub use lsm_label::*;
 mod kill;
 pub use kill::*;
 mod ptrace;
 pub use ptrace::*;
+mod process_memory;
+pub use process_memory::*;
+mod seccomp;
+pub use seccomp::*;
+mod sysctl;
+pub use sysctl::*;
+mod shell;
+pub use shell::*;
+mod http;
+pub use http::*;
+mod tls;
+pub use tls::*;
 pub mod error;
 pub use error::*;
 mod loss;
@@ -75,14 +99,26 @@ const fn max_bpf_event_size() -> usize {
             Type::Prctl => PrctlEvent::size_of(),
             Type::Kill => KillEvent::size_of(),
             Type::Ptrace => PtraceEvent::size_of(),
+            Type::IoUringSetup => IoUringSetupEvent::size_of(),
+            Type::ProcessMemoryAccess => ProcessMemoryAccessEvent::size_of(),
+            Type::Seccomp => SeccompEvent::size_of(),
+            Type::ExecveFailed => ExecveFailedEvent::size_of(),
+            Type::ShellCommand => ShellCommandEvent::size_of(),
             Type::InitModule => InitModuleEvent::size_of(),
             Type::BpfProgLoad => BpfProgLoadEvent::size_of(),
             Type::BpfSocketFilter => BpfSocketFilterEvent::size_of(),
+            Type::DeleteModule => DeleteModuleEvent::size_of(),
+            Type::KexecLoad => KexecLoadEvent::size_of(),
+            Type::Reboot => RebootEvent::size_of(),
+            Type::SysctlWrite => SysctlWriteEvent::size_of(),
             Type::MprotectExec => MprotectEvent::size_of(),
             Type::MmapExec => MmapExecEvent::size_of(),
             Type::Connect => ConnectEvent::size_of(),
             Type::DnsQuery => DnsQueryEvent::size_of(),
             Type::SendData => SendEntropyEvent::size_of(),
+            Type::SocketCreate => SocketCreateEvent::size_of(),
+            Type::TlsClientHello => TlsClientHelloEvent::size_of(),
+            Type::HttpRequest => HttpRequestEvent::size_of(),
             Type::Read
             | Type::ReadConfig
             | Type::Write
@@ -91,18 +127,29 @@ const fn max_bpf_event_size() -> usize {
             | Type::FileCreate => FileEvent::size_of(),
             Type::FileRename => FileRenameEvent::size_of(),
             Type::FileUnlink => UnlinkEvent::size_of(),
+            Type::Mount | Type::Umount => MountEvent::size_of(),
+            Type::FileChmod => FileChmodEvent::size_of(),
+            Type::FileChown => FileChownEvent::size_of(),
+            Type::FileSetxattr | Type::FileRemovexattr => FileXattrEvent::size_of(),
+            Type::FileUtimes => FileUtimesEvent::size_of(),
+            Type::FileLink => FileLinkEvent::size_of(),
+            Type::FileSymlink => FileSymlinkEvent::size_of(),
             Type::Log => LogEvent::size_of(),
             Type::Start => StatusEvent::size_of(),
             Type::Loss => LossEvent::size_of(),
             Type::Error => ErrorEvent::size_of(),
             Type::SyscoreResume => SysCoreResumeEvent::size_of(),
+            Type::Chunk => ChunkEvent::size_of(),
+            Type::CgroupMkdir | Type::CgroupRmdir => CgroupEvent::size_of(),
+            Type::LsmLabelChange => LsmLabelChangeEvent::size_of(),
             // these are event types only used in user land
             Type::Unknown
             | Type::EndConfigurable
             | Type::Correlation
             | Type::CacheHash
             | Type::Max
-            | Type::FileScan => 0,
+            | Type::FileScan
+            | Type::EncryptedDns => 0,
             // never handle _ pattern otherwise this function loses all interest
         };
         if size > max {
@@ -0,0 +1,9 @@
+use crate::bpf_events::Event;
+use crate::cgroup::Cgroup;
+
+pub type CgroupEvent = Event<CgroupData>;
+
+#[repr(C)]
+pub struct CgroupData {
+    pub cgroup: Cgroup,
+}
@@ -0,0 +1,193 @@
+use crate::bpf_events::Event;
+use crate::buffer::Buffer;
+use crate::macros::not_bpf_target_code;
+
+pub const CHUNK_SIZE: usize = 2048;
+// maximum number of chunks sent for argv
+pub const MAX_ARGV_CHUNKS: u32 = 32;
+// maximum number of chunks sent for the environment
+pub const MAX_ENV_CHUNKS: u32 = 16;
+// maximum number of chunks sent for a path, paths are resolved
+// MAX_PATH_LEN bytes at a time so this is enough to cover PATH_MAX
+pub const MAX_PATH_CHUNKS: u32 = 4;
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkKind {
+    Argv = 0,
+    Executable,
+    Interpreter,
+    Env,
+}
+
+/// ChunkEvent carries the part of some data which does not
+/// fit into the event it belongs to. Chunks are sent before
+/// that event and are reassembled in userland.
+pub type ChunkEvent = Event<ChunkData>;
+
+#[repr(C)]
+pub struct ChunkData {
+    // identifies the event chunks belong to
+    pub id: u64,
+    pub kind: ChunkKind,
+    // position of the chunk in the sequence
+    pub index: u32,
+    pub data: Buffer<CHUNK_SIZE>,
+}
+
+not_bpf_target_code! {
+    use std::collections::BTreeMap;
+
+    /// Chunks of data waiting to be reassembled
+    #[derive(Debug, Default, Clone)]
+    pub struct Chunks {
+        chunks: BTreeMap<u32, Vec<u8>>,
+    }
+
+    impl Chunks {
+        /// Inserts a chunk, a chunk of index zero starts a new sequence
+        pub fn insert(&mut self, c: &ChunkData) {
+            if c.index == 0 {
+                self.chunks.clear();
+            }
+            self.chunks.insert(c.index, c.data.as_slice().to_vec());
+        }
+
+        /// Returns `true` if no chunk is missing in the sequence
+        pub fn is_complete(&self) -> bool {
+            self.chunks.keys().enumerate().all(|(i, &k)| i as u32 == k)
+        }
+
+        /// Returns the contiguous chunks in order
+        fn contiguous(&self) -> Vec<&Vec<u8>> {
+            (0..).map_while(|i| self.chunks.get(&i)).collect()
+        }
+
+        /// Appends chunks to `head`, data is appended up to `max_len`
+        pub fn append_to(&self, head: &[u8], max_len: usize) -> Vec<u8> {
+            let mut out = head.to_vec();
+            for c in self.contiguous() {
+                out.extend_from_slice(c);
+            }
+            out.truncate(max_len);
+            out
+        }
+
+        /// Rebuilds a path whose resolution has been split into chunks.
+        /// Paths are resolved from their last component so `head` is
+        /// the beginning of the path and chunks must be prepended in
+        /// reverse order.
+        pub fn join_path(&self, head: &[u8]) -> Vec<u8> {
+            let mut out = head.to_vec();
+            for c in self.contiguous().into_iter().rev() {
+                if !out.ends_with(b"/") && !c.starts_with(b"/") {
+                    out.push(b'/');
+                }
+                out.extend_from_slice(c);
+            }
+            out
+        }
+    }
+
+    /// Chunks sent for an execve event
+    #[derive(Debug, Default, Clone)]
+    pub struct ExecveChunks {
+        id: u64,
+        pub argv: Chunks,
+        pub executable: Chunks,
+        pub interpreter: Chunks,
+        pub env: Chunks,
+    }
+
+    impl ExecveChunks {
+        /// Inserts a chunk, chunks belonging to another execve
+        /// (i.e. one which failed) are discarded
+        pub fn insert(&mut self, c: &ChunkData) {
+            if c.id != self.id {
+                *self = Self {
+                    id: c.id,
+                    ..Default::default()
+                };
+            }
+
+            match c.kind {
+                ChunkKind::Argv => self.argv.insert(c),
+                ChunkKind::Executable => self.executable.insert(c),
+                ChunkKind::Interpreter => self.interpreter.insert(c),
+                ChunkKind::Env => self.env.insert(c),
+            }
+        }
+
+        /// Returns `true` if chunks belong to the execve identified by `id`
+        pub fn belongs_to(&self, id: u64) -> bool {
+            self.id == id
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chunk(kind: ChunkKind, index: u32, data: &[u8]) -> ChunkData {
+        chunk_with_id(1, kind, index, data)
+    }
+
+    fn chunk_with_id(id: u64, kind: ChunkKind, index: u32, data: &[u8]) -> ChunkData {
+        let mut c = ChunkData {
+            id,
+            kind,
+            index,
+            data: Buffer::new(),
+        };
+        c.data.copy_from_slice(data);
+        c
+    }
+
+    #[test]
+    fn test_append() {
+        let mut chunks = Chunks::default();
+        chunks.insert(&chunk(ChunkKind::Argv, 0, b"b\0c"));
+        chunks.insert(&chunk(ChunkKind::Argv, 1, b"c\0d"));
+        assert!(chunks.is_complete());
+        assert_eq!(chunks.append_to(b"a\0", 1024), b"a\0b\0cc\0d");
+        assert_eq!(chunks.append_to(b"a\0", 4), b"a\0b\0");
+
+        // missing chunk
+        chunks.insert(&chunk(ChunkKind::Argv, 3, b"e"));
+        assert!(!chunks.is_complete());
+        assert_eq!(chunks.append_to(b"a\0", 1024), b"a\0b\0cc\0d");
+
+        // new sequence
+        chunks.insert(&chunk(ChunkKind::Argv, 0, b"x"));
+        assert!(chunks.is_complete());
+        assert_eq!(chunks.append_to(b"", 1024), b"x");
+    }
+
+    #[test]
+    fn test_join_path() {
+        let mut chunks = Chunks::default();
+        // chunks come from the end of the path
+        chunks.insert(&chunk(ChunkKind::Executable, 0, b"/file"));
+        chunks.insert(&chunk(ChunkKind::Executable, 1, b"dir"));
+        assert_eq!(chunks.join_path(b"/root"), b"/root/dir/file");
+        assert_eq!(chunks.join_path(b"/"), b"/dir/file");
+    }
+
+    #[test]
+    fn test_failed_execve_chunks() {
+        let mut chunks = ExecveChunks::default();
+
+        // chunks of an execve which failed are never consumed
+        chunks.insert(&chunk_with_id(1, ChunkKind::Executable, 0, b"/failed"));
+        chunks.insert(&chunk_with_id(1, ChunkKind::Argv, 0, b"stale"));
+
+        // chunks of the next successful execve
+        chunks.insert(&chunk_with_id(2, ChunkKind::Argv, 0, b"b\0c"));
+
+        assert!(!chunks.belongs_to(1));
+        assert!(chunks.belongs_to(2));
+        assert_eq!(chunks.argv.append_to(b"a\0", 1024), b"a\0b\0c");
+        assert_eq!(chunks.executable.join_path(b"/bin/ls"), b"/bin/ls");
+    }
+}
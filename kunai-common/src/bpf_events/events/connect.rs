@@ -11,4 +11,6 @@ pub struct ConnectData {
     pub src: SockAddr,
     pub dst: SockAddr,
     pub connected: bool,
+    // connection initiated through io_uring
+    pub io_uring: bool,
 }
@@ -22,6 +22,8 @@ pub struct DnsQueryData {
     pub data: Buffer<DNS_MAX_PACKET_SIZE>,
     pub tcp_header: bool,
     pub error: DnsError,
+    // data received through io_uring
+    pub io_uring: bool,
 }
 
 pub type DnsQueryEvent = Event<DnsQueryData>;
@@ -50,6 +52,8 @@ not_bpf_target_code! {
     pub struct DnsResponse {
         pub question: String,
         pub answers: Vec<String>,
+        // smallest TTL (in seconds) of the answers
+        pub ttl: Option<u32>,
     }
 
     impl DnsQueryData {
@@ -82,13 +86,19 @@ not_bpf_target_code! {
 
             // we go through the list of domains and collect A and AAAA records
             for d in domains {
-                let answers = packet
+                let records = packet
                 .answers
                 .iter()
                 .filter(|r| {
                     matches!(r.data, RData::A(_) | RData::AAAA(_) | RData::CNAME(_))
                     && r.name.to_string() == d
                 })
+                .collect::<Vec<_>>();
+
+                let ttl = records.iter().map(|r| r.ttl).min();
+
+                let answers = records
+                .iter()
                 .map(|r| match r.data {
                     RData::A(ip) => ip.0.to_string(),
                     RData::AAAA(ip) => ip.0.to_string(),
@@ -102,6 +112,7 @@ not_bpf_target_code! {
                 out.push(DnsResponse {
                     question: d,
                     answers,
+                    ttl,
                 });
             }
             Ok(out)
@@ -1,18 +1,164 @@
 use crate::bpf_events::{Event, Nodename};
 use crate::buffer::Buffer;
 use crate::cgroup::Cgroup;
-use crate::path::Path;
+use crate::macros::not_bpf_target_code;
+use crate::net::{SockAddr, SocketInfo};
+use crate::path::{Path, MAX_PATH_LEN};
+use crate::string::String;
 
 pub const MAX_ARGV_SIZE: usize = 512;
+pub const MAX_ENV_SIZE: usize = 4096;
 
 pub type ExecveEvent = Event<ExecveData>;
 
+// number of standard file descriptors (stdin, stdout, stderr)
+pub const STDIO_FDS: usize = 3;
+
+/// Information about the file behind a standard file descriptor
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdioFd {
+    pub open: bool,
+    // inode i_mode
+    pub mode: u16,
+    // inode i_rdev, used to identify ttys
+    pub rdev: u32,
+    pub path: Path,
+    pub socket: Option<SocketInfo>,
+    pub dst: Option<SockAddr>,
+}
+
+const S_IFMT: u16 = 0o170000;
+const S_IFSOCK: u16 = 0o140000;
+const S_IFIFO: u16 = 0o010000;
+
+impl StdioFd {
+    #[inline(always)]
+    pub fn is_sock(&self) -> bool {
+        self.mode & S_IFMT == S_IFSOCK
+    }
+
+    #[inline(always)]
+    pub fn is_fifo(&self) -> bool {
+        self.mode & S_IFMT == S_IFIFO
+    }
+}
+
+not_bpf_target_code! {
+    const S_IFREG: u16 = 0o100000;
+    const S_IFBLK: u16 = 0o060000;
+    const S_IFDIR: u16 = 0o040000;
+    const S_IFCHR: u16 = 0o020000;
+
+    // major numbers of tty devices (see Documentation/admin-guide/devices.txt)
+    const PTY_SLAVE_MAJOR: u32 = 3;
+    const TTY_MAJOR: u32 = 4;
+    const TTYAUX_MAJOR: u32 = 5;
+    const UNIX98_PTY_SLAVE_MAJOR: u32 = 136;
+    const UNIX98_PTY_MAJOR_COUNT: u32 = 8;
+
+    impl StdioFd {
+        #[inline]
+        fn is_tty(&self) -> bool {
+            // kernel dev_t encoding: 12 bits of major and 20 bits of minor
+            let major = self.rdev >> 20;
+            matches!(major, PTY_SLAVE_MAJOR | TTY_MAJOR | TTYAUX_MAJOR)
+                || (UNIX98_PTY_SLAVE_MAJOR..UNIX98_PTY_SLAVE_MAJOR + UNIX98_PTY_MAJOR_COUNT)
+                    .contains(&major)
+        }
+
+        /// Returns the kind of file behind the descriptor
+        pub fn kind(&self) -> &'static str {
+            if !self.open {
+                return "closed";
+            }
+
+            match self.mode & S_IFMT {
+                S_IFSOCK => "socket",
+                S_IFIFO => "pipe",
+                S_IFREG => "file",
+                S_IFDIR => "directory",
+                S_IFCHR if self.is_tty() => "tty",
+                S_IFCHR => "char_device",
+                S_IFBLK => "block_device",
+                // anonymous inodes (eventfd, memfd_secret ...) have no file type
+                _ => "other",
+            }
+        }
+    }
+}
+
 #[repr(C)]
 pub struct ExecveData {
     pub executable: Path,
     pub interpreter: Path,
     pub argv: Buffer<MAX_ARGV_SIZE>,
+    // size of argv in process memory, what does not fit
+    // in argv is sent as chunks up to a configurable limit
+    pub argv_len: u32,
+    // NUL separated KEY=VALUE strings, only
+    // filled if environment capture is enabled
+    pub env: Buffer<MAX_ENV_SIZE>,
+    // size of the environment in process memory, what
+    // does not fit in env is sent as chunks
+    pub env_len: u32,
+    pub cwd: Path,
+    pub stdio: [StdioFd; STDIO_FDS],
     pub cgroup: Cgroup,
     pub nodename: Nodename,
     pub rc: i32,
+    // set if argv, env or paths could not be sent entirely
+    pub truncated: bool,
+    // identifies the chunks sent for this execve
+    pub chunk_id: u64,
+}
+
+// maximum number of arguments reported for a failed execve
+pub const MAX_FAILED_ARGS: usize = 16;
+// maximum length of an argument reported for a failed execve
+pub const MAX_FAILED_ARG_LEN: usize = 128;
+
+pub type ExecveFailedEvent = Event<ExecveFailedData>;
+
+#[repr(C)]
+pub struct ExecveFailedData {
+    // filename as passed to execve(at)
+    pub filename: String<MAX_PATH_LEN>,
+    // directory a relative filename is looked up from,
+    // either current working directory or execveat dirfd
+    pub dir: Path,
+    pub argv: [String<MAX_FAILED_ARG_LEN>; MAX_FAILED_ARGS],
+    // number of arguments read
+    pub argc: u32,
+    // set if some arguments could not be reported
+    pub truncated: bool,
+    pub rc: i64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stdio_kind() {
+        let mut fd = StdioFd::default();
+        assert_eq!(fd.kind(), "closed");
+
+        fd.open = true;
+        fd.mode = S_IFCHR | 0o620;
+        // /dev/pts/1
+        fd.rdev = (136 << 20) | 1;
+        assert_eq!(fd.kind(), "tty");
+        // /dev/null
+        fd.rdev = (1 << 20) | 3;
+        assert_eq!(fd.kind(), "char_device");
+
+        fd.mode = S_IFIFO | 0o600;
+        assert!(fd.is_fifo());
+        assert_eq!(fd.kind(), "pipe");
+
+        fd.mode = S_IFSOCK | 0o777;
+        assert!(fd.is_sock());
+        assert_eq!(fd.kind(), "socket");
+    }
 }
@@ -1,8 +1,68 @@
 use crate::bpf_events::Event;
+use crate::macros::not_bpf_target_code;
 
 pub type ExitEvent = Event<ExitData>;
 
 #[repr(C)]
 pub struct ExitData {
     pub error_code: u64,
+    // task exit_code set by the kernel, it encodes
+    // either exit status or terminating signal
+    pub exit_code: i32,
+    // time elapsed since task start in ns
+    pub lifetime: u64,
+    // cpu time spent in user and kernel mode in ns
+    pub utime: u64,
+    pub stime: u64,
+    // max resident set size in pages, only
+    // known when the whole thread group is dead
+    pub maxrss: u64,
+    // number of regular files written by the process
+    pub file_writes: u64,
+}
+
+not_bpf_target_code! {
+    impl ExitData {
+        /// Returns the exit status if the task exited normally
+        pub fn exit_status(&self) -> Option<i32> {
+            (self.exit_code & 0x7f == 0).then_some((self.exit_code >> 8) & 0xff)
+        }
+
+        /// Returns the signal which terminated the task if any
+        pub fn signal(&self) -> Option<u32> {
+            let sig = self.exit_code & 0x7f;
+            (sig != 0).then_some(sig as u32)
+        }
+
+        /// Returns `true` if the task dumped a core
+        pub fn core_dumped(&self) -> bool {
+            self.exit_code & 0x80 == 0x80
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        let mut data = ExitData {
+            error_code: 0,
+            exit_code: 2 << 8,
+            lifetime: 0,
+            utime: 0,
+            stime: 0,
+            maxrss: 0,
+            file_writes: 0,
+        };
+        assert_eq!(data.exit_status(), Some(2));
+        assert_eq!(data.signal(), None);
+
+        // SIGSEGV with core dump
+        data.exit_code = 11 | 0x80;
+        assert_eq!(data.exit_status(), None);
+        assert_eq!(data.signal(), Some(11));
+        assert!(data.core_dumped());
+    }
 }
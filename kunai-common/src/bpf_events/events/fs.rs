@@ -1,11 +1,15 @@
 use crate::bpf_events::Event;
-use crate::path::Path;
+use crate::path::{Path, MAX_PATH_LEN};
+use crate::string::String;
+use crate::time::Time;
 
 pub type FileEvent = Event<FileData>;
 
 #[repr(C)]
 pub struct FileData {
     pub path: Path,
+    // operation done through io_uring
+    pub io_uring: bool,
 }
 
 pub type FileRenameEvent = Event<FileRenameData>;
@@ -23,3 +27,116 @@ pub struct UnlinkData {
     pub path: Path,
     pub success: bool,
 }
+
+pub type FileLinkEvent = Event<FileLinkData>;
+
+#[repr(C)]
+pub struct FileLinkData {
+    // new hardlink being created
+    pub link: Path,
+    // existing file the link points to
+    pub target: Path,
+    // owner and mode of the target inode
+    pub uid: u32,
+    pub gid: u32,
+    pub mode: u32,
+}
+
+pub type FileSymlinkEvent = Event<FileSymlinkData>;
+
+#[repr(C)]
+pub struct FileSymlinkData {
+    // new symlink being created
+    pub link: Path,
+    // content of the symlink as given by the user, it
+    // may be relative and does not need to exist
+    pub target: String<MAX_PATH_LEN>,
+}
+
+const S_IFMT: u32 = 0o170000;
+const S_IFREG: u32 = 0o100000;
+const S_IXUGO: u32 = 0o111;
+
+pub type FileChmodEvent = Event<FileChmodData>;
+
+#[repr(C)]
+pub struct FileChmodData {
+    pub path: Path,
+    pub old_mode: u32,
+    pub new_mode: u32,
+}
+
+impl FileChmodData {
+    /// Returns true if a regular file without any execute
+    /// permission gets at least one
+    #[inline(always)]
+    pub fn became_executable(&self) -> bool {
+        self.old_mode & S_IFMT == S_IFREG
+            && self.old_mode & S_IXUGO == 0
+            && self.new_mode & S_IXUGO != 0
+    }
+}
+
+pub type FileChownEvent = Event<FileChownData>;
+
+#[repr(C)]
+pub struct FileChownData {
+    pub path: Path,
+    pub old_uid: u32,
+    pub old_gid: u32,
+    // u32::MAX if left unchanged
+    pub new_uid: u32,
+    // u32::MAX if left unchanged
+    pub new_gid: u32,
+}
+
+pub type FileXattrEvent = Event<FileXattrData>;
+
+#[repr(C)]
+pub struct FileXattrData {
+    pub path: Path,
+    pub name: String<256>,
+    // size of the value set (always 0 for removexattr)
+    pub size: u64,
+    pub flags: u32,
+}
+
+pub const UTIME_NOW: i64 = (1 << 30) - 1;
+pub const UTIME_OMIT: i64 = (1 << 30) - 2;
+
+pub type FileUtimesEvent = Event<FileUtimesData>;
+
+#[repr(C)]
+pub struct FileUtimesData {
+    pub path: Path,
+    // nsec may be UTIME_NOW or UTIME_OMIT
+    pub atime: Time,
+    pub mtime: Time,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_became_executable() {
+        let chmod = |old_mode: u32, new_mode: u32| FileChmodData {
+            path: Path::default(),
+            old_mode,
+            new_mode,
+        };
+
+        // non executable regular file becomes executable
+        assert!(chmod(S_IFREG | 0o644, 0o744).became_executable());
+        assert!(chmod(S_IFREG | 0o644, 0o645).became_executable());
+        // already executable
+        assert!(!chmod(S_IFREG | 0o744, 0o755).became_executable());
+        assert!(!chmod(S_IFREG | 0o700, 0o600).became_executable());
+        // setuid/setgid bits alone do not make a file executable
+        assert!(!chmod(S_IFREG | 0o644, 0o6644).became_executable());
+        assert!(chmod(S_IFREG | 0o644, 0o4755).became_executable());
+        assert!(!chmod(S_IFREG | 0o4755, 0o755).became_executable());
+        // only regular files are considered
+        assert!(!chmod(0o040000 | 0o644, 0o755).became_executable());
+    }
+}
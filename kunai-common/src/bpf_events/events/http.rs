@@ -0,0 +1,76 @@
+use crate::bpf_events::Event;
+use crate::buffer::Buffer;
+use crate::net::{SockAddr, SocketInfo};
+
+use super::ENCRYPT_DATA_MAX_BUFFER_SIZE;
+
+// HTTP/1.x methods a request can start with
+const HTTP_METHODS: [&[u8]; 9] = [
+    b"GET ",
+    b"POST ",
+    b"PUT ",
+    b"HEAD ",
+    b"DELETE ",
+    b"OPTIONS ",
+    b"PATCH ",
+    b"CONNECT ",
+    b"TRACE ",
+];
+
+pub type HttpRequestEvent = Event<HttpRequestData>;
+
+#[repr(C)]
+pub struct HttpRequestData {
+    pub socket: SocketInfo,
+    pub src: SockAddr,
+    pub dst: SockAddr,
+    // beginning of the data sent, request is parsed in userland
+    pub data: Buffer<ENCRYPT_DATA_MAX_BUFFER_SIZE>,
+    // data sent through io_uring
+    pub io_uring: bool,
+    // plaintext data captured from a TLS library
+    pub tls: bool,
+    // request received rather than sent
+    pub inbound: bool,
+}
+
+/// Returns `true` if data in `b` starts with an HTTP/1.x method
+#[inline(always)]
+pub fn is_http_request<const N: usize>(b: &Buffer<N>) -> bool {
+    for m in HTTP_METHODS {
+        if b.len() < m.len() || m.len() > N {
+            continue;
+        }
+
+        let mut found = true;
+        for (i, &c) in m.iter().enumerate() {
+            if b[i] != c {
+                found = false;
+                break;
+            }
+        }
+
+        if found {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_http_request() {
+        let mut b = Buffer::<64>::new();
+        b.copy_from_slice(b"GET / HTTP/1.1\r\n");
+        assert!(is_http_request(&b));
+        b.copy_from_slice(b"OPTIONS * HTTP/1.1\r\n");
+        assert!(is_http_request(&b));
+        b.copy_from_slice(b"GETS / HTTP/1.1\r\n");
+        assert!(!is_http_request(&b));
+        b.copy_from_slice(b"\x16\x03\x01");
+        assert!(!is_http_request(&b));
+    }
+}
@@ -0,0 +1,25 @@
+use crate::bpf_events::Event;
+
+// the kernel polls the submission queue from a kernel thread
+pub const IORING_SETUP_SQPOLL: u32 = 1 << 1;
+
+pub type IoUringSetupEvent = Event<IoUringSetupData>;
+
+#[repr(C)]
+pub struct IoUringSetupData {
+    // number of submission queue entries requested
+    pub entries: u32,
+    // io_uring_params members set by userland
+    pub flags: u32,
+    pub sq_thread_cpu: u32,
+    pub sq_thread_idle: u32,
+    // io_uring file descriptor or error
+    pub rc: i64,
+}
+
+impl IoUringSetupData {
+    #[inline(always)]
+    pub fn is_sq_poll(&self) -> bool {
+        self.flags & IORING_SETUP_SQPOLL == IORING_SETUP_SQPOLL
+    }
+}
@@ -0,0 +1,104 @@
+use crate::bpf_events::Event;
+use crate::path::Path;
+use crate::string::String;
+use kunai_macros::StrEnum;
+
+// delete_module syscall uses O_TRUNC as force flag
+const DELETE_MODULE_FORCE: u64 = 0o1000;
+
+pub type DeleteModuleEvent = Event<DeleteModuleData>;
+
+#[repr(C)]
+pub struct DeleteModuleData {
+    pub name: String<64>,
+    pub flags: u64,
+    pub rc: i64,
+}
+
+impl DeleteModuleData {
+    #[inline(always)]
+    pub fn is_forced(&self) -> bool {
+        self.flags & DELETE_MODULE_FORCE == DELETE_MODULE_FORCE
+    }
+}
+
+pub type KexecLoadEvent = Event<KexecLoadData>;
+
+#[repr(C)]
+pub struct KexecLoadData {
+    // set to true if event comes from kexec_file_load
+    pub file_load: bool,
+    // kernel image (kexec_file_load only)
+    pub kernel: Path,
+    // kernel command line (kexec_file_load only)
+    pub cmdline: String<256>,
+    // number of segments (kexec_load only)
+    pub nr_segments: u64,
+    pub flags: u64,
+    pub rc: i64,
+}
+
+impl KexecLoadData {
+    pub const fn syscall_name(&self) -> &'static str {
+        if self.file_load {
+            "kexec_file_load"
+        } else {
+            "kexec_load"
+        }
+    }
+}
+
+pub type RebootEvent = Event<RebootData>;
+
+#[repr(C)]
+pub struct RebootData {
+    pub magic1: u32,
+    pub magic2: u32,
+    pub cmd: u32,
+}
+
+// magic values checked by the reboot syscall
+const LINUX_REBOOT_MAGIC1: u32 = 0xfee1dead;
+const LINUX_REBOOT_MAGIC2: &[u32] = &[672274793, 85072278, 369367448, 537993216];
+
+impl RebootData {
+    /// Returns true if magic values are the ones expected
+    /// by the kernel, otherwise the syscall fails.
+    #[inline(always)]
+    pub fn has_valid_magic(&self) -> bool {
+        self.magic1 == LINUX_REBOOT_MAGIC1 && LINUX_REBOOT_MAGIC2.contains(&self.magic2)
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+#[derive(StrEnum, Debug, PartialEq, Eq)]
+/// Commands accepted by reboot syscall
+pub enum RebootCmd {
+    LINUX_REBOOT_CMD_CAD_OFF = 0x00000000,
+    LINUX_REBOOT_CMD_RESTART = 0x01234567,
+    LINUX_REBOOT_CMD_KEXEC = 0x45584543,
+    LINUX_REBOOT_CMD_POWER_OFF = 0x4321FEDC,
+    LINUX_REBOOT_CMD_CAD_ON = 0x89ABCDEF,
+    LINUX_REBOOT_CMD_RESTART2 = 0xA1B2C3D4,
+    LINUX_REBOOT_CMD_HALT = 0xCDEF0123,
+    LINUX_REBOOT_CMD_SW_SUSPEND = 0xD000FCE2,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reboot_magic() {
+        let mut data = RebootData {
+            magic1: LINUX_REBOOT_MAGIC1,
+            magic2: 672274793,
+            cmd: RebootCmd::LINUX_REBOOT_CMD_RESTART as u32,
+        };
+        assert!(data.has_valid_magic());
+
+        data.magic2 = 0x41414141;
+        assert!(!data.has_valid_magic());
+    }
+}
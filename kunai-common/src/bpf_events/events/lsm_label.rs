@@ -0,0 +1,6 @@
+use crate::bpf_events::Event;
+
+pub type LsmLabelChangeEvent = Event<LsmLabelChangeData>;
+
+#[repr(C)]
+pub struct LsmLabelChangeData {}
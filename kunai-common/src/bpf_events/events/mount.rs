@@ -2,12 +2,20 @@ use crate::bpf_events::Event;
 use crate::path::Path;
 use crate::string::String;
 
+// same event structure is used for mount and umount
 pub type MountEvent = Event<MountData>;
 
 #[repr(C)]
 pub struct MountData {
+    // source device of the mount
     pub dev_name: String<1024>,
+    // mountpoint
     pub path: Path,
+    // filesystem type
     pub ty: String<64>,
+    // mount(2) or umount2(2) flags depending on event type
+    pub flags: u64,
+    // inode number of the mount namespace the change happened in
+    pub mnt_ns: u32,
     pub rc: i32,
 }
@@ -0,0 +1,33 @@
+use crate::bpf_events::{Event, TaskInfo};
+use kunai_macros::StrEnum;
+
+#[repr(u32)]
+#[derive(StrEnum, Debug, Clone, Copy, PartialEq, Eq)]
+/// How the memory of another process has been accessed
+pub enum MemoryAccessMethod {
+    #[str("process_vm_readv")]
+    ProcessVmReadv,
+    #[str("process_vm_writev")]
+    ProcessVmWritev,
+    #[str("proc_pid_mem")]
+    ProcPidMem,
+}
+
+pub type ProcessMemoryAccessEvent = Event<ProcessMemoryAccessData>;
+
+#[repr(C)]
+pub struct ProcessMemoryAccessData {
+    pub method: MemoryAccessMethod,
+    pub write: bool,
+    pub target: TaskInfo,
+    // number of bytes transferred or error, for a /proc/<pid>/mem
+    // open denied this is the result of the access check
+    pub rc: i64,
+}
+
+impl ProcessMemoryAccessData {
+    #[inline(always)]
+    pub fn has_target(&self) -> bool {
+        self.target.pid != 0
+    }
+}
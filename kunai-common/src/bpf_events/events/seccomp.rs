@@ -0,0 +1,31 @@
+use crate::bpf_events::Event;
+use crate::buffer::Buffer;
+
+pub const SECCOMP_MODE_STRICT: u32 = 1;
+pub const SECCOMP_MODE_FILTER: u32 = 2;
+
+// room for 256 sock_filter instructions
+pub const MAX_SECCOMP_FILTER_SIZE: usize = 2048;
+
+pub type SeccompEvent = Event<SeccompData>;
+
+#[repr(C)]
+pub struct SeccompData {
+    // SECCOMP_MODE_* being set
+    pub mode: u32,
+    // SECCOMP_FILTER_FLAG_* (only for seccomp syscall)
+    pub flags: u32,
+    pub filter: Buffer<MAX_SECCOMP_FILTER_SIZE>,
+    // size in sock_filter blocks
+    pub filter_len: u16,
+    // filter did not fit in buffer, only its beginning is there
+    pub filter_truncated: bool,
+    pub rc: i64,
+}
+
+impl SeccompData {
+    #[inline(always)]
+    pub fn is_filter(&self) -> bool {
+        self.mode == SECCOMP_MODE_FILTER
+    }
+}
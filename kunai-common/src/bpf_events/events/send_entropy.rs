@@ -12,6 +12,8 @@ pub struct SendEntropyData {
     pub freq: [u32; 256],
     pub freq_sum: u32,
     pub real_data_size: u64,
+    // data sent through io_uring
+    pub io_uring: bool,
 }
 
 pub type SendEntropyEvent = Event<SendEntropyData>;
@@ -0,0 +1,12 @@
+use crate::bpf_events::Event;
+use crate::string::String;
+
+pub const MAX_SHELL_LINE_LEN: usize = 1024;
+
+pub type ShellCommandEvent = Event<ShellCommandData>;
+
+#[repr(C)]
+pub struct ShellCommandData {
+    // line as returned by the line editor of the shell
+    pub line: String<MAX_SHELL_LINE_LEN>,
+}
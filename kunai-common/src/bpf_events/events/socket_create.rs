@@ -0,0 +1,9 @@
+use crate::{bpf_events::Event, net::SocketInfo};
+
+pub type SocketCreateEvent = Event<SocketCreateData>;
+
+#[repr(C)]
+pub struct SocketCreateData {
+    // family, type and protocol as passed to socket(2)
+    pub socket: SocketInfo,
+}
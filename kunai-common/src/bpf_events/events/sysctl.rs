@@ -0,0 +1,20 @@
+use crate::bpf_events::Event;
+use crate::buffer::Buffer;
+use crate::path::Path;
+
+pub const SYSCTL_VALUE_MAX: usize = 256;
+
+pub type SysctlWriteEvent = Event<SysctlWriteData>;
+
+#[repr(C)]
+pub struct SysctlWriteData {
+    pub path: Path,
+    // buffer written by the task
+    pub new_value: Buffer<SYSCTL_VALUE_MAX>,
+    // raw ctl_table data before the write, its
+    // layout depends on the sysctl handler
+    pub old_value: Buffer<SYSCTL_VALUE_MAX>,
+    // address of ctl_table proc_handler
+    pub handler: u64,
+    pub rc: i64,
+}
@@ -0,0 +1,36 @@
+use crate::bpf_events::Event;
+use crate::buffer::Buffer;
+use crate::net::{SockAddr, SocketInfo};
+
+use super::ENCRYPT_DATA_MAX_BUFFER_SIZE;
+
+// TLS record content type of handshake messages
+const TLS_CONTENT_TYPE_HANDSHAKE: u8 = 0x16;
+// major version of TLS records (SSLv3 to TLS 1.3)
+const TLS_RECORD_VERSION_MAJOR: u8 = 0x03;
+// handshake message type of a ClientHello
+const TLS_HANDSHAKE_CLIENT_HELLO: u8 = 0x01;
+
+pub type TlsClientHelloEvent = Event<TlsClientHelloData>;
+
+#[repr(C)]
+pub struct TlsClientHelloData {
+    pub socket: SocketInfo,
+    pub src: SockAddr,
+    pub dst: SockAddr,
+    // beginning of the data sent, ClientHello is parsed in userland
+    pub data: Buffer<ENCRYPT_DATA_MAX_BUFFER_SIZE>,
+    // data sent through io_uring
+    pub io_uring: bool,
+}
+
+/// Returns `true` if data in `b` starts like a TLS
+/// record holding a ClientHello handshake message
+#[inline(always)]
+pub fn is_tls_client_hello<const N: usize>(b: &Buffer<N>) -> bool {
+    // record header is 5 bytes long and followed by handshake type
+    b.len() > 5
+        && b[0] == TLS_CONTENT_TYPE_HANDSHAKE
+        && b[1] == TLS_RECORD_VERSION_MAJOR
+        && b[5] == TLS_HANDSHAKE_CLIENT_HELLO
+}
@@ -1,24 +1,34 @@
-use core::str;
-
 use super::Buffer;
 
+/// Converts NUL separated arguments into a list of arguments
+pub fn argv_from_bytes(b: &[u8]) -> Vec<String> {
+    b.split(|&b| b == b'\0')
+        .map(String::from_utf8_lossy)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            if s.chars().any(|c| c.is_whitespace()) {
+                // we wrap strings containg space between double quotes
+                // but we also need to replace double quotes by escaped double quotes
+                format!(r#""{}""#, s.replace(r#"""#, r#"\""#))
+            } else {
+                s.into()
+            }
+        })
+        .collect()
+}
+
 impl<const N: usize> Buffer<N> {
+    /// Copies at most N bytes of `s` into the buffer and
+    /// returns the number of bytes copied
+    pub fn copy_from_slice(&mut self, s: &[u8]) -> usize {
+        let n = s.len().min(N);
+        self.buf[..n].copy_from_slice(&s[..n]);
+        self.len = n;
+        n
+    }
+
     #[inline]
     pub fn to_argv(&self) -> Vec<String> {
-        self.as_slice()
-            .split(|&b| b == b'\0')
-            // this must not panic as we are sure to have utf8 from kernel
-            .map(|s| str::from_utf8(s).unwrap().to_string())
-            .filter(|s| !s.is_empty())
-            .map(|s| {
-                if s.chars().any(|c| c.is_whitespace()) {
-                    // we wrap strings containg space between double quotes
-                    // but we also need to replace double quotes by escaped double quotes
-                    format!(r#""{}""#, s.replace(r#"""#, r#"\""#))
-                } else {
-                    s
-                }
-            })
-            .collect()
+        argv_from_bytes(self.as_slice())
     }
 }
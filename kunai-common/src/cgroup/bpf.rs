@@ -16,7 +16,39 @@ impl Cgroup {
             return Ok(());
         }
 
-        let mut kn = core_read_kernel!(cgroup, kn).ok_or(Error::Kn)?;
+        let kn = core_read_kernel!(cgroup, kn).ok_or(Error::Kn)?;
+
+        self.resolve_kn(kn)
+    }
+
+    /// Resolve the path of cgroup `name` about to be created under `parent`
+    #[inline(always)]
+    pub unsafe fn resolve_new(
+        &mut self,
+        parent: co_re::kernfs_node,
+        name: *const u8,
+    ) -> Result<(), Error> {
+        // initialize error
+        self.error = None;
+
+        self.path.append_kernel_str_bytes(name).map_err(|_| {
+            self.error = Some(Error::Append);
+            Error::Append
+        })?;
+
+        self.path.push_byte(b'/').map_err(|_| {
+            self.error = Some(Error::Append);
+            Error::Append
+        })?;
+
+        self.resolve_kn(parent)
+    }
+
+    /// Resolve the path of the cgroup kernfs entry `kn`
+    #[inline(always)]
+    pub unsafe fn resolve_kn(&mut self, mut kn: co_re::kernfs_node) -> Result<(), Error> {
+        // initialize error
+        self.error = None;
 
         for _ in 0..MAX_CGROUP_DEPTH {
             let kn_name = core_read_kernel!(kn, name).ok_or(Error::KnName)?;
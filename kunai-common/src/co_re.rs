@@ -52,6 +52,9 @@ pub use core_files_struct::*;
 mod core_page;
 pub use core_page::*;
 
+mod core_sysctl;
+pub use core_sysctl::*;
+
 #[derive(Clone, Copy)]
 pub struct CoRe<P> {
     ptr: *const P,
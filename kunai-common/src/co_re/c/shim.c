@@ -104,15 +104,40 @@ struct kuid_t
 // Defining shim for cred struct
 // We just need to define the fields we need to access
 
+// kernel_cap_t is an array of two u32 before 6.3 and a
+// struct holding a single u64 after, both have the same size
+struct kernel_cap_struct
+{
+	__u64 val;
+};
+
 struct cred
 {
 	struct kuid_t uid;
 	struct kgid_t gid;
+	struct kernel_cap_struct cap_permitted;
+	struct kernel_cap_struct cap_effective;
+	struct kernel_cap_struct cap_bounding;
 } __attribute__((preserve_access_index));
 
 _SHIM_GETTER_BPF_CORE_READ(uid_t, shim_cred_uid(struct cred *pcred), pcred, uid.val);
 _SHIM_GETTER_BPF_CORE_READ(gid_t, shim_cred_gid(struct cred *pcred), pcred, gid.val);
 
+// we read capabilities as raw u64 so that it works whatever
+// the layout of kernel_cap_t
+#define SHIM_CRED_CAP(memb)                                              \
+	__attribute__((always_inline)) __u64 shim_cred_##memb(struct cred *pcred) \
+	{                                                                    \
+		__u64 cap = 0;                                                   \
+		bpf_core_read(&cap, sizeof(cap), &pcred->memb);                  \
+		return cap;                                                      \
+	}                                                                    \
+	_FIELD_EXISTS_DEF(cred, memb, memb)
+
+SHIM_CRED_CAP(cap_permitted);
+SHIM_CRED_CAP(cap_effective);
+SHIM_CRED_CAP(cap_bounding);
+
 struct qstr
 {
 	union
@@ -134,12 +159,20 @@ SHIM(qstr, hash_len);
 SHIM(qstr, hash);
 SHIM(qstr, len);
 
+struct list_head
+{
+	struct list_head *next;
+	struct list_head *prev;
+} __attribute__((preserve_access_index));
+
 struct vfsmount
 {
 	struct dentry *mnt_root;
+	struct super_block *mnt_sb;
 } __attribute__((preserve_access_index));
 
 SHIM(vfsmount, mnt_root);
+SHIM(vfsmount, mnt_sb);
 
 struct mountpoint;
 
@@ -149,12 +182,15 @@ struct mount
 	struct dentry *mnt_mountpoint;
 	struct vfsmount mnt;
 	struct mountpoint *mnt_mp;
+	const char *mnt_devname;
+	struct list_head mnt_instance;
 } __attribute__((preserve_access_index));
 
 SHIM(mount, mnt_parent);
 SHIM(mount, mnt_mountpoint);
 SHIM_REF(mount, mnt)
 SHIM(mount, mnt_mp)
+SHIM(mount, mnt_devname)
 
 __attribute__((always_inline)) struct mount *shim_mount_from_vfsmount(struct vfsmount *vfs)
 {
@@ -164,12 +200,42 @@ __attribute__((always_inline)) struct mount *shim_mount_from_vfsmount(struct vfs
 	return ((void *)vfs - offset);
 }
 
+struct file_system_type
+{
+	const char *name;
+} __attribute__((preserve_access_index));
+
+SHIM(file_system_type, name);
+
 struct super_block
 {
 	struct dentry *s_root;
+	struct file_system_type *s_type;
+	struct list_head s_mounts;
+	unsigned long s_magic;
 } __attribute__((preserve_access_index));
 
 SHIM(super_block, s_root);
+SHIM(super_block, s_type);
+SHIM(super_block, s_magic);
+
+// returns the first mount found in super_block.s_mounts list
+// or NULL if the list is empty
+__attribute__((always_inline)) struct mount *shim_super_block_first_mount(struct super_block *sb)
+{
+	struct mount *mount = 0;
+	struct list_head *head = __builtin_preserve_access_index(&(sb->s_mounts));
+	struct list_head *next = BPF_CORE_READ(sb, s_mounts.next);
+
+	if (next == head)
+	{
+		return 0;
+	}
+
+	struct list_head *instance = __builtin_preserve_access_index(&(mount->mnt_instance));
+	__u64 offset = (void *)instance - (void *)mount;
+	return ((void *)next - offset);
+}
 
 struct dentry
 {
@@ -211,15 +277,19 @@ struct timespec64
 };
 
 typedef short unsigned int umode_t;
+typedef unsigned int dev_t;
 typedef long long int __kernel_loff_t;
 typedef __kernel_loff_t loff_t;
 
 struct inode
 {
 	umode_t i_mode;
+	struct kuid_t i_uid;
+	struct kgid_t i_gid;
 	unsigned long i_ino;
 	struct super_block *i_sb;
 	loff_t i_size;
+	dev_t i_rdev;
 	// mac time changed in kernel 6.11
 	// https://elixir.bootlin.com/linux/v6.11/source/include/linux/fs.h#L668
 	time64_t i_atime_sec;
@@ -250,6 +320,9 @@ SHIM(inode, i_ino);
 SHIM(inode, i_mode);
 SHIM(inode, i_sb);
 SHIM(inode, i_size);
+SHIM(inode, i_rdev);
+_SHIM_GETTER_BPF_CORE_READ(uid_t, shim_inode_i_uid(struct inode *inode), inode, i_uid.val);
+_SHIM_GETTER_BPF_CORE_READ(gid_t, shim_inode_i_gid(struct inode *inode), inode, i_gid.val);
 SHIM(inode, i_atime);
 SHIM(inode, __i_atime);
 SHIM(inode, i_atime_sec);
@@ -263,6 +336,36 @@ SHIM(inode, __i_ctime);
 SHIM(inode, i_ctime_sec);
 SHIM(inode, i_ctime_nsec);
 
+struct ctl_table
+{
+	const char *procname;
+	void *data;
+	int maxlen;
+	void *proc_handler;
+} __attribute__((preserve_access_index));
+
+SHIM(ctl_table, procname);
+SHIM(ctl_table, data);
+SHIM(ctl_table, maxlen);
+SHIM(ctl_table, proc_handler);
+
+struct proc_inode
+{
+	struct ctl_table *sysctl_entry;
+	struct inode vfs_inode;
+} __attribute__((preserve_access_index));
+
+SHIM(proc_inode, sysctl_entry);
+
+// equivalent of PROC_I() macro in kernel
+__attribute__((always_inline)) struct proc_inode *shim_proc_inode_from_inode(struct inode *inode)
+{
+	struct proc_inode *pi = 0;
+	struct inode *vfs_inode = __builtin_preserve_access_index(&(pi->vfs_inode));
+	__u64 offset = (void *)vfs_inode - (void *)pi;
+	return ((void *)inode - offset);
+}
+
 struct file
 {
 	struct inode *f_inode;
@@ -278,6 +381,13 @@ SHIM(file, private_data);
 SHIM(file, f_flags);
 SHIM(file, f_mode);
 
+struct kiocb
+{
+	struct file *ki_filp;
+} __attribute__((preserve_access_index));
+
+SHIM(kiocb, ki_filp);
+
 struct fd
 {
 	struct file *file;
@@ -291,11 +401,15 @@ struct mm_struct
 {
 	unsigned long arg_start;
 	unsigned long arg_end;
+	unsigned long env_start;
+	unsigned long env_end;
 	struct file *exe_file;
 } __attribute__((preserve_access_index));
 
 SHIM(mm_struct, arg_start);
 SHIM(mm_struct, arg_end);
+SHIM(mm_struct, env_start);
+SHIM(mm_struct, env_end);
 SHIM(mm_struct, exe_file);
 
 // Defining shim for task_struct
@@ -348,14 +462,23 @@ struct uts_namespace
 SHIM_REF(uts_namespace, ns);
 SHIM_REF(uts_namespace, name);
 
+struct net
+{
+	struct ns_common ns;
+} __attribute__((preserve_access_index));
+
+SHIM_REF(net, ns);
+
 struct nsproxy
 {
 	struct mnt_namespace *mnt_ns;
 	struct uts_namespace *uts_ns;
+	struct net *net_ns;
 } __attribute__((preserve_access_index));
 
 SHIM(nsproxy, mnt_ns);
 SHIM(nsproxy, uts_ns);
+SHIM(nsproxy, net_ns);
 
 struct kernfs_node
 {
@@ -406,6 +529,66 @@ struct files_struct
 ARRAY_SHIM(files_struct, fd_array);
 SHIM(files_struct, fdt);
 
+struct fs_struct
+{
+	struct path pwd;
+} __attribute__((preserve_access_index));
+
+SHIM_REF(fs_struct, pwd);
+
+struct seccomp
+{
+	int mode;
+} __attribute__((preserve_access_index));
+
+#define TTY_NAME_LEN 64
+
+struct tty_struct
+{
+	unsigned char name[TTY_NAME_LEN];
+} __attribute__((preserve_access_index));
+
+ARRAY_SHIM(tty_struct, name);
+
+struct upid
+{
+	int nr;
+} __attribute__((preserve_access_index));
+
+struct pid
+{
+	struct upid numbers[1];
+} __attribute__((preserve_access_index));
+
+#define PIDTYPE_SID 3
+
+struct atomic_t
+{
+	int counter;
+} __attribute__((preserve_access_index));
+
+struct signal_struct
+{
+	struct atomic_t live;
+	struct tty_struct *tty;
+	struct pid *pids[4];
+	// cpu time of dead threads
+	__u64 utime;
+	__u64 stime;
+	unsigned long maxrss;
+} __attribute__((preserve_access_index));
+
+SHIM(signal_struct, tty);
+// number of live threads in the group
+_SHIM_GETTER_BPF_CORE_READ(int, shim_signal_struct_live(struct signal_struct *signal_struct), signal_struct, live.counter);
+_FIELD_EXISTS_DEF(signal_struct, live, live);
+SHIM(signal_struct, utime);
+SHIM(signal_struct, stime);
+SHIM(signal_struct, maxrss);
+// pid number of the session leader in the initial pid namespace
+_SHIM_GETTER_BPF_CORE_READ(int, shim_signal_struct_session_nr(struct signal_struct *signal_struct), signal_struct, pids[PIDTYPE_SID], numbers[0].nr);
+_FIELD_EXISTS_DEF(signal_struct, pids, session_nr);
+
 struct task_struct
 {
 	unsigned int flags;
@@ -418,14 +601,24 @@ struct task_struct
 		__u64 real_start_time;
 	};
 	pid_t tgid;
+	int exit_code;
+	__u64 utime;
+	__u64 stime;
 	unsigned char comm[COMM_LEN];
 	struct cred *cred; // gives an example of nested access
 	struct task_struct *real_parent;
 	struct task_struct *group_leader;
 	struct mm_struct *mm;
 	struct files_struct *files;
+	struct fs_struct *fs;
 	struct nsproxy *nsproxy;
 	struct task_group *sched_task_group;
+	unsigned long atomic_flags;
+	struct seccomp seccomp;
+	struct signal_struct *signal;
+	// only if kernel is compiled with CONFIG_AUDIT
+	struct kuid_t loginuid;
+	unsigned int sessionid;
 } __attribute__((preserve_access_index));
 
 SHIM(task_struct, flags);
@@ -435,13 +628,24 @@ SHIM(task_struct, real_start_time);
 ARRAY_SHIM(task_struct, comm);
 SHIM(task_struct, pid);
 SHIM(task_struct, tgid);
+SHIM(task_struct, exit_code);
+SHIM(task_struct, utime);
+SHIM(task_struct, stime);
 SHIM(task_struct, cred);
 SHIM(task_struct, group_leader);
 SHIM(task_struct, real_parent);
 SHIM(task_struct, mm);
 SHIM(task_struct, files);
+SHIM(task_struct, fs);
 SHIM(task_struct, nsproxy);
 SHIM(task_struct, sched_task_group);
+SHIM(task_struct, atomic_flags);
+_SHIM_GETTER_BPF_CORE_READ(int, shim_task_struct_seccomp_mode(struct task_struct *task_struct), task_struct, seccomp.mode);
+_FIELD_EXISTS_DEF(task_struct, seccomp.mode, seccomp_mode);
+SHIM(task_struct, signal);
+_SHIM_GETTER_BPF_CORE_READ(uid_t, shim_task_struct_loginuid(struct task_struct *task_struct), task_struct, loginuid.val);
+_FIELD_EXISTS_DEF(task_struct, loginuid, loginuid);
+SHIM(task_struct, sessionid);
 
 #define KSYM_NAME_LEN 512
 
@@ -603,6 +807,12 @@ SHIM(sockaddr_in6, sin6_family);
 SHIM(sockaddr_in6, sin6_port);
 SHIM_REF(sockaddr_in6, sin6_addr);
 
+// possible_net_t is a typedef of an anonymous struct in the kernel
+struct possible_net_t
+{
+	struct net *net;
+} __attribute__((preserve_access_index));
+
 struct sock_common
 {
 	union
@@ -620,6 +830,8 @@ struct sock_common
 	struct in6_addr skc_v6_daddr;
 	struct in6_addr skc_v6_rcv_saddr;
 
+	struct possible_net_t skc_net;
+
 } __attribute__((preserve_access_index));
 
 SHIM(sock_common, skc_family);
@@ -627,6 +839,23 @@ SHIM(sock_common, skc_addrpair);
 SHIM(sock_common, skc_portpair);
 SHIM_REF(sock_common, skc_v6_daddr);
 SHIM_REF(sock_common, skc_v6_rcv_saddr);
+SHIM_WITH_NAME(sock_common, skc_net.net, skc_net);
+
+#define IFNAMSIZ 16
+
+struct net_device
+{
+	char name[IFNAMSIZ];
+} __attribute__((preserve_access_index));
+
+ARRAY_SHIM(net_device, name);
+
+struct dst_entry
+{
+	struct net_device *dev;
+} __attribute__((preserve_access_index));
+
+SHIM(dst_entry, dev);
 
 struct sk_buff
 {
@@ -668,6 +897,7 @@ struct sock
 	// sk_type is always a u16, bitfield or not
 	__u16 sk_type;
 	struct sk_buff_head sk_receive_queue;
+	struct dst_entry *sk_dst_cache;
 } __attribute__((preserve_access_index));
 
 // BPF core trims any ___$SUFFIX and treat structure
@@ -685,6 +915,7 @@ SHIM_REF(sock, __sk_common);
 SHIM(sock, sk_protocol);
 SHIM_BITFIELD(sock, sk_type);
 SHIM_REF(sock, sk_receive_queue)
+SHIM(sock, sk_dst_cache);
 
 struct socket
 {
@@ -1,5 +1,5 @@
 use super::gen::{self, *};
-use super::{CoRe};
+use super::{rust_shim_kernel_impl, CoRe};
 
 #[allow(non_camel_case_types)]
 pub type cred = CoRe<gen::cred>;
@@ -14,4 +14,8 @@ impl cred {
     pub unsafe fn gid(&self) -> u32 {
         shim_cred_gid(self.as_ptr_mut())
     }
+
+    rust_shim_kernel_impl!(pub, cred, cap_permitted, u64);
+    rust_shim_kernel_impl!(pub, cred, cap_effective, u64);
+    rust_shim_kernel_impl!(pub, cred, cap_bounding, u64);
 }
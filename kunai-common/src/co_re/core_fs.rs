@@ -15,6 +15,23 @@ impl inode {
     rust_shim_kernel_impl!(inode, i_mode, u16);
     rust_shim_kernel_impl!(inode, i_sb, super_block);
     rust_shim_kernel_impl!(inode, i_size, i64);
+    rust_shim_kernel_impl!(inode, i_rdev, u32);
+
+    #[inline(always)]
+    pub unsafe fn i_uid(&self) -> Option<u32> {
+        if self.is_null() {
+            return None;
+        }
+        Some(shim_inode_i_uid(self.as_ptr_mut()))
+    }
+
+    #[inline(always)]
+    pub unsafe fn i_gid(&self) -> Option<u32> {
+        if self.is_null() {
+            return None;
+        }
+        Some(shim_inode_i_gid(self.as_ptr_mut()))
+    }
 
     // for kernels < 6.7
     rust_shim_kernel_impl!(pub(self),_i_atime, inode, i_atime, timespec64);
@@ -107,6 +124,13 @@ impl fd {
     rust_shim_kernel_impl!(pub, fd, file, file);
 }
 
+#[allow(non_camel_case_types)]
+pub type kiocb = CoRe<gen::kiocb>;
+
+impl kiocb {
+    rust_shim_kernel_impl!(pub, kiocb, ki_filp, file);
+}
+
 #[allow(non_camel_case_types)]
 pub type path = CoRe<gen::path>;
 
@@ -115,6 +139,13 @@ impl path {
     rust_shim_kernel_impl!(pub, path, dentry, dentry);
 }
 
+#[allow(non_camel_case_types)]
+pub type fs_struct = CoRe<gen::fs_struct>;
+
+impl fs_struct {
+    rust_shim_kernel_impl!(pub, fs_struct, pwd, path);
+}
+
 #[allow(non_camel_case_types)]
 pub type qstr = CoRe<gen::qstr>;
 
@@ -154,6 +185,32 @@ pub type super_block = CoRe<gen::super_block>;
 
 impl super_block {
     rust_shim_kernel_impl!(pub, super_block, s_root, dentry);
+    rust_shim_kernel_impl!(pub, super_block, s_type, file_system_type);
+    rust_shim_kernel_impl!(pub, super_block, s_magic, u64);
+
+    #[inline(always)]
+    pub unsafe fn first_mount(&self) -> Option<mount> {
+        if self.is_null() {
+            return None;
+        }
+        let m = mount::from_ptr(shim_super_block_first_mount(self.as_ptr_mut()));
+        if m.is_null() {
+            return None;
+        }
+        Some(m)
+    }
+}
+
+#[allow(non_camel_case_types)]
+pub type file_system_type = CoRe<gen::file_system_type>;
+
+impl file_system_type {
+    rust_shim_kernel_impl!(pub(self), _name, file_system_type, name, *const i8);
+
+    #[inline(always)]
+    pub unsafe fn name(&self) -> Option<*const u8> {
+        Some(self._name()? as *const u8)
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -164,6 +221,12 @@ impl mount {
     rust_shim_kernel_impl!(pub, mount, mnt_mountpoint, dentry);
     rust_shim_kernel_impl!(pub, mount, mnt_parent, mount);
     rust_shim_kernel_impl!(mount, mnt_mp, mountpoint);
+    rust_shim_kernel_impl!(pub(self), _mnt_devname, mount, mnt_devname, *const i8);
+
+    #[inline(always)]
+    pub unsafe fn mnt_devname(&self) -> Option<*const u8> {
+        Some(self._mnt_devname()? as *const u8)
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -176,6 +239,7 @@ impl vfsmount {
     }
 
     rust_shim_kernel_impl!(pub, vfsmount, mnt_root, dentry);
+    rust_shim_kernel_impl!(pub, vfsmount, mnt_sb, super_block);
 }
 
 #[allow(non_camel_case_types)]
@@ -22,5 +22,22 @@ impl mm_struct {
     }
 
     rust_shim_kernel_impl!(mm_struct, arg_end, u64);
+
+    rust_shim_kernel_impl!(mm_struct, env_start, u64);
+
+    #[inline(always)]
+    pub unsafe fn env_len(&self) -> Option<u64> {
+        let start = self.env_start()?;
+        let end = self.env_end()?;
+        Some({
+            if end == 0 || start >= end {
+                0
+            } else {
+                end - start
+            }
+        })
+    }
+
+    rust_shim_kernel_impl!(mm_struct, env_end, u64);
     rust_shim_kernel_impl!(mm_struct, exe_file, file);
 }
@@ -7,6 +7,7 @@ pub type nsproxy = CoRe<gen::nsproxy>;
 impl nsproxy {
     rust_shim_kernel_impl!(pub, nsproxy, mnt_ns, mnt_namespace);
     rust_shim_kernel_impl!(pub, nsproxy, uts_ns, uts_namespace);
+    rust_shim_kernel_impl!(pub, nsproxy, net_ns, net);
 }
 
 #[allow(non_camel_case_types)]
@@ -44,3 +45,10 @@ impl new_utsname {
     rust_shim_kernel_impl!(new_utsname, machine, *mut i8);
     rust_shim_kernel_impl!(new_utsname, domainname, *mut i8);
 }
+
+#[allow(non_camel_case_types)]
+pub type net = CoRe<gen::net>;
+
+impl net {
+    rust_shim_kernel_impl!(net, ns, ns_common);
+}
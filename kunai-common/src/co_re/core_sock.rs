@@ -2,7 +2,8 @@ use aya_ebpf::cty::c_void;
 use aya_ebpf::helpers::{bpf_probe_read_kernel_buf, bpf_probe_read_user_buf};
 
 use super::gen::{self, *};
-use super::{iov_iter, iovec, rust_shim_kernel_impl, rust_shim_user_impl, CoRe};
+use super::{iov_iter, iovec, net, rust_shim_kernel_impl, rust_shim_user_impl, CoRe};
+use crate::string::String;
 
 #[allow(non_camel_case_types)]
 pub type in6_addr = CoRe<gen::in6_addr>;
@@ -84,6 +85,7 @@ impl sock {
     }
 
     rust_shim_kernel_impl!(pub, sock, sk_receive_queue, sk_buff_head);
+    rust_shim_kernel_impl!(pub, sock, sk_dst_cache, dst_entry);
 }
 
 #[allow(non_camel_case_types)]
@@ -135,6 +137,28 @@ impl sock_common {
 
     rust_shim_kernel_impl!(pub, sock_common, skc_v6_daddr, in6_addr);
     rust_shim_kernel_impl!(pub, sock_common, skc_v6_rcv_saddr, in6_addr);
+    rust_shim_kernel_impl!(pub, sock_common, skc_net, net);
+}
+
+#[allow(non_camel_case_types)]
+pub type dst_entry = CoRe<gen::dst_entry>;
+
+impl dst_entry {
+    rust_shim_kernel_impl!(pub, dst_entry, dev, net_device);
+}
+
+#[allow(non_camel_case_types)]
+pub type net_device = CoRe<gen::net_device>;
+
+impl net_device {
+    rust_shim_kernel_impl!(pub, net_device, name, *mut i8);
+
+    #[inline(always)]
+    pub unsafe fn name_str(&self) -> Option<String<16>> {
+        let mut name = String::<16>::new();
+        name.read_kernel_str_bytes(self.name()?).ok()?;
+        Some(name)
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -0,0 +1,27 @@
+use aya_ebpf::cty::c_void;
+
+use super::gen::{self, *};
+use super::{inode, rust_shim_kernel_impl, CoRe};
+
+#[allow(non_camel_case_types)]
+pub type ctl_table = CoRe<gen::ctl_table>;
+
+impl ctl_table {
+    rust_shim_kernel_impl!(pub, ctl_table, data, *mut c_void);
+    rust_shim_kernel_impl!(pub, ctl_table, maxlen, i32);
+    rust_shim_kernel_impl!(pub, ctl_table, proc_handler, *mut c_void);
+}
+
+#[allow(non_camel_case_types)]
+pub type proc_inode = CoRe<gen::proc_inode>;
+
+impl proc_inode {
+    /// Returns the `proc_inode` embedding `inode`. The caller must
+    /// make sure `inode` belongs to procfs.
+    #[inline(always)]
+    pub unsafe fn from_inode(inode: &inode) -> Self {
+        Self::from_ptr(shim_proc_inode_from_inode(inode.as_ptr_mut()))
+    }
+
+    rust_shim_kernel_impl!(pub, proc_inode, sysctl_entry, ctl_table);
+}
@@ -7,8 +7,8 @@ use crate::string::String;
 
 use super::gen::{self, *};
 use super::{
-    core_read_kernel, cred, file, files_struct, mm_struct, nsproxy, rust_shim_kernel_impl,
-    task_group, CoRe,
+    core_read_kernel, cred, file, files_struct, fs_struct, mm_struct, nsproxy,
+    rust_shim_kernel_impl, task_group, CoRe,
 };
 
 #[allow(non_camel_case_types)]
@@ -32,6 +32,10 @@ impl task_struct {
 
     rust_shim_kernel_impl!(pub, task_struct, flags, u32);
     rust_shim_kernel_impl!(pub, task_struct, start_time, u64);
+    rust_shim_kernel_impl!(pub, task_struct, exit_code, i32);
+    // cpu time spent in user and kernel mode (ns)
+    rust_shim_kernel_impl!(pub, task_struct, utime, u64);
+    rust_shim_kernel_impl!(pub, task_struct, stime, u64);
 
     rust_shim_kernel_impl!(pub(self), _start_boot_time, task_struct, start_boottime, u64);
     rust_shim_kernel_impl!(pub(self),_real_start_time, task_struct, real_start_time, u64);
@@ -73,16 +77,24 @@ impl task_struct {
     rust_shim_kernel_impl!(pub, task_struct, tgid, pid_t);
     rust_shim_kernel_impl!(pub, task_struct, pid, pid_t);
     rust_shim_kernel_impl!(pub, task_struct, cred, cred);
+    rust_shim_kernel_impl!(pub, task_struct, atomic_flags, u64);
+    rust_shim_kernel_impl!(pub, task_struct, seccomp_mode, i32);
     rust_shim_kernel_impl!(pub, task_struct, mm, mm_struct);
 
     rust_shim_kernel_impl!(pub, task_struct, group_leader, Self);
     rust_shim_kernel_impl!(pub, task_struct, real_parent, Self);
 
     rust_shim_kernel_impl!(task_struct, files, files_struct);
+    rust_shim_kernel_impl!(pub, task_struct, fs, fs_struct);
     rust_shim_kernel_impl!(pub, task_struct, nsproxy, nsproxy);
 
     rust_shim_kernel_impl!(task_struct, sched_task_group, task_group);
 
+    rust_shim_kernel_impl!(pub, task_struct, signal, signal_struct);
+    // loginuid and sessionid exist only if kernel has CONFIG_AUDIT
+    rust_shim_kernel_impl!(pub, task_struct, loginuid, u32);
+    rust_shim_kernel_impl!(pub, task_struct, sessionid, u32);
+
     #[inline(always)]
     /// this is a shortcut function to easily get a file from its fd
     /// looking up the task_struct fdtable.
@@ -90,3 +102,30 @@ impl task_struct {
         core_read_kernel!(self, files)?.get_file(fd)
     }
 }
+
+#[allow(non_camel_case_types)]
+pub type signal_struct = CoRe<gen::signal_struct>;
+
+impl signal_struct {
+    rust_shim_kernel_impl!(pub, signal_struct, tty, tty_struct);
+    rust_shim_kernel_impl!(pub, signal_struct, session_nr, i32);
+    rust_shim_kernel_impl!(pub, signal_struct, live, i32);
+    // cpu time (ns) and max rss (pages) of the threads already dead
+    rust_shim_kernel_impl!(pub, signal_struct, utime, u64);
+    rust_shim_kernel_impl!(pub, signal_struct, stime, u64);
+    rust_shim_kernel_impl!(pub, signal_struct, maxrss, u64);
+}
+
+#[allow(non_camel_case_types)]
+pub type tty_struct = CoRe<gen::tty_struct>;
+
+impl tty_struct {
+    rust_shim_kernel_impl!(pub, tty_struct, name, *mut u8);
+
+    #[inline(always)]
+    pub unsafe fn name_str(&self) -> Option<String<64>> {
+        let mut name = String::<64>::new();
+        name.read_kernel_str_bytes(self.name()?).ok()?;
+        Some(name)
+    }
+}
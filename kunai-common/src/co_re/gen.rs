@@ -32,9 +32,17 @@ pub struct kuid_t {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct kernel_cap_struct {
+    pub val: __u64,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct cred {
     pub uid: kuid_t,
     pub gid: kgid_t,
+    pub cap_permitted: kernel_cap_struct,
+    pub cap_effective: kernel_cap_struct,
+    pub cap_bounding: kernel_cap_struct,
 }
 unsafe extern "C" {
     pub fn shim_cred_uid(pcred: *mut cred) -> uid_t;
@@ -42,6 +50,24 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_cred_gid(pcred: *mut cred) -> gid_t;
 }
+unsafe extern "C" {
+    pub fn shim_cred_cap_permitted(pcred: *mut cred) -> __u64;
+}
+unsafe extern "C" {
+    pub fn shim_cred_cap_permitted_exists(cred: *mut cred) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_cred_cap_effective(pcred: *mut cred) -> __u64;
+}
+unsafe extern "C" {
+    pub fn shim_cred_cap_effective_exists(cred: *mut cred) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_cred_cap_bounding(pcred: *mut cred) -> __u64;
+}
+unsafe extern "C" {
+    pub fn shim_cred_cap_bounding_exists(cred: *mut cred) -> bool;
+}
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct qstr {
@@ -98,8 +124,15 @@ unsafe extern "C" {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct list_head {
+    pub next: *mut list_head,
+    pub prev: *mut list_head,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct vfsmount {
     pub mnt_root: *mut dentry,
+    pub mnt_sb: *mut super_block,
 }
 unsafe extern "C" {
     pub fn shim_vfsmount_mnt_root(vfsmount: *mut vfsmount) -> *mut dentry;
@@ -110,6 +143,15 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_vfsmount_mnt_root_exists(vfsmount: *mut vfsmount) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_vfsmount_mnt_sb(vfsmount: *mut vfsmount) -> *mut super_block;
+}
+unsafe extern "C" {
+    pub fn shim_vfsmount_mnt_sb_user(vfsmount: *mut vfsmount) -> *mut super_block;
+}
+unsafe extern "C" {
+    pub fn shim_vfsmount_mnt_sb_exists(vfsmount: *mut vfsmount) -> bool;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct mount {
@@ -117,6 +159,8 @@ pub struct mount {
     pub mnt_mountpoint: *mut dentry,
     pub mnt: vfsmount,
     pub mnt_mp: *mut mountpoint,
+    pub mnt_devname: *const ::core::ffi::c_char,
+    pub mnt_instance: list_head,
 }
 unsafe extern "C" {
     pub fn shim_mount_mnt_parent(mount: *mut mount) -> *mut mount;
@@ -154,13 +198,43 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_mount_mnt_mp_exists(mount: *mut mount) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_mount_mnt_devname(mount: *mut mount) -> *const ::core::ffi::c_char;
+}
+unsafe extern "C" {
+    pub fn shim_mount_mnt_devname_user(mount: *mut mount) -> *const ::core::ffi::c_char;
+}
+unsafe extern "C" {
+    pub fn shim_mount_mnt_devname_exists(mount: *mut mount) -> bool;
+}
 unsafe extern "C" {
     pub fn shim_mount_from_vfsmount(vfs: *mut vfsmount) -> *mut mount;
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct file_system_type {
+    pub name: *const ::core::ffi::c_char,
+}
+unsafe extern "C" {
+    pub fn shim_file_system_type_name(
+        file_system_type: *mut file_system_type,
+    ) -> *const ::core::ffi::c_char;
+}
+unsafe extern "C" {
+    pub fn shim_file_system_type_name_user(
+        file_system_type: *mut file_system_type,
+    ) -> *const ::core::ffi::c_char;
+}
+unsafe extern "C" {
+    pub fn shim_file_system_type_name_exists(file_system_type: *mut file_system_type) -> bool;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct super_block {
     pub s_root: *mut dentry,
+    pub s_type: *mut file_system_type,
+    pub s_mounts: list_head,
+    pub s_magic: ::core::ffi::c_ulong,
 }
 unsafe extern "C" {
     pub fn shim_super_block_s_root(super_block: *mut super_block) -> *mut dentry;
@@ -171,6 +245,27 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_super_block_s_root_exists(super_block: *mut super_block) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_super_block_s_type(super_block: *mut super_block) -> *mut file_system_type;
+}
+unsafe extern "C" {
+    pub fn shim_super_block_s_type_user(super_block: *mut super_block) -> *mut file_system_type;
+}
+unsafe extern "C" {
+    pub fn shim_super_block_s_type_exists(super_block: *mut super_block) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_super_block_s_magic(super_block: *mut super_block) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_super_block_s_magic_user(super_block: *mut super_block) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_super_block_s_magic_exists(super_block: *mut super_block) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_super_block_first_mount(sb: *mut super_block) -> *mut mount;
+}
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct dentry {
@@ -271,14 +366,18 @@ pub struct timespec64 {
     pub tv_nsec: ::core::ffi::c_long,
 }
 pub type umode_t = ::core::ffi::c_ushort;
+pub type dev_t = ::core::ffi::c_uint;
 pub type loff_t = __kernel_loff_t;
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct inode {
     pub i_mode: umode_t,
+    pub i_uid: kuid_t,
+    pub i_gid: kgid_t,
     pub i_ino: ::core::ffi::c_ulong,
     pub i_sb: *mut super_block,
     pub i_size: loff_t,
+    pub i_rdev: dev_t,
     pub i_atime_sec: time64_t,
     pub i_mtime_sec: time64_t,
     pub i_ctime_sec: time64_t,
@@ -343,6 +442,21 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_inode_i_size_exists(inode: *mut inode) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_inode_i_rdev(inode: *mut inode) -> dev_t;
+}
+unsafe extern "C" {
+    pub fn shim_inode_i_rdev_user(inode: *mut inode) -> dev_t;
+}
+unsafe extern "C" {
+    pub fn shim_inode_i_rdev_exists(inode: *mut inode) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_inode_i_uid(inode: *mut inode) -> uid_t;
+}
+unsafe extern "C" {
+    pub fn shim_inode_i_gid(inode: *mut inode) -> gid_t;
+}
 unsafe extern "C" {
     pub fn shim_inode_i_atime(inode: *mut inode) -> timespec64;
 }
@@ -453,6 +567,68 @@ unsafe extern "C" {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct ctl_table {
+    pub procname: *const ::core::ffi::c_char,
+    pub data: *mut ::core::ffi::c_void,
+    pub maxlen: ::core::ffi::c_int,
+    pub proc_handler: *mut ::core::ffi::c_void,
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_procname(ctl_table: *mut ctl_table) -> *const ::core::ffi::c_char;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_procname_user(ctl_table: *mut ctl_table) -> *const ::core::ffi::c_char;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_procname_exists(ctl_table: *mut ctl_table) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_data(ctl_table: *mut ctl_table) -> *mut ::core::ffi::c_void;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_data_user(ctl_table: *mut ctl_table) -> *mut ::core::ffi::c_void;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_data_exists(ctl_table: *mut ctl_table) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_maxlen(ctl_table: *mut ctl_table) -> ::core::ffi::c_int;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_maxlen_user(ctl_table: *mut ctl_table) -> ::core::ffi::c_int;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_maxlen_exists(ctl_table: *mut ctl_table) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_proc_handler(ctl_table: *mut ctl_table) -> *mut ::core::ffi::c_void;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_proc_handler_user(ctl_table: *mut ctl_table) -> *mut ::core::ffi::c_void;
+}
+unsafe extern "C" {
+    pub fn shim_ctl_table_proc_handler_exists(ctl_table: *mut ctl_table) -> bool;
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct proc_inode {
+    pub sysctl_entry: *mut ctl_table,
+    pub vfs_inode: inode,
+}
+unsafe extern "C" {
+    pub fn shim_proc_inode_sysctl_entry(proc_inode: *mut proc_inode) -> *mut ctl_table;
+}
+unsafe extern "C" {
+    pub fn shim_proc_inode_sysctl_entry_user(proc_inode: *mut proc_inode) -> *mut ctl_table;
+}
+unsafe extern "C" {
+    pub fn shim_proc_inode_sysctl_entry_exists(proc_inode: *mut proc_inode) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_proc_inode_from_inode(inode: *mut inode) -> *mut proc_inode;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct file {
     pub f_inode: *mut inode,
     pub f_path: path,
@@ -507,6 +683,20 @@ unsafe extern "C" {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct kiocb {
+    pub ki_filp: *mut file,
+}
+unsafe extern "C" {
+    pub fn shim_kiocb_ki_filp(kiocb: *mut kiocb) -> *mut file;
+}
+unsafe extern "C" {
+    pub fn shim_kiocb_ki_filp_user(kiocb: *mut kiocb) -> *mut file;
+}
+unsafe extern "C" {
+    pub fn shim_kiocb_ki_filp_exists(kiocb: *mut kiocb) -> bool;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct fd {
     pub file: *mut file,
     pub flags: ::core::ffi::c_uint,
@@ -534,6 +724,8 @@ unsafe extern "C" {
 pub struct mm_struct {
     pub arg_start: ::core::ffi::c_ulong,
     pub arg_end: ::core::ffi::c_ulong,
+    pub env_start: ::core::ffi::c_ulong,
+    pub env_end: ::core::ffi::c_ulong,
     pub exe_file: *mut file,
 }
 unsafe extern "C" {
@@ -554,6 +746,24 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_mm_struct_arg_end_exists(mm_struct: *mut mm_struct) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_mm_struct_env_start(mm_struct: *mut mm_struct) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_mm_struct_env_start_user(mm_struct: *mut mm_struct) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_mm_struct_env_start_exists(mm_struct: *mut mm_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_mm_struct_env_end(mm_struct: *mut mm_struct) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_mm_struct_env_end_user(mm_struct: *mut mm_struct) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_mm_struct_env_end_exists(mm_struct: *mut mm_struct) -> bool;
+}
 unsafe extern "C" {
     pub fn shim_mm_struct_exe_file(mm_struct: *mut mm_struct) -> *mut file;
 }
@@ -710,9 +920,24 @@ unsafe extern "C" {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct net {
+    pub ns: ns_common,
+}
+unsafe extern "C" {
+    pub fn shim_net_ns(net: *mut net) -> *mut ns_common;
+}
+unsafe extern "C" {
+    pub fn shim_net_ns_user(net: *mut net) -> *mut ns_common;
+}
+unsafe extern "C" {
+    pub fn shim_net_ns_exists(net: *mut net) -> bool;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct nsproxy {
     pub mnt_ns: *mut mnt_namespace,
     pub uts_ns: *mut uts_namespace,
+    pub net_ns: *mut net,
 }
 unsafe extern "C" {
     pub fn shim_nsproxy_mnt_ns(nsproxy: *mut nsproxy) -> *mut mnt_namespace;
@@ -732,6 +957,15 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_nsproxy_uts_ns_exists(nsproxy: *mut nsproxy) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_nsproxy_net_ns(nsproxy: *mut nsproxy) -> *mut net;
+}
+unsafe extern "C" {
+    pub fn shim_nsproxy_net_ns_user(nsproxy: *mut nsproxy) -> *mut net;
+}
+unsafe extern "C" {
+    pub fn shim_nsproxy_net_ns_exists(nsproxy: *mut nsproxy) -> bool;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct kernfs_node {
@@ -853,6 +1087,112 @@ unsafe extern "C" {
     pub fn shim_files_struct_fdt_exists(files_struct: *mut files_struct) -> bool;
 }
 #[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct fs_struct {
+    pub pwd: path,
+}
+unsafe extern "C" {
+    pub fn shim_fs_struct_pwd(fs_struct: *mut fs_struct) -> *mut path;
+}
+unsafe extern "C" {
+    pub fn shim_fs_struct_pwd_user(fs_struct: *mut fs_struct) -> *mut path;
+}
+unsafe extern "C" {
+    pub fn shim_fs_struct_pwd_exists(fs_struct: *mut fs_struct) -> bool;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct seccomp {
+    pub mode: ::core::ffi::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct tty_struct {
+    pub name: [::core::ffi::c_uchar; 64usize],
+}
+unsafe extern "C" {
+    pub fn shim_tty_struct_name(tty_struct: *mut tty_struct) -> *mut ::core::ffi::c_uchar;
+}
+unsafe extern "C" {
+    pub fn shim_tty_struct_name_user(tty_struct: *mut tty_struct) -> *mut ::core::ffi::c_uchar;
+}
+unsafe extern "C" {
+    pub fn shim_tty_struct_name_exists(tty_struct: *mut tty_struct) -> bool;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct upid {
+    pub nr: ::core::ffi::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct pid {
+    pub numbers: [upid; 1usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct atomic_t {
+    pub counter: ::core::ffi::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct signal_struct {
+    pub live: atomic_t,
+    pub tty: *mut tty_struct,
+    pub pids: [*mut pid; 4usize],
+    pub utime: __u64,
+    pub stime: __u64,
+    pub maxrss: ::core::ffi::c_ulong,
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_tty(signal_struct: *mut signal_struct) -> *mut tty_struct;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_tty_user(signal_struct: *mut signal_struct) -> *mut tty_struct;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_tty_exists(signal_struct: *mut signal_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_session_nr(signal_struct: *mut signal_struct) -> ::core::ffi::c_int;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_session_nr_exists(signal_struct: *mut signal_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_live(signal_struct: *mut signal_struct) -> ::core::ffi::c_int;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_live_exists(signal_struct: *mut signal_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_utime(signal_struct: *mut signal_struct) -> ::core::ffi::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_utime_user(signal_struct: *mut signal_struct) -> ::core::ffi::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_utime_exists(signal_struct: *mut signal_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_stime(signal_struct: *mut signal_struct) -> ::core::ffi::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_stime_user(signal_struct: *mut signal_struct) -> ::core::ffi::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_stime_exists(signal_struct: *mut signal_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_maxrss(signal_struct: *mut signal_struct) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_maxrss_user(signal_struct: *mut signal_struct) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_signal_struct_maxrss_exists(signal_struct: *mut signal_struct) -> bool;
+}
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub struct task_struct {
     pub flags: ::core::ffi::c_uint,
@@ -860,14 +1200,23 @@ pub struct task_struct {
     pub start_time: __u64,
     pub __bindgen_anon_1: task_struct__bindgen_ty_1,
     pub tgid: pid_t,
+    pub exit_code: ::core::ffi::c_int,
+    pub utime: __u64,
+    pub stime: __u64,
     pub comm: [::core::ffi::c_uchar; 16usize],
     pub cred: *mut cred,
     pub real_parent: *mut task_struct,
     pub group_leader: *mut task_struct,
     pub mm: *mut mm_struct,
     pub files: *mut files_struct,
+    pub fs: *mut fs_struct,
     pub nsproxy: *mut nsproxy,
     pub sched_task_group: *mut task_group,
+    pub atomic_flags: ::core::ffi::c_ulong,
+    pub seccomp: seccomp,
+    pub signal: *mut signal_struct,
+    pub loginuid: kuid_t,
+    pub sessionid: ::core::ffi::c_uint,
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -948,6 +1297,33 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_task_struct_tgid_exists(task_struct: *mut task_struct) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_task_struct_exit_code(task_struct: *mut task_struct) -> ::core::ffi::c_int;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_exit_code_user(task_struct: *mut task_struct) -> ::core::ffi::c_int;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_exit_code_exists(task_struct: *mut task_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_utime(task_struct: *mut task_struct) -> ::core::ffi::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_utime_user(task_struct: *mut task_struct) -> ::core::ffi::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_utime_exists(task_struct: *mut task_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_stime(task_struct: *mut task_struct) -> ::core::ffi::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_stime_user(task_struct: *mut task_struct) -> ::core::ffi::c_ulonglong;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_stime_exists(task_struct: *mut task_struct) -> bool;
+}
 unsafe extern "C" {
     pub fn shim_task_struct_cred(task_struct: *mut task_struct) -> *mut cred;
 }
@@ -993,6 +1369,15 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_task_struct_files_exists(task_struct: *mut task_struct) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_task_struct_fs(task_struct: *mut task_struct) -> *mut fs_struct;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_fs_user(task_struct: *mut task_struct) -> *mut fs_struct;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_fs_exists(task_struct: *mut task_struct) -> bool;
+}
 unsafe extern "C" {
     pub fn shim_task_struct_nsproxy(task_struct: *mut task_struct) -> *mut nsproxy;
 }
@@ -1012,6 +1397,47 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_task_struct_sched_task_group_exists(task_struct: *mut task_struct) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_task_struct_atomic_flags(task_struct: *mut task_struct) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_atomic_flags_user(
+        task_struct: *mut task_struct,
+    ) -> ::core::ffi::c_ulong;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_atomic_flags_exists(task_struct: *mut task_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_seccomp_mode(task_struct: *mut task_struct) -> ::core::ffi::c_int;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_seccomp_mode_exists(task_struct: *mut task_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_signal(task_struct: *mut task_struct) -> *mut signal_struct;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_signal_user(task_struct: *mut task_struct) -> *mut signal_struct;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_signal_exists(task_struct: *mut task_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_loginuid(task_struct: *mut task_struct) -> uid_t;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_loginuid_exists(task_struct: *mut task_struct) -> bool;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_sessionid(task_struct: *mut task_struct) -> ::core::ffi::c_uint;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_sessionid_user(task_struct: *mut task_struct) -> ::core::ffi::c_uint;
+}
+unsafe extern "C" {
+    pub fn shim_task_struct_sessionid_exists(task_struct: *mut task_struct) -> bool;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct bpf_ksym {
@@ -1422,6 +1848,11 @@ unsafe extern "C" {
     pub fn shim_sockaddr_in6_sin6_addr_exists(sockaddr_in6: *mut sockaddr_in6) -> bool;
 }
 #[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct possible_net_t {
+    pub net: *mut net,
+}
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub struct sock_common {
     pub __bindgen_anon_1: sock_common__bindgen_ty_1,
@@ -1429,6 +1860,7 @@ pub struct sock_common {
     pub skc_family: ::core::ffi::c_ushort,
     pub skc_v6_daddr: in6_addr,
     pub skc_v6_rcv_saddr: in6_addr,
+    pub skc_net: possible_net_t,
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -1490,6 +1922,43 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_sock_common_skc_v6_rcv_saddr_exists(sock_common: *mut sock_common) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_sock_common_skc_net(sock_common: *mut sock_common) -> *mut net;
+}
+unsafe extern "C" {
+    pub fn shim_sock_common_skc_net_user(sock_common: *mut sock_common) -> *mut net;
+}
+unsafe extern "C" {
+    pub fn shim_sock_common_skc_net_exists(sock_common: *mut sock_common) -> bool;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct net_device {
+    pub name: [::core::ffi::c_char; 16usize],
+}
+unsafe extern "C" {
+    pub fn shim_net_device_name(net_device: *mut net_device) -> *mut ::core::ffi::c_char;
+}
+unsafe extern "C" {
+    pub fn shim_net_device_name_user(net_device: *mut net_device) -> *mut ::core::ffi::c_char;
+}
+unsafe extern "C" {
+    pub fn shim_net_device_name_exists(net_device: *mut net_device) -> bool;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct dst_entry {
+    pub dev: *mut net_device,
+}
+unsafe extern "C" {
+    pub fn shim_dst_entry_dev(dst_entry: *mut dst_entry) -> *mut net_device;
+}
+unsafe extern "C" {
+    pub fn shim_dst_entry_dev_user(dst_entry: *mut dst_entry) -> *mut net_device;
+}
+unsafe extern "C" {
+    pub fn shim_dst_entry_dev_exists(dst_entry: *mut dst_entry) -> bool;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct sk_buff {
@@ -1589,6 +2058,7 @@ pub struct sock {
     pub sk_protocol: __u8,
     pub sk_type: __u16,
     pub sk_receive_queue: sk_buff_head,
+    pub sk_dst_cache: *mut dst_entry,
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -1636,6 +2106,15 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn shim_sock_sk_receive_queue_exists(sock: *mut sock) -> bool;
 }
+unsafe extern "C" {
+    pub fn shim_sock_sk_dst_cache(sock: *mut sock) -> *mut dst_entry;
+}
+unsafe extern "C" {
+    pub fn shim_sock_sk_dst_cache_user(sock: *mut sock) -> *mut dst_entry;
+}
+unsafe extern "C" {
+    pub fn shim_sock_sk_dst_cache_exists(sock: *mut sock) -> bool;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct socket {
@@ -84,6 +84,43 @@ impl Filter {
     }
 }
 
+/// Maximum number of ports DNS traffic is monitored on
+pub const MAX_DNS_PORTS: usize = 8;
+
+/// Set of ports DNS traffic is monitored on. Unused slots are 0.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DnsPorts {
+    ports: [u16; MAX_DNS_PORTS],
+}
+
+impl DnsPorts {
+    /// Creates [DnsPorts] from a slice. Ports which do not fit
+    /// in the set are ignored.
+    pub fn from_slice(ports: &[u16]) -> Self {
+        let mut out = Self::default();
+        for (i, p) in ports.iter().take(MAX_DNS_PORTS).enumerate() {
+            out.ports[i] = *p;
+        }
+        out
+    }
+
+    /// Returns `true` if `port` is a DNS port
+    #[inline(always)]
+    pub fn contains(&self, port: u16) -> bool {
+        if port == 0 {
+            return false;
+        }
+
+        for p in self.ports {
+            if p == port {
+                return true;
+            }
+        }
+        false
+    }
+}
+
 /// Structure holding configuration to use in eBPF programs
 #[derive(Debug, Clone, Copy)]
 pub struct BpfConfig {
@@ -92,4 +129,23 @@ pub struct BpfConfig {
     pub glob_max_eps_fs: Option<u64>,
     pub task_max_eps_fs: Option<u64>,
     pub send_data_min_len: u64,
+    pub socket_create_all_families: bool,
+    pub execve_env: bool,
+    pub execve_max_len: u32,
+    pub dns_ports: DnsPorts,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dns_ports() {
+        let p = DnsPorts::from_slice(&[53, 5353]);
+        assert!(p.contains(53));
+        assert!(p.contains(5353));
+        assert!(!p.contains(853));
+        // unused slots must not match
+        assert!(!p.contains(0));
+    }
 }
@@ -24,6 +24,11 @@ impl BpfConfig {
         self.filter.is_enabled(ty)
     }
 
+    #[inline(always)]
+    pub fn is_dns_port(&self, port: u16) -> bool {
+        self.dns_ports.contains(port)
+    }
+
     #[inline(always)]
     pub fn is_event_disabled(&self, ty: bpf_events::Type) -> bool {
         !self.filter.is_enabled(ty)
@@ -64,6 +64,8 @@ pub enum ProbeError {
     NullPointer,
     #[error("file not found")]
     FileNotFound,
+    #[error("bpf probe read failed")]
+    BpfProbeReadFailure,
     #[wrap]
     BpfMapError(MapError),
     #[wrap]
@@ -32,6 +32,7 @@ pub enum ProbeFn {
     dns_sys_recv_from,
     net_dns_sys_recvmsg,
     net_sys_connect,
+    net_sys_connect_file,
     fs_security_sb_mount,
     sk_sk_attach_prog,
     sk_reuseport_attach_prog,
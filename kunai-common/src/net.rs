@@ -1,6 +1,11 @@
 use kunai_macros::{BpfError, StrEnum};
 
-use crate::{errors::ProbeError, macros::bpf_target_code, macros::not_bpf_target_code};
+use crate::{
+    errors::ProbeError, macros::bpf_target_code, macros::not_bpf_target_code, string::String,
+};
+
+/// Maximum size of a network interface name
+pub const IFNAMSIZ: usize = 16;
 
 not_bpf_target_code! {
     mod user;
@@ -257,6 +262,11 @@ pub struct SocketInfo {
     pub ty: u16,
     /// Value of socket.sk_protocol
     pub proto: u16,
+    /// Inode of the network namespace the socket belongs to
+    pub netns: u32,
+    /// Name of the interface packets are routed through,
+    /// empty if no route is cached for the socket
+    pub ifname: String<IFNAMSIZ>,
 }
 
 impl SocketInfo {
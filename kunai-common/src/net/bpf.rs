@@ -126,7 +126,16 @@ impl TryFrom<crate::co_re::sock> for SocketInfo {
             let domain =
                 core_read_kernel!(s, sk_common, skc_family).ok_or(Error::SkcFamilyMissing)?;
             let proto = core_read_kernel!(s, sk_protocol).ok_or(Error::SkProtocolMissing)?;
-            Ok(Self { domain, ty, proto })
+            // those are not always available (i.e. unconnected sockets)
+            let netns = core_read_kernel!(s, sk_common, skc_net, ns, inum).unwrap_or_default();
+            let ifname = core_read_kernel!(s, sk_dst_cache, dev, name_str).unwrap_or_default();
+            Ok(Self {
+                domain,
+                ty,
+                proto,
+                netns,
+                ifname,
+            })
         }
     }
 }
@@ -13,6 +13,7 @@ not_bpf_target_code! {
 
 bpf_target_code! {
     mod bpf;
+    pub use bpf::Resume;
 }
 
 // for path resolution
@@ -1,3 +1,4 @@
+use crate::buffer::Buffer;
 use crate::co_re::{self, core_read_kernel};
 use aya_ebpf::check_bounds_signed;
 use aya_ebpf::helpers::gen;
@@ -14,6 +15,14 @@ fn xor_shift_star(a: u64, b: u64) -> u64 {
     return x * 0x2545F4914F6CDD1D;
 }
 
+/// State needed to resume a path resolution which
+/// stopped because the path buffer got full
+#[derive(Clone, Copy)]
+pub struct Resume {
+    entry: co_re::dentry,
+    mount: co_re::mount,
+}
+
 impl Path {
     #[inline(always)]
     unsafe fn init_from_inode(&mut self, i: &co_re::inode) -> Result<()> {
@@ -53,6 +62,73 @@ impl Path {
         }
     }
 
+    /// Resolves the path where `mnt` is mounted
+    #[inline(always)]
+    pub unsafe fn core_resolve_vfsmount(
+        &mut self,
+        mnt: &co_re::vfsmount,
+        max_depth: u16,
+    ) -> Result<()> {
+        // if mnt is null we return Ok
+        // this is mostly to massage our friend verifier
+        if mnt.is_null() {
+            return Ok(());
+        }
+
+        let root = mnt.mnt_root().ok_or(Error::MntRootMissing)?;
+        match self.inner_resolve_dentry(&root, mnt, max_depth) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                self.error = Some(e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Resolves the path of a dentry when no vfsmount is available. The
+    /// first mount of the dentry's super_block is used to cross mountpoints
+    /// so the path might not be accurate for bind mounted directories.
+    #[inline(always)]
+    pub unsafe fn core_resolve_dentry(&mut self, d: &co_re::dentry, max_depth: u16) -> Result<()> {
+        if d.is_null() {
+            return Ok(());
+        }
+
+        let mount = core_read_kernel!(d, d_sb)
+            .and_then(|sb| sb.first_mount())
+            .ok_or(Error::RFMountPtr)?;
+        let mnt = mount.mnt().ok_or(Error::MissingMountMnt)?;
+
+        match self.inner_resolve_dentry(d, &mnt, max_depth) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                self.error = Some(e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Resolves the path of a dentry known to live under `mnt`
+    #[inline(always)]
+    pub unsafe fn core_resolve_dentry_in(
+        &mut self,
+        d: &co_re::dentry,
+        mnt: &co_re::vfsmount,
+        max_depth: u16,
+    ) -> Result<()> {
+        if d.is_null() || mnt.is_null() {
+            return Ok(());
+        }
+
+        match self.inner_resolve_dentry(d, mnt, max_depth) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                self.error = Some(e);
+                Err(e)
+            }
+        }
+    }
+
     #[inline(always)]
     unsafe fn inner_resolve(&mut self, p: &co_re::path, max_depth: u16) -> Result<()> {
         // if path is null we return Ok
@@ -61,19 +137,113 @@ impl Path {
             return Ok(());
         }
 
-        let mut entry = p.dentry().ok_or(Error::DentryMissing)?;
+        let entry = p.dentry().ok_or(Error::DentryMissing)?;
+        let mnt = p.mnt().ok_or(Error::RFPathMnt)?;
+
+        self.inner_resolve_dentry(&entry, &mnt, max_depth)
+    }
+
+    #[inline(always)]
+    unsafe fn init_resolve(&mut self, entry: &co_re::dentry) -> Result<()> {
         let d_inode = core_read_kernel!(entry, d_inode).ok_or(Error::DentryDinode)?;
 
         // initialization
         self.mode = Mode::Prepend;
-        self.init_from_inode(&d_inode)?;
+        self.init_from_inode(&d_inode)
+    }
+
+    #[inline(always)]
+    unsafe fn inner_resolve_dentry(
+        &mut self,
+        entry: &co_re::dentry,
+        mnt: &co_re::vfsmount,
+        max_depth: u16,
+    ) -> Result<()> {
+        self.init_resolve(entry)?;
+
+        match self.walk(entry, &mnt.mount(), max_depth)? {
+            Some(_) => Err(Error::FilePathTooLong),
+            None => Ok(()),
+        }
+    }
 
+    /// Same as [Path::core_resolve_file] but when the path does not fit
+    /// in the buffer, a [Resume] is returned so that resolution can go on
+    /// with [Path::core_resume] once the buffer has been flushed.
+    #[inline(always)]
+    pub unsafe fn core_resolve_file_resumable(
+        &mut self,
+        f: &co_re::file,
+        max_depth: u16,
+    ) -> Result<Option<Resume>> {
+        if f.is_null() {
+            return Ok(None);
+        }
+
+        let p = f.f_path().ok_or(Error::FPathMissing)?;
+        let entry = p.dentry().ok_or(Error::DentryMissing)?;
         let mnt = p.mnt().ok_or(Error::RFPathMnt)?;
-        let mut mount = mnt.mount();
+
+        let res = self
+            .init_resolve(&entry)
+            .and_then(|_| self.walk(&entry, &mnt.mount(), max_depth));
+        self.track_resumable(res)
+    }
+
+    /// Resumes a path resolution, the path buffer is reset
+    /// so it must have been flushed before calling this
+    #[inline(always)]
+    pub unsafe fn core_resume(&mut self, r: &Resume, max_depth: u16) -> Result<Option<Resume>> {
+        self.len = 0;
+        self.error = None;
+        let res = self.walk(&r.entry, &r.mount, max_depth);
+        self.track_resumable(res)
+    }
+
+    #[inline(always)]
+    fn track_resumable(&mut self, res: Result<Option<Resume>>) -> Result<Option<Resume>> {
+        match res {
+            Ok(Some(r)) => {
+                self.error = Some(Error::FilePathTooLong);
+                Ok(Some(r))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                self.error = Some(e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Copies the part of the path resolved so far into `buf`
+    #[inline(always)]
+    pub unsafe fn flush_into<const N: usize>(&self, buf: &mut Buffer<N>) -> Result<()> {
+        let len = self.len() as i64;
+        if !check_bounds_signed(len, 0, MAX_PATH_LEN as i64) {
+            return Err(Error::OutOfBound);
+        }
+
+        let start = self.buffer.len() - len as usize;
+        buf.read_kernel_at(self.buffer.as_ptr().add(start), len as u32)
+            .map_err(|_| Error::BpfProbeReadFailure)
+    }
+
+    /// Walks up the dentry tree from `entry` and prepends path components.
+    /// If the path buffer gets full, the walk stops and returns the point
+    /// at which it has to be resumed.
+    #[inline(always)]
+    unsafe fn walk(
+        &mut self,
+        entry: &co_re::dentry,
+        mount: &co_re::mount,
+        max_depth: u16,
+    ) -> Result<Option<Resume>> {
+        let mut entry = *entry;
+        let mut mount = *mount;
 
         let mut mnt_parent = mount.mnt_parent().ok_or(Error::MntParentMissing)?;
 
-        let mut mnt_root = mnt.mnt_root().ok_or(Error::MntRootMissing)?;
+        let mut mnt_root = core_read_kernel!(mount, mnt, mnt_root).ok_or(Error::MntRootMissing)?;
 
         for _i in 0..max_depth {
             if entry == mnt_root {
@@ -93,13 +263,17 @@ impl Path {
                 break;
             }
 
-            // prepend path separator
-            if !self.is_empty() {
-                self.prepend_path_sep()?;
+            // prepend path separator, this fails only if buffer is full
+            if !self.is_empty() && self.prepend_path_sep().is_err() {
+                return Ok(Some(Resume { entry, mount }));
             }
 
             // prepend segment
-            self.prepend_dentry(&entry)?;
+            match self.prepend_dentry(&entry) {
+                Ok(()) => {}
+                Err(Error::FilePathTooLong) => return Ok(Some(Resume { entry, mount })),
+                Err(e) => return Err(e),
+            }
 
             if parent.is_null() {
                 break;
@@ -108,9 +282,11 @@ impl Path {
         }
 
         // we read root
-        self.prepend_dentry(&entry)?;
-
-        Ok(())
+        match self.prepend_dentry(&entry) {
+            Ok(()) => Ok(None),
+            Err(Error::FilePathTooLong) => Ok(Some(Resume { entry, mount })),
+            Err(e) => Err(e),
+        }
     }
 
     fn prepend_path_sep(&mut self) -> Result<()> {
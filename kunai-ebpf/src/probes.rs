@@ -18,22 +18,36 @@ mod debug;
 
 mod bpf;
 mod bpf_socket;
+mod cgroup;
 mod clone;
 mod connect;
 mod dns;
 mod execve;
+mod execve_failed;
 mod exit;
 mod fs;
 mod init_module;
+mod io_uring;
+mod kernel_tamper;
 mod kill;
 mod lsm;
+mod lsm_label;
 mod mmap;
+mod mount;
 mod mprotect;
 mod prctl;
+mod process_memory;
 mod ptrace;
 mod schedule;
+mod seccomp;
 mod send_data;
+mod shell;
+mod socket_create;
 mod syscore_resume;
+mod sysctl;
+mod tls_lib;
+
+use io_uring::is_io_uring_context;
 
 /// macro to track ignored results
 macro_rules! ignore_result {
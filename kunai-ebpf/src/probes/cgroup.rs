@@ -0,0 +1,61 @@
+use aya_ebpf::programs::ProbeContext;
+
+use super::*;
+
+// cgroups are created and removed by container runtimes when containers
+// start and stop. Those events are only used in userland to keep track
+// of containers' metadata and are never displayed.
+#[kprobe(function = "cgroup_mkdir")]
+pub fn enter_cgroup_mkdir(ctx: ProbeContext) -> u32 {
+    match unsafe { try_enter_cgroup_mkdir(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_enter_cgroup_mkdir(ctx: &ProbeContext) -> ProbeResult<()> {
+    let parent = co_re::kernfs_node::from_ptr(kprobe_arg!(ctx, 0)?);
+    let name: *const u8 = kprobe_arg!(ctx, 1)?;
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<CgroupEvent>()?;
+
+    event.init_from_current_task(Type::CgroupMkdir)?;
+
+    // path may be truncated but the leaf, which is the
+    // part userland is interested in, is resolved first
+    ignore_result!(event.data.cgroup.resolve_new(parent, name));
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
+
+#[kprobe(function = "cgroup_rmdir")]
+pub fn enter_cgroup_rmdir(ctx: ProbeContext) -> u32 {
+    match unsafe { try_enter_cgroup_rmdir(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_enter_cgroup_rmdir(ctx: &ProbeContext) -> ProbeResult<()> {
+    let kn = co_re::kernfs_node::from_ptr(kprobe_arg!(ctx, 0)?);
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<CgroupEvent>()?;
+
+    event.init_from_current_task(Type::CgroupRmdir)?;
+
+    ignore_result!(event.data.cgroup.resolve_kn(kn));
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
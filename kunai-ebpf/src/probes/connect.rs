@@ -55,23 +55,11 @@ unsafe fn try_exit_connect(
     let addr = co_re::sockaddr::from_ptr(kprobe_arg!(entry_ctx, 1)?);
     let sa_family = core_read_user!(addr, sa_family)?;
 
-    let current = task_struct::current();
-
     // get the file corresponding to that fd
-    let file = current
+    let file = task_struct::current()
         .get_fd(fd as usize)
         .ok_or(ProbeError::FileNotFound)?;
 
-    // we raise an error if file is null
-    if file.is_null() {
-        return Err(ProbeError::NullPointer);
-    }
-
-    alloc::init()?;
-    let event = alloc::alloc_zero::<ConnectEvent>()?;
-
-    event.init_from_task(Type::Connect, current)?;
-
     let dst = match sa_family {
         AF_INET => {
             let in_addr: co_re::sockaddr_in = addr.into();
@@ -91,6 +79,94 @@ unsafe fn try_exit_connect(
         _ => return Ok(()),
     };
 
+    connect_event(exit_ctx, &file, dst, rc, false)
+}
+
+// __sys_connect_file is called by __sys_connect but also by io_uring
+// to process IORING_OP_CONNECT, in which case the address has already
+// been copied in kernel memory. To prevent duplicates we only process
+// calls made from an io_uring context.
+#[kprobe(function = "__sys_connect_file")]
+pub fn net_enter_sys_connect_file(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() || !unsafe { is_io_uring_context() } {
+        return 0;
+    }
+
+    unsafe { ignore_result!(ProbeFn::net_sys_connect_file.save_ctx(&ctx)) }
+    0
+}
+
+#[kretprobe(function = "__sys_connect_file")]
+pub fn net_exit_sys_connect_file(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() || !unsafe { is_io_uring_context() } {
+        return 0;
+    }
+
+    let rc = match unsafe {
+        ProbeFn::net_sys_connect_file
+            .restore_ctx()
+            .map_err(ProbeError::from)
+            .and_then(|ent_ctx| try_exit_connect_file(ent_ctx, &ctx))
+    } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    };
+    ignore_result!(unsafe { ProbeFn::net_sys_connect_file.clean_ctx() });
+    rc
+}
+
+unsafe fn try_exit_connect_file(
+    entry_ctx: &mut KProbeEntryContext,
+    exit_ctx: &RetProbeContext,
+) -> ProbeResult<()> {
+    let rc = exit_ctx.ret().unwrap_or(-1);
+
+    let entry_ctx = &entry_ctx.probe_context();
+    let file = co_re::file::from_ptr(kprobe_arg!(entry_ctx, 0)?);
+    let addr = co_re::sockaddr::from_ptr(kprobe_arg!(entry_ctx, 1)?);
+    let sa_family = core_read_kernel!(addr, sa_family)?;
+
+    let dst = match sa_family {
+        AF_INET => {
+            let in_addr: co_re::sockaddr_in = addr.into();
+            let ip = core_read_kernel!(in_addr, s_addr)?.to_be();
+            let port = core_read_kernel!(in_addr, sin_port)?.to_be();
+
+            SockAddr::new_v4_from_be(ip, port)
+        }
+        AF_INET6 => {
+            let in6_addr: co_re::sockaddr_in6 = addr.into();
+            let ip = core_read_kernel!(in6_addr, sin6_addr)?;
+            let port = core_read_kernel!(in6_addr, sin6_port)?.to_be();
+            SockAddr::new_v6_from_be(core_read_kernel!(ip, addr32)?, port)
+        }
+        _ => return Ok(()),
+    };
+
+    connect_event(exit_ctx, &file, dst, rc, true)
+}
+
+#[inline(always)]
+unsafe fn connect_event(
+    exit_ctx: &RetProbeContext,
+    file: &co_re::file,
+    dst: SockAddr,
+    rc: i32,
+    io_uring: bool,
+) -> ProbeResult<()> {
+    // we raise an error if file is null
+    if file.is_null() {
+        return Err(ProbeError::NullPointer);
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<ConnectEvent>()?;
+
+    event.init_from_task(Type::Connect, task_struct::current())?;
+
     let socket = co_re::socket::from_ptr(core_read_kernel!(file, private_data)? as *const _);
     let sk = core_read_kernel!(socket, sk)?;
     // retrieve sock_common to grap src information
@@ -100,6 +176,7 @@ unsafe fn try_exit_connect(
     event.data.src = SockAddr::src_from_sock_common(sk_common)?;
     event.data.dst = dst;
     event.data.connected = rc == 0 || rc == -EINPROGRESS;
+    event.data.io_uring = io_uring;
 
     pipe_event(exit_ctx, event);
 
@@ -1,6 +1,7 @@
 use super::*;
 use aya_ebpf::{
     cty::{c_int, c_void, size_t},
+    maps::LruHashMap,
     programs::{ProbeContext, RetProbeContext},
 };
 
@@ -56,7 +57,7 @@ impl SockHelper {
         };
 
         // we don't take protocol communicating on other ports than dns
-        if dst.port() != 53 {
+        if !get_cfg!()?.is_dns_port(dst.port()) {
             return Ok(());
         }
 
@@ -67,6 +68,7 @@ impl SockHelper {
         event.data.src = SockAddr::src_from_sock_common(sk_common)?;
         event.data.dst = dst;
         event.data.tcp_header = tcp_header;
+        event.data.io_uring = is_io_uring_context();
 
         match self.udata {
             Udata::Buf(ubuf, size) => {
@@ -97,7 +99,7 @@ unsafe fn is_dns_dst(sock: &co_re::sock) -> Result<bool, ProbeError> {
     let dst = SockAddr::dst_from_sock_common(sock_common)?;
 
     // filter on dst port
-    Ok(dst.port() == 53)
+    Ok(get_cfg!()?.is_dns_port(dst.port()))
 }
 
 #[kprobe(function = "vfs_read")]
@@ -423,3 +425,107 @@ unsafe fn try_exit_recvmsg(exit_ctx: &RetProbeContext) -> ProbeResult<()> {
     sh.dns_event(exit_ctx, server, false)?;
     Ok(())
 }
+
+#[repr(C)]
+struct IoUringRecv {
+    socket: co_re::socket,
+    // beginning of the user buffer data is received in
+    ubuf: *const c_void,
+}
+
+// io_uring receptions on DNS sockets being processed
+#[map]
+static mut IO_URING_DNS_RECV: LruHashMap<u64, IoUringRecv> = LruHashMap::with_max_entries(1024, 0);
+
+// IORING_OP_RECV, IORING_OP_RECVMSG (and IORING_OP_READ on a socket) don't
+// go through the syscalls hooked above but they all end up in sock_recvmsg.
+// As sock_recvmsg is on the path of any socket reception both probes bail
+// out before touching any map if we are not processing io_uring requests.
+#[kprobe(function = "sock_recvmsg")]
+pub fn net_dns_enter_sock_recvmsg(ctx: ProbeContext) -> u32 {
+    // receptions made from syscalls are handled by the other probes
+    if !unsafe { is_io_uring_context() } || is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_sock_recvmsg(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_enter_sock_recvmsg(ctx: &ProbeContext) -> ProbeResult<()> {
+    let socket = co_re::socket::from_ptr(kprobe_arg!(ctx, 0)?);
+    let msg = co_re::msghdr::from_ptr(kprobe_arg!(ctx, 1)?);
+    let sock = core_read_kernel!(socket, sk)?;
+
+    if !is_dns_dst(&sock)? {
+        return Ok(());
+    }
+
+    // iterator is advanced while data is copied so we
+    // need to keep where the user buffer starts
+    let iter = core_read_kernel!(msg, msg_iter)?;
+    let ubuf = if iter.is_iter_ubuf() {
+        core_read_kernel!(iter, ubuf)?
+    } else if iter.is_iter_iovec() {
+        core_read_kernel!(iter, iov, iov_base)?
+    } else {
+        return Ok(());
+    };
+
+    IO_URING_DNS_RECV
+        .insert(
+            &bpf_task_tracking_id(),
+            &IoUringRecv {
+                socket,
+                ubuf: ubuf as *const _,
+            },
+            0,
+        )
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[kretprobe(function = "sock_recvmsg")]
+pub fn net_dns_exit_sock_recvmsg(ctx: RetProbeContext) -> u32 {
+    // nothing was tracked at entry
+    if !unsafe { is_io_uring_context() } || is_current_loader_task() {
+        return 0;
+    }
+
+    let rc = match unsafe { try_exit_sock_recvmsg(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    };
+    ignore_result!(unsafe { IO_URING_DNS_RECV.remove(&bpf_task_tracking_id()) });
+    rc
+}
+
+#[inline(always)]
+unsafe fn try_exit_sock_recvmsg(exit_ctx: &RetProbeContext) -> ProbeResult<()> {
+    let Some(recv) = IO_URING_DNS_RECV.get(&bpf_task_tracking_id()) else {
+        return Ok(());
+    };
+
+    let rc = exit_ctx.ret().unwrap_or(-1);
+
+    // rc is also the size of the data read so we don't irrelevant cases
+    if rc < DNS_HEADER_SIZE as i32 {
+        return Ok(());
+    }
+
+    let sh = SockHelper::from_ubuf(recv.socket, recv.ubuf, rc as usize);
+
+    // like with vfs_read data may contain TCP length header
+    sh.dns_event(exit_ctx, None, true)?;
+
+    Ok(())
+}
@@ -1,10 +1,15 @@
 use super::*;
 
+use aya_ebpf::helpers::bpf_ktime_get_ns;
 use aya_ebpf::maps::LruHashMap;
 use aya_ebpf::programs::{ProbeContext, RetProbeContext, TracePointContext};
 use aya_ebpf::EbpfContext;
 use co_re::task_struct;
-use kunai_common::syscalls::SysExitArgs;
+use core::cmp::min;
+use kunai_common::{
+    net::{SockAddr, SocketInfo},
+    syscalls::SysExitArgs,
+};
 
 const MAP_SIZE: u32 = 2048;
 
@@ -50,11 +55,18 @@ unsafe fn try_security_bprm_check(ctx: &ProbeContext) -> ProbeResult<()> {
     alloc::init()?;
     let event = alloc::alloc_zero::<ExecveEvent>()?;
 
+    // chunks are tagged so that userland never joins the
+    // chunks of an execve which failed to the next one
+    event.data.chunk_id = bpf_ktime_get_ns();
+
     if let Some(file) = linux_binprm.file() {
-        event
-            .data
-            .executable
-            .core_resolve_file(&file, MAX_PATH_DEPTH)?;
+        event.data.truncated |= resolve_file_chunked(
+            ctx,
+            event.data.chunk_id,
+            ChunkKind::Executable,
+            &mut event.data.executable,
+            &file,
+        )?;
     }
 
     // read uts nodename and store it in event
@@ -71,6 +83,127 @@ unsafe fn try_security_bprm_check(ctx: &ProbeContext) -> ProbeResult<()> {
     Ok(())
 }
 
+/// Resolves `file` into `path`, the part of the path not fitting in `path`
+/// is sent as chunks. Returns `true` if path could not be sent entirely.
+#[inline(always)]
+unsafe fn resolve_file_chunked<C: EbpfContext>(
+    ctx: &C,
+    id: u64,
+    kind: ChunkKind,
+    path: &mut Path,
+    file: &co_re::file,
+) -> ProbeResult<bool> {
+    let mut resume = path.core_resolve_file_resumable(file, MAX_PATH_DEPTH)?;
+
+    if resume.is_none() {
+        return Ok(false);
+    }
+
+    let max_len = get_cfg!()?.execve_max_len as usize;
+    let chunk = alloc::alloc_zero::<ChunkEvent>()?;
+    chunk.init_from_current_task(Type::Chunk)?;
+    chunk.data.id = id;
+    chunk.data.kind = kind;
+
+    let mut sent = 0;
+    for i in 0..MAX_PATH_CHUNKS {
+        let Some(r) = resume else {
+            break;
+        };
+
+        if sent >= max_len {
+            break;
+        }
+
+        chunk.data.index = i;
+        path.flush_into(&mut chunk.data.data)?;
+        sent += chunk.data.data.len();
+        pipe_event(ctx, chunk);
+
+        resume = path.core_resume(&r, MAX_PATH_DEPTH)?;
+    }
+
+    Ok(resume.is_some())
+}
+
+/// Sends user memory from `start + skip` up to `start + len` as chunks of
+/// `kind`. Returns the offset up to which memory has been sent.
+#[inline(always)]
+unsafe fn send_user_chunks<C: EbpfContext, const MAX_CHUNKS: u32>(
+    ctx: &C,
+    current: task_struct,
+    id: u64,
+    kind: ChunkKind,
+    start: u64,
+    skip: u64,
+    len: u64,
+) -> ProbeResult<u64> {
+    let chunk = alloc::alloc_zero::<ChunkEvent>()?;
+    chunk.init_from_task(Type::Chunk, current)?;
+    chunk.data.id = id;
+    chunk.data.kind = kind;
+
+    let mut off = skip;
+    for i in 0..MAX_CHUNKS {
+        if off >= len {
+            break;
+        }
+
+        let size = min(len - off, CHUNK_SIZE as u64);
+        chunk.data.index = i;
+        if chunk
+            .data
+            .data
+            .read_user_at((start + off) as *const u8, size as u32)
+            .is_err()
+        {
+            warn!(ctx, "failed to read chunk");
+            break;
+        }
+        pipe_event(ctx, chunk);
+        off += size;
+    }
+
+    Ok(off)
+}
+
+/// Fills `stdio` with information about the file behind `fd`
+#[inline(always)]
+unsafe fn stdio_fd(stdio: &mut StdioFd, task: task_struct, fd: usize) -> ProbeResult<()> {
+    let Some(file) = task.get_fd(fd) else {
+        return Ok(());
+    };
+
+    // fd is not open
+    if file.is_null() {
+        return Ok(());
+    }
+
+    stdio.open = true;
+
+    let inode = core_read_kernel!(file, f_inode)?;
+    stdio.mode = core_read_kernel!(inode, i_mode)?;
+    stdio.rdev = core_read_kernel!(inode, i_rdev)?;
+
+    if stdio.is_sock() {
+        let socket = co_re::socket::from_ptr(core_read_kernel!(file, private_data)? as *const _);
+        let sk = core_read_kernel!(socket, sk)?;
+        stdio.socket = SocketInfo::try_from(sk).ok();
+        // only inet sockets have a remote address
+        stdio.dst = core_read_kernel!(sk, sk_common)
+            .ok()
+            .and_then(|skc| SockAddr::dst_from_sock_common(skc).ok());
+        return Ok(());
+    }
+
+    // anonymous pipes do not have a meaningful path
+    if !stdio.is_fifo() {
+        stdio.path.core_resolve_file(&file, MAX_PATH_DEPTH)?;
+    }
+
+    Ok(())
+}
+
 #[map]
 static mut BPRM_EXECVE_ARGS: LruHashMap<u64, co_re::linux_binprm> =
     LruHashMap::with_max_entries(MAP_SIZE, 0);
@@ -109,20 +242,23 @@ unsafe fn execve_event<C: EbpfContext>(ctx: &C, rc: i32) -> ProbeResult<()> {
 
     let event = &mut (*event);
 
-    // initializing event
-    event.init_from_task(Type::Execve, current)?;
+    alloc::init()?;
+
+    // chunks must be sent before the event is initialized so that
+    // their timestamps make them processed first in userland
 
     // file should not be null here
     // we are getting interpreter which is set to the file attribute by exec_binprm kernel function
     if let Some(file) = linux_binprm.file() {
-        event
-            .data
-            .interpreter
-            .core_resolve_file(&file, MAX_PATH_DEPTH)?;
+        event.data.truncated |= resolve_file_chunked(
+            ctx,
+            event.data.chunk_id,
+            ChunkKind::Interpreter,
+            &mut event.data.interpreter,
+            &file,
+        )?;
     }
 
-    event.data.rc = rc;
-
     let arg_start = core_read_kernel!(current, mm, arg_start)?;
     let arg_len = core_read_kernel!(current, mm, arg_len)?;
 
@@ -136,6 +272,70 @@ unsafe fn execve_event<C: EbpfContext>(ctx: &C, rc: i32) -> ProbeResult<()> {
         warn!(ctx, "failed to read argv")
     }
 
+    event.data.argv_len = arg_len as u32;
+    if arg_len > MAX_ARGV_SIZE as u64 {
+        let max_len = min(get_cfg!()?.execve_max_len as u64, arg_len);
+        let sent = send_user_chunks::<_, MAX_ARGV_CHUNKS>(
+            ctx,
+            current,
+            event.data.chunk_id,
+            ChunkKind::Argv,
+            arg_start,
+            MAX_ARGV_SIZE as u64,
+            max_len,
+        )?;
+        event.data.truncated |= sent < arg_len;
+    }
+
+    // environment is filtered in userland so we
+    // read it only if at least a variable is wanted
+    if get_cfg!()?.execve_env {
+        let env_start = core_read_kernel!(current, mm, env_start)?;
+        let env_len = core_read_kernel!(current, mm, env_len)?;
+
+        if event
+            .data
+            .env
+            .read_user_at(env_start as *const u8, env_len as u32)
+            .is_err()
+        {
+            warn!(ctx, "failed to read environment")
+        }
+
+        event.data.env_len = env_len as u32;
+        if env_len > MAX_ENV_SIZE as u64 {
+            let sent = send_user_chunks::<_, MAX_ENV_CHUNKS>(
+                ctx,
+                current,
+                event.data.chunk_id,
+                ChunkKind::Env,
+                env_start,
+                MAX_ENV_SIZE as u64,
+                env_len,
+            )?;
+            event.data.truncated |= sent < env_len;
+        }
+    }
+
+    // working directory
+    let pwd = core_read_kernel!(current, fs, pwd)?;
+    ignore_result!(inspect_err!(
+        event.data.cwd.core_resolve(&pwd, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve cwd", (*e).into())
+    ));
+
+    // standard file descriptors
+    for (fd, stdio) in event.data.stdio.iter_mut().enumerate() {
+        if stdio_fd(stdio, current, fd).is_err() {
+            warn!(ctx, "failed to get stdio information")
+        }
+    }
+
+    // initializing event
+    event.init_from_task(Type::Execve, current)?;
+
+    event.data.rc = rc;
+
     // cgroup parsing
     let cgroup = core_read_kernel!(current, sched_task_group, css, cgroup)?;
     // we do not raise any error on cgroup parsing, we let a chance to userland to solve it
@@ -150,11 +350,21 @@ unsafe fn execve_event<C: EbpfContext>(ctx: &C, rc: i32) -> ProbeResult<()> {
     Ok(())
 }
 
+/// Drops what has been tracked for an execve which failed
+/// so that it does not get mixed with the next execve
+#[inline(always)]
+unsafe fn execve_failed_cleanup() {
+    // we use LruHashMap so we can safely ignore results
+    ignore_result!(EXECVE_TRACKING.remove(&task_struct::current().uuid()));
+    ignore_result!(BPRM_EXECVE_ARGS.remove(&bpf_task_tracking_id()));
+}
+
 unsafe fn try_bprm_execve(ctx: &RetProbeContext) -> ProbeResult<()> {
     let rc = ctx.ret().unwrap_or(-1);
 
     // execve failed
     if rc < 0 {
+        execve_failed_cleanup();
         return Ok(());
     }
 
@@ -197,6 +407,7 @@ pub unsafe fn try_sys_exit_execve(ctx: &TracePointContext) -> Result<(), ProbeEr
     let rc = args.ret as i32;
 
     if rc < 0 {
+        execve_failed_cleanup();
         return Ok(());
     }
 
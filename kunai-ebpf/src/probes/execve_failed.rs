@@ -0,0 +1,191 @@
+use super::*;
+
+use aya_ebpf::helpers::bpf_probe_read_user;
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::TracePointContext;
+use co_re::task_struct;
+use kunai_common::syscalls::{SysEnterArgs, SysExitArgs};
+
+// special dirfd value meaning current working directory
+const AT_FDCWD: i64 = -100;
+
+#[map]
+static mut EXECVE_FAILED_TRACKING: LruHashMap<u64, ExecveFailedEvent> =
+    LruHashMap::with_max_entries(1024, 0);
+
+#[repr(C)]
+struct ExecveArgs {
+    filename: u64,
+    argv: u64,
+    _envp: u64,
+}
+
+#[repr(C)]
+struct ExecveatArgs {
+    fd: i64,
+    filename: u64,
+    argv: u64,
+    _envp: u64,
+    _flags: u64,
+}
+
+#[tracepoint(name = "sys_enter_execve", category = "syscalls")]
+pub fn execve_failed_sys_enter_execve(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_execve(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_execve(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::ExecveFailed, ());
+
+    let args = SysEnterArgs::<ExecveArgs>::from_context(ctx)?.args;
+
+    track_execve(ctx, AT_FDCWD, args.filename, args.argv)
+}
+
+#[tracepoint(name = "sys_enter_execveat", category = "syscalls")]
+pub fn execve_failed_sys_enter_execveat(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_execveat(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_execveat(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::ExecveFailed, ());
+
+    let args = SysEnterArgs::<ExecveatArgs>::from_context(ctx)?.args;
+
+    track_execve(ctx, args.fd, args.filename, args.argv)
+}
+
+#[inline(always)]
+unsafe fn track_execve(
+    ctx: &TracePointContext,
+    dirfd: i64,
+    filename: u64,
+    argv: u64,
+) -> ProbeResult<()> {
+    alloc::init()?;
+    let event = alloc::alloc_zero::<ExecveFailedEvent>()?;
+
+    let current = task_struct::current();
+
+    event.init_from_task(Type::ExecveFailed, current)?;
+
+    ignore_result!(inspect_err!(
+        event
+            .data
+            .filename
+            .read_user_str_bytes(filename as *const u8),
+        |_| warn!(ctx, "failed to read filename")
+    ));
+
+    // relative filenames are looked up from dirfd
+    if event.data.filename.s[0] != b'/' {
+        if dirfd == AT_FDCWD {
+            let pwd = core_read_kernel!(current, fs, pwd)?;
+            ignore_result!(event.data.dir.core_resolve(&pwd, MAX_PATH_DEPTH));
+        } else if let Some(file) = current.get_fd(dirfd as usize) {
+            ignore_result!(event.data.dir.core_resolve_file(&file, MAX_PATH_DEPTH));
+        }
+    }
+
+    if argv != 0 {
+        for (i, arg) in event.data.argv.iter_mut().enumerate() {
+            let Ok(p) = bpf_probe_read_user((argv as *const u64).add(i)) else {
+                break;
+            };
+
+            // argv is NULL terminated
+            if p == 0 {
+                break;
+            }
+
+            if arg.read_user_str_bytes(p as *const u8).is_err() {
+                warn!(ctx, "failed to read argument");
+                break;
+            }
+            event.data.argc += 1;
+        }
+
+        // there are more arguments than what we can report
+        if event.data.argc as usize == MAX_FAILED_ARGS {
+            event.data.truncated = matches!(
+                bpf_probe_read_user((argv as *const u64).add(MAX_FAILED_ARGS)),
+                Ok(p) if p != 0
+            );
+        }
+    }
+
+    EXECVE_FAILED_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[tracepoint(name = "sys_exit_execve", category = "syscalls")]
+pub fn execve_failed_sys_exit_execve(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_execve(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[tracepoint(name = "sys_exit_execveat", category = "syscalls")]
+pub fn execve_failed_sys_exit_execveat(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_execve(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_exit_execve(ctx: &TracePointContext) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+    let args = SysExitArgs::from_context(ctx)?;
+
+    // successful executions are reported by execve events
+    if args.ret < 0 {
+        if let Some(event) = EXECVE_FAILED_TRACKING.get_ptr_mut(&key) {
+            let event = &mut (*event);
+            event.data.rc = args.ret;
+            pipe_event(ctx, event);
+        }
+    }
+
+    // we remove item from map
+    ignore_result!(EXECVE_FAILED_TRACKING.remove(&key));
+
+    Ok(())
+}
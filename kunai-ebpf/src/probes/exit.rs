@@ -1,7 +1,22 @@
+use super::fs::{drop_process_file_writes, process_file_writes};
 use super::*;
+use aya_ebpf::helpers::bpf_ktime_get_ns;
+use aya_ebpf::maps::LruHashMap;
 use aya_ebpf::programs::TracePointContext;
+use co_re::task_struct;
 use kunai_common::syscalls::{SysEnterArgs, Syscall};
 
+// exit status and accounting are only known when the task is
+// really exiting so events are kept until sched_process_exit
+#[map]
+static mut EXIT_TRACKING: LruHashMap<u64, ExitEvent> = LruHashMap::with_max_entries(4096, 0);
+
+const PF_KTHREAD: u32 = 0x00200000;
+
+// exit_group events are sent when the last thread of the group exits
+#[map]
+static mut EXIT_GROUP_TRACKING: LruHashMap<u32, ExitEvent> = LruHashMap::with_max_entries(4096, 0);
+
 // this is important not to filter out exit event as those
 // are used to clean up some structure in userland
 #[tracepoint(name = "sys_enter_exit", category = "syscalls")]
@@ -53,7 +68,126 @@ unsafe fn try_sys_enter_exit(ctx: &TracePointContext, t: Type) -> ProbeResult<()
 
     // set event data
     event.data.error_code = args.error_code;
+
+    let res = if matches!(t, Type::ExitGroup) {
+        EXIT_GROUP_TRACKING.insert(&(event.info.process.tgid as u32), event, 0)
+    } else {
+        EXIT_TRACKING.insert(&bpf_task_tracking_id(), event, 0)
+    };
+
+    // exit events are needed by userland to cleanup its state so if we
+    // cannot keep them until the task exits we send them without accounting
+    if res.is_err() {
+        pipe_event(ctx, event);
+        return Err(MapError::InsertFailure.into());
+    }
+
+    Ok(())
+}
+
+#[tracepoint(name = "sched_process_exit", category = "sched")]
+pub fn sched_process_exit(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sched_process_exit(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn try_sched_process_exit(ctx: &TracePointContext) -> ProbeResult<()> {
+    let current = task_struct::current();
+    let key = bpf_task_tracking_id();
+    let tgid = core_read_kernel!(current, tgid)? as u32;
+
+    // the last thread of the group is exiting
+    let group_dead = core_read_kernel!(current, signal, live)? == 0;
+
+    // counter is read only once so that all the events of the
+    // group report the same value
+    let file_writes = process_file_writes(tgid);
+
+    if let Some(event) = EXIT_TRACKING.get_ptr_mut(&key) {
+        let event = &mut (*event);
+        fill_exit_data(event, current, group_dead, file_writes)?;
+        pipe_event(ctx, event);
+        ignore_result!(EXIT_TRACKING.remove(&key));
+    }
+
+    if !group_dead {
+        return Ok(());
+    }
+
+    let res = try_group_exit(ctx, current, tgid, file_writes);
+    // the counter is dropped only once the exit_group event got filled
+    drop_process_file_writes(tgid);
+    res
+}
+
+#[inline(always)]
+unsafe fn try_group_exit(
+    ctx: &TracePointContext,
+    current: task_struct,
+    tgid: u32,
+    file_writes: u64,
+) -> ProbeResult<()> {
+    if let Some(event) = EXIT_GROUP_TRACKING.get_ptr_mut(&tgid) {
+        let event = &mut (*event);
+        fill_exit_data(event, current, true, file_writes)?;
+        pipe_event(ctx, event);
+        ignore_result!(EXIT_GROUP_TRACKING.remove(&tgid));
+        return Ok(());
+    }
+
+    // there is no exit_group event for this group either because it
+    // got killed by a signal (there is no syscall to hook), its last
+    // thread called exit or because the tracked event got evicted from
+    // the LRU map. Userland needs it to cleanup so we generate it here.
+    if core_read_kernel!(current, flags)? & PF_KTHREAD == PF_KTHREAD {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<ExitEvent>()?;
+    event.init_from_task(Type::ExitGroup, current)?;
+    fill_exit_data(event, current, true, file_writes)?;
     pipe_event(ctx, event);
 
     Ok(())
 }
+
+#[inline(always)]
+unsafe fn fill_exit_data(
+    event: &mut ExitEvent,
+    task: task_struct,
+    group_dead: bool,
+    file_writes: u64,
+) -> ProbeResult<()> {
+    event.data.exit_code = core_read_kernel!(task, exit_code)?;
+    event.data.utime = core_read_kernel!(task, utime)?;
+    event.data.stime = core_read_kernel!(task, stime)?;
+
+    let start_time = if group_dead {
+        // threads already dead are accounted in signal_struct
+        let signal = core_read_kernel!(task, signal)?;
+        event.data.utime += core_read_kernel!(signal, utime)?;
+        event.data.stime += core_read_kernel!(signal, stime)?;
+        // updated by kernel before the last thread exits
+        event.data.maxrss = core_read_kernel!(signal, maxrss)?;
+        core_read_kernel!(task, group_leader, start_time)?
+    } else {
+        core_read_kernel!(task, start_time)?
+    };
+
+    event.data.lifetime = bpf_ktime_get_ns().saturating_sub(start_time);
+
+    event.data.file_writes = file_writes;
+
+    Ok(())
+}
@@ -3,12 +3,14 @@ use core::ops::Div;
 use super::*;
 
 use aya_ebpf::cty::c_int;
-use aya_ebpf::helpers::bpf_ktime_get_ns;
+use aya_ebpf::helpers::{bpf_get_current_pid_tgid, bpf_ktime_get_ns, bpf_probe_read_kernel};
 use aya_ebpf::maps::{LruHashMap, LruPerCpuHashMap};
 use aya_ebpf::programs::{ProbeContext, RetProbeContext};
 use aya_ebpf::EbpfContext;
 use kunai_common::kprobe::ProbeFn;
-use kunai_common::{bpf_events, inspect_err};
+use kunai_common::time::Time;
+use kunai_common::version::kernel_version;
+use kunai_common::{bpf_events, inspect_err, kernel};
 
 const READ: Flag = Flag(0b00000001);
 const WRITE: Flag = Flag(0b00000010);
@@ -81,6 +83,33 @@ unsafe fn file_key(file: &co_re::file) -> ProbeResult<FileKey> {
     Ok(FileKey(task_id, ino))
 }
 
+// number of regular files written by a process (tgid), this is
+// counted whatever the file events enabled and reported on exit
+#[map]
+static mut PROCESS_FILE_WRITES: LruHashMap<u32, u64> = LruHashMap::with_max_entries(8192, 0);
+
+#[inline(always)]
+unsafe fn count_file_write() {
+    let tgid = (bpf_get_current_pid_tgid() >> 32) as u32;
+    match PROCESS_FILE_WRITES.get_ptr_mut(&tgid) {
+        Some(count) => *count += 1,
+        None => ignore_result!(PROCESS_FILE_WRITES.insert(&tgid, &1, 0)),
+    }
+}
+
+/// Returns the number of regular files written by process `tgid`.
+#[inline(always)]
+pub(crate) unsafe fn process_file_writes(tgid: u32) -> u64 {
+    PROCESS_FILE_WRITES.get(&tgid).copied().unwrap_or_default()
+}
+
+/// Drops the file write counter of process `tgid`, this must be done
+/// once the whole thread group is dead and all its events are filled.
+#[inline(always)]
+pub(crate) unsafe fn drop_process_file_writes(tgid: u32) {
+    ignore_result!(PROCESS_FILE_WRITES.remove(&tgid));
+}
+
 // (task_id, sampling_ts): counter
 #[map]
 static mut THROTTLE: LruPerCpuHashMap<(u64, u64), u64> =
@@ -207,6 +236,8 @@ unsafe fn try_vfs_read(ctx: &ProbeContext) -> ProbeResult<()> {
     alloc::init()?;
     let event = alloc::alloc_zero::<FileEvent>()?;
 
+    event.data.io_uring = is_io_uring_context();
+
     ignore_result!(inspect_err!(
         event.data.path.core_resolve_file(&file, MAX_PATH_DEPTH),
         |e: &path::Error| warn!(ctx, "failed to resolve filename", (*e).into())
@@ -282,9 +313,14 @@ unsafe fn try_vfs_write(ctx: &ProbeContext) -> ProbeResult<()> {
         return Ok(());
     }
 
+    // first write to this file
+    count_file_write();
+
     alloc::init()?;
     let event = alloc::alloc_zero::<FileEvent>()?;
 
+    event.data.io_uring = is_io_uring_context();
+
     ignore_result!(inspect_err!(
         event.data.path.core_resolve_file(&file, MAX_PATH_DEPTH),
         |e: &path::Error| warn!(ctx, "failed to resolve filename", (*e).into())
@@ -538,6 +574,8 @@ unsafe fn try_enter_fput(ctx: &ProbeContext) -> ProbeResult<()> {
 
     let event = alloc::alloc_zero::<FileEvent>()?;
 
+    event.data.io_uring = is_io_uring_context();
+
     event.init_from_current_task(Type::WriteClose)?;
 
     ignore_result!(inspect_err!(
@@ -614,6 +652,9 @@ unsafe fn try_security_file_open(ctx: &ProbeContext) -> ProbeResult<()> {
 
     e.init_from_current_task(Type::FileCreate)?;
 
+    // files may be opened with IORING_OP_OPENAT
+    e.data.io_uring = is_io_uring_context();
+
     e.data.path.core_resolve_file(&file, MAX_PATH_DEPTH)?;
 
     // we send event
@@ -621,3 +662,389 @@ unsafe fn try_security_file_open(ctx: &ProbeContext) -> ProbeResult<()> {
 
     Ok(())
 }
+
+#[kprobe(function = "security_path_chmod")]
+pub fn fs_security_path_chmod(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_path_chmod(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_path_chmod(ctx: &ProbeContext) -> ProbeResult<()> {
+    // if event is disabled we return
+    if_disabled_return!(Type::FileChmod, ());
+
+    let path = co_re::path::from_ptr(kprobe_arg!(ctx, 0)?);
+    // umode_t is an unsigned short
+    let mode: u16 = kprobe_arg!(ctx, 1)?;
+
+    // we rate limit this event
+    if limit_eps_with_context(ctx)? {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<FileChmodEvent>()?;
+
+    event.init_from_current_task(Type::FileChmod)?;
+
+    event.data.old_mode = core_read_kernel!(path, dentry, d_inode, i_mode)? as u32;
+    event.data.new_mode = mode as u32;
+
+    ignore_result!(inspect_err!(
+        event.data.path.core_resolve(&path, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve path", (*e).into())
+    ));
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
+
+#[kprobe(function = "security_path_chown")]
+pub fn fs_security_path_chown(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_path_chown(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_path_chown(ctx: &ProbeContext) -> ProbeResult<()> {
+    // if event is disabled we return
+    if_disabled_return!(Type::FileChown, ());
+
+    let path = co_re::path::from_ptr(kprobe_arg!(ctx, 0)?);
+    // kuid_t and kgid_t are passed by value
+    let uid: u32 = kprobe_arg!(ctx, 1)?;
+    let gid: u32 = kprobe_arg!(ctx, 2)?;
+
+    // we rate limit this event
+    if limit_eps_with_context(ctx)? {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<FileChownEvent>()?;
+
+    event.init_from_current_task(Type::FileChown)?;
+
+    let inode = core_read_kernel!(path, dentry, d_inode)?;
+    event.data.old_uid = core_read_kernel!(inode, i_uid)?;
+    event.data.old_gid = core_read_kernel!(inode, i_gid)?;
+    event.data.new_uid = uid;
+    event.data.new_gid = gid;
+
+    ignore_result!(inspect_err!(
+        event.data.path.core_resolve(&path, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve path", (*e).into())
+    ));
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
+
+// since 5.12 xattr security hooks take a user namespace (then
+// an idmap since 6.3) as first argument
+#[kprobe(function = "security_inode_setxattr")]
+pub fn fs_security_inode_setxattr(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_inode_setxattr(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_inode_setxattr(ctx: &ProbeContext) -> ProbeResult<()> {
+    // if event is disabled we return
+    if_disabled_return!(Type::FileSetxattr, ());
+
+    let (dentry, name, size, flags): (_, *const u8, u64, c_int) =
+        if kernel_version() < kernel!(5, 12) {
+            (
+                co_re::dentry::from_ptr(kprobe_arg!(ctx, 0)?),
+                kprobe_arg!(ctx, 1)?,
+                kprobe_arg!(ctx, 3)?,
+                kprobe_arg!(ctx, 4)?,
+            )
+        } else {
+            (
+                co_re::dentry::from_ptr(kprobe_arg!(ctx, 1)?),
+                kprobe_arg!(ctx, 2)?,
+                kprobe_arg!(ctx, 4)?,
+                kprobe_arg!(ctx, 5)?,
+            )
+        };
+
+    xattr_event(ctx, Type::FileSetxattr, &dentry, name, size, flags as u32)
+}
+
+#[kprobe(function = "security_inode_removexattr")]
+pub fn fs_security_inode_removexattr(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_inode_removexattr(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_inode_removexattr(ctx: &ProbeContext) -> ProbeResult<()> {
+    // if event is disabled we return
+    if_disabled_return!(Type::FileRemovexattr, ());
+
+    let (dentry, name): (_, *const u8) = if kernel_version() < kernel!(5, 12) {
+        (
+            co_re::dentry::from_ptr(kprobe_arg!(ctx, 0)?),
+            kprobe_arg!(ctx, 1)?,
+        )
+    } else {
+        (
+            co_re::dentry::from_ptr(kprobe_arg!(ctx, 1)?),
+            kprobe_arg!(ctx, 2)?,
+        )
+    };
+
+    xattr_event(ctx, Type::FileRemovexattr, &dentry, name, 0, 0)
+}
+
+#[inline(always)]
+unsafe fn xattr_event(
+    ctx: &ProbeContext,
+    t: Type,
+    dentry: &co_re::dentry,
+    name: *const u8,
+    size: u64,
+    flags: u32,
+) -> ProbeResult<()> {
+    // we rate limit this event
+    if limit_eps_with_context(ctx)? {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<FileXattrEvent>()?;
+
+    event.init_from_current_task(t)?;
+
+    ignore_result!(inspect_err!(
+        event.data.name.read_kernel_str_bytes(name),
+        |_| warn!(ctx, "failed to read xattr name")
+    ));
+
+    event.data.size = size;
+    event.data.flags = flags;
+
+    ignore_result!(inspect_err!(
+        event.data.path.core_resolve_dentry(dentry, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve path", (*e).into())
+    ));
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
+
+// vfs_utimes appeared in 5.10 and is called by all the
+// utime(s) syscalls family
+#[kprobe(function = "vfs_utimes")]
+pub fn fs_vfs_utimes(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_vfs_utimes(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_vfs_utimes(ctx: &ProbeContext) -> ProbeResult<()> {
+    // if event is disabled we return
+    if_disabled_return!(Type::FileUtimes, ());
+
+    let path = co_re::path::from_ptr(kprobe_arg!(ctx, 0)?);
+    let times: *const co_re::timespec64 = kprobe_arg!(ctx, 1)?;
+
+    // we rate limit this event
+    if limit_eps_with_context(ctx)? {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<FileUtimesEvent>()?;
+
+    event.init_from_current_task(Type::FileUtimes)?;
+
+    // a NULL times pointer means both times are set to now
+    if times.is_null() {
+        event.data.atime = Time::new(0, UTIME_NOW);
+        event.data.mtime = Time::new(0, UTIME_NOW);
+    } else {
+        event.data.atime = bpf_probe_read_kernel(times)
+            .map_err(|_| ProbeError::BpfProbeReadFailure)?
+            .into();
+        event.data.mtime = bpf_probe_read_kernel(times.add(1))
+            .map_err(|_| ProbeError::BpfProbeReadFailure)?
+            .into();
+    }
+
+    ignore_result!(inspect_err!(
+        event.data.path.core_resolve(&path, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve path", (*e).into())
+    ));
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
+
+#[kprobe(function = "security_path_link")]
+pub fn fs_security_path_link(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_path_link(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_path_link(ctx: &ProbeContext) -> ProbeResult<()> {
+    // if event is disabled we return
+    if_disabled_return!(Type::FileLink, ());
+
+    let old_dentry = co_re::dentry::from_ptr(kprobe_arg!(ctx, 0)?);
+    let new_dir = co_re::path::from_ptr(kprobe_arg!(ctx, 1)?);
+    let new_dentry = co_re::dentry::from_ptr(kprobe_arg!(ctx, 2)?);
+
+    // we rate limit this event
+    if limit_eps_with_context(ctx)? {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<FileLinkEvent>()?;
+
+    event.init_from_current_task(Type::FileLink)?;
+
+    let inode = core_read_kernel!(old_dentry, d_inode)?;
+    event.data.uid = core_read_kernel!(inode, i_uid)?;
+    event.data.gid = core_read_kernel!(inode, i_gid)?;
+    event.data.mode = core_read_kernel!(inode, i_mode)? as u32;
+
+    // hardlinks cannot cross mountpoints so the target lives
+    // in the same mount as the directory the link is created in
+    if let Some(mnt) = new_dir.mnt() {
+        ignore_result!(inspect_err!(
+            event
+                .data
+                .target
+                .core_resolve_dentry_in(&old_dentry, &mnt, MAX_PATH_DEPTH),
+            |e: &path::Error| warn!(ctx, "failed to resolve target", (*e).into())
+        ));
+    }
+
+    // new_dentry is not yet instantiated so we build
+    // the path from its name and the parent directory
+    ignore_result!(inspect_err!(
+        event.data.link.prepend_dentry(&new_dentry),
+        |e: &path::Error| warn!(ctx, "failed to parse link dentry", (*e).into())
+    ));
+
+    ignore_result!(inspect_err!(
+        event.data.link.core_resolve(&new_dir, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve link dir", (*e).into())
+    ));
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
+
+#[kprobe(function = "security_path_symlink")]
+pub fn fs_security_path_symlink(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_path_symlink(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_path_symlink(ctx: &ProbeContext) -> ProbeResult<()> {
+    // if event is disabled we return
+    if_disabled_return!(Type::FileSymlink, ());
+
+    let dir = co_re::path::from_ptr(kprobe_arg!(ctx, 0)?);
+    let dentry = co_re::dentry::from_ptr(kprobe_arg!(ctx, 1)?);
+    // old_name has already been copied into kernel memory
+    let old_name: *const u8 = kprobe_arg!(ctx, 2)?;
+
+    // we rate limit this event
+    if limit_eps_with_context(ctx)? {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<FileSymlinkEvent>()?;
+
+    event.init_from_current_task(Type::FileSymlink)?;
+
+    ignore_result!(inspect_err!(
+        event.data.target.read_kernel_str_bytes(old_name),
+        |_| warn!(ctx, "failed to read symlink target")
+    ));
+
+    ignore_result!(inspect_err!(
+        event.data.link.prepend_dentry(&dentry),
+        |e: &path::Error| warn!(ctx, "failed to parse link dentry", (*e).into())
+    ));
+
+    ignore_result!(inspect_err!(
+        event.data.link.core_resolve(&dir, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve link dir", (*e).into())
+    ));
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
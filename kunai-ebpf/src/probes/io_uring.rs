@@ -0,0 +1,139 @@
+use super::*;
+
+use aya_ebpf::helpers::bpf_probe_read_user;
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::TracePointContext;
+use co_re::task_struct;
+use kunai_common::syscalls::{SysEnterArgs, SysExitArgs};
+
+// task flag set on io-wq and sqpoll workers
+const PF_IO_WORKER: u32 = 0x00000010;
+
+// tasks currently inside io_uring_enter syscall
+#[map]
+static mut IO_URING_ENTER: LruHashMap<u64, bool> = LruHashMap::with_max_entries(4096, 0);
+
+#[map]
+static mut IO_URING_SETUP_TRACKING: LruHashMap<u64, IoUringSetupEvent> =
+    LruHashMap::with_max_entries(256, 0);
+
+/// Returns true if the current task is processing io_uring
+/// requests, either inline from io_uring_enter syscall or
+/// asynchronously from an io_uring worker thread.
+#[inline(always)]
+pub(crate) unsafe fn is_io_uring_context() -> bool {
+    if task_struct::current().flags().unwrap_or_default() & PF_IO_WORKER == PF_IO_WORKER {
+        return true;
+    }
+    IO_URING_ENTER.get(&bpf_task_tracking_id()).is_some()
+}
+
+#[tracepoint(name = "sys_enter_io_uring_enter", category = "syscalls")]
+pub fn io_uring_syscalls_sys_enter_io_uring_enter(_ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { IO_URING_ENTER.insert(&bpf_task_tracking_id(), &true, 0) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(_) => errors::BPF_PROG_FAILURE,
+    }
+}
+
+#[tracepoint(name = "sys_exit_io_uring_enter", category = "syscalls")]
+pub fn io_uring_syscalls_sys_exit_io_uring_enter(_ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    ignore_result!(unsafe { IO_URING_ENTER.remove(&bpf_task_tracking_id()) });
+    errors::BPF_PROG_SUCCESS
+}
+
+#[repr(C)]
+struct IoUringSetupArgs {
+    entries: u64,
+    params: u64,
+}
+
+// beginning of struct io_uring_params we are interested in
+#[repr(C)]
+struct IoUringParams {
+    _sq_entries: u32,
+    _cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+}
+
+#[tracepoint(name = "sys_enter_io_uring_setup", category = "syscalls")]
+pub fn io_uring_syscalls_sys_enter_io_uring_setup(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_io_uring_setup(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_io_uring_setup(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::IoUringSetup, ());
+
+    let args = SysEnterArgs::<IoUringSetupArgs>::from_context(ctx)?.args;
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<IoUringSetupEvent>()?;
+
+    event.init_from_current_task(Type::IoUringSetup)?;
+
+    event.data.entries = args.entries as u32;
+
+    let params = bpf_probe_read_user(args.params as *const IoUringParams)
+        .map_err(|_| ProbeError::BpfProbeReadFailure)?;
+
+    event.data.flags = params.flags;
+    event.data.sq_thread_cpu = params.sq_thread_cpu;
+    event.data.sq_thread_idle = params.sq_thread_idle;
+
+    IO_URING_SETUP_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[tracepoint(name = "sys_exit_io_uring_setup", category = "syscalls")]
+pub fn io_uring_syscalls_sys_exit_io_uring_setup(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_io_uring_setup(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_exit_io_uring_setup(ctx: &TracePointContext) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+    let args = SysExitArgs::from_context(ctx)?;
+
+    if let Some(event) = IO_URING_SETUP_TRACKING.get_ptr_mut(&key) {
+        let event = &mut (*event);
+        event.data.rc = args.ret;
+        pipe_event(ctx, event);
+    }
+
+    // we remove item from map
+    ignore_result!(IO_URING_SETUP_TRACKING.remove(&key));
+
+    Ok(())
+}
@@ -0,0 +1,290 @@
+use super::*;
+
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::TracePointContext;
+use co_re::task_struct;
+use kunai_common::syscalls::{SysEnterArgs, SysExitArgs};
+
+#[map]
+static mut DELETE_MODULE_TRACKING: LruHashMap<u64, DeleteModuleEvent> =
+    LruHashMap::with_max_entries(1024, 0);
+
+#[map]
+static mut KEXEC_TRACKING: LruHashMap<u64, KexecLoadEvent> = LruHashMap::with_max_entries(256, 0);
+
+// flag passed to kexec_file_load to unload the current image
+const KEXEC_FILE_UNLOAD: u64 = 0x00000001;
+
+#[repr(C)]
+struct DeleteModuleArgs {
+    name: u64,
+    flags: u64,
+}
+
+#[tracepoint(name = "sys_enter_delete_module", category = "syscalls")]
+pub fn lkm_syscalls_sys_enter_delete_module(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_delete_module(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_delete_module(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::DeleteModule, ());
+
+    let args = SysEnterArgs::<DeleteModuleArgs>::from_context(ctx)?.args;
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<DeleteModuleEvent>()?;
+
+    event.init_from_current_task(Type::DeleteModule)?;
+
+    ignore_result!(inspect_err!(
+        event.data.name.read_user_str_bytes(args.name as *const u8),
+        |_| warn!(ctx, "failed to read module name")
+    ));
+
+    event.data.flags = args.flags;
+
+    DELETE_MODULE_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[tracepoint(name = "sys_exit_delete_module", category = "syscalls")]
+pub fn lkm_syscalls_sys_exit_delete_module(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_delete_module(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_exit_delete_module(ctx: &TracePointContext) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+    let args = SysExitArgs::from_context(ctx)?;
+
+    if let Some(event) = DELETE_MODULE_TRACKING.get_ptr_mut(&key) {
+        let event = &mut (*event);
+        event.data.rc = args.ret;
+        pipe_event(ctx, event);
+    }
+
+    // we remove item from map
+    ignore_result!(DELETE_MODULE_TRACKING.remove(&key));
+
+    Ok(())
+}
+
+#[repr(C)]
+struct KexecLoadArgs {
+    _entry: u64,
+    nr_segments: u64,
+    _segments: u64,
+    flags: u64,
+}
+
+#[tracepoint(name = "sys_enter_kexec_load", category = "syscalls")]
+pub fn kexec_syscalls_sys_enter_kexec_load(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_kexec_load(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_kexec_load(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::KexecLoad, ());
+
+    let args = SysEnterArgs::<KexecLoadArgs>::from_context(ctx)?.args;
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<KexecLoadEvent>()?;
+
+    event.init_from_current_task(Type::KexecLoad)?;
+
+    event.data.file_load = false;
+    event.data.nr_segments = args.nr_segments;
+    event.data.flags = args.flags;
+
+    KEXEC_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[repr(C)]
+struct KexecFileLoadArgs {
+    kernel_fd: u64,
+    _initrd_fd: u64,
+    cmdline_len: u64,
+    cmdline_ptr: u64,
+    flags: u64,
+}
+
+#[tracepoint(name = "sys_enter_kexec_file_load", category = "syscalls")]
+pub fn kexec_syscalls_sys_enter_kexec_file_load(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_kexec_file_load(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_kexec_file_load(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::KexecLoad, ());
+
+    let args = SysEnterArgs::<KexecFileLoadArgs>::from_context(ctx)?.args;
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<KexecLoadEvent>()?;
+
+    event.init_from_current_task(Type::KexecLoad)?;
+
+    event.data.file_load = true;
+    event.data.flags = args.flags;
+
+    // when unloading, file descriptors are ignored by the kernel
+    if args.flags & KEXEC_FILE_UNLOAD != KEXEC_FILE_UNLOAD {
+        if let Some(file) = task_struct::current().get_fd(args.kernel_fd as usize) {
+            ignore_result!(inspect_err!(
+                event.data.kernel.core_resolve_file(&file, MAX_PATH_DEPTH),
+                |e: &path::Error| warn!(ctx, "failed to resolve kernel", (*e).into())
+            ));
+        }
+
+        if args.cmdline_ptr != 0 && args.cmdline_len != 0 {
+            ignore_result!(inspect_err!(
+                event
+                    .data
+                    .cmdline
+                    .read_user_str_bytes(args.cmdline_ptr as *const u8),
+                |_| warn!(ctx, "failed to read kexec cmdline")
+            ));
+        }
+    }
+
+    KEXEC_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[tracepoint(name = "sys_exit_kexec_load", category = "syscalls")]
+pub fn kexec_syscalls_sys_exit_kexec_load(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_kexec(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[tracepoint(name = "sys_exit_kexec_file_load", category = "syscalls")]
+pub fn kexec_syscalls_sys_exit_kexec_file_load(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_kexec(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_exit_kexec(ctx: &TracePointContext) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+    let args = SysExitArgs::from_context(ctx)?;
+
+    if let Some(event) = KEXEC_TRACKING.get_ptr_mut(&key) {
+        let event = &mut (*event);
+        event.data.rc = args.ret;
+        pipe_event(ctx, event);
+    }
+
+    // we remove item from map
+    ignore_result!(KEXEC_TRACKING.remove(&key));
+
+    Ok(())
+}
+
+#[repr(C)]
+struct RebootArgs {
+    magic1: u64,
+    magic2: u64,
+    cmd: u64,
+    _arg: u64,
+}
+
+// a successful reboot never returns so the event
+// is sent at syscall entry
+#[tracepoint(name = "sys_enter_reboot", category = "syscalls")]
+pub fn syscalls_sys_enter_reboot(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_reboot(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_reboot(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::Reboot, ());
+
+    let args = SysEnterArgs::<RebootArgs>::from_context(ctx)?.args;
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<RebootEvent>()?;
+
+    event.init_from_current_task(Type::Reboot)?;
+
+    event.data.magic1 = args.magic1 as u32;
+    event.data.magic2 = args.magic2 as u32;
+    event.data.cmd = args.cmd as u32;
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use aya_ebpf::helpers::bpf_probe_read_kernel;
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::{ProbeContext, RetProbeContext};
+
+use super::*;
+
+// A task changes its own LSM label (setcon, aa_change_profile ...) by
+// writing to /proc/self/attr/current which ends up in security_setprocattr.
+// Those events are only used in userland to know the label it keeps
+// for the process is outdated and are never displayed.
+
+// tasks writing to their current attribute
+#[map]
+static mut SETPROCATTR_TRACKING: LruHashMap<u64, bool> = LruHashMap::with_max_entries(256, 0);
+
+#[kprobe(function = "security_setprocattr")]
+pub fn enter_security_setprocattr(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_security_setprocattr(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_enter_security_setprocattr(ctx: &ProbeContext) -> ProbeResult<()> {
+    // first argument changed from an LSM name to an LSM id
+    // across kernel versions but name is always the second one
+    let name: *const [u8; 8] = kprobe_arg!(ctx, 1)?;
+
+    // other attributes (exec, fscreate ...) do not change current label
+    if !bpf_probe_read_kernel(name).is_ok_and(|n| &n == b"current\0") {
+        return Ok(());
+    }
+
+    SETPROCATTR_TRACKING
+        .insert(&bpf_task_tracking_id(), &true, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[kretprobe(function = "security_setprocattr")]
+pub fn exit_security_setprocattr(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    let key = bpf_task_tracking_id();
+
+    let rc = match unsafe { try_exit_security_setprocattr(&ctx, &key) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    };
+
+    ignore_result!(unsafe { SETPROCATTR_TRACKING.remove(&key) });
+    rc
+}
+
+unsafe fn try_exit_security_setprocattr(ctx: &RetProbeContext, key: &u64) -> ProbeResult<()> {
+    if SETPROCATTR_TRACKING.get(key).is_none() {
+        return Ok(());
+    }
+
+    // number of bytes written is returned on success
+    let rc: i32 = ctx.ret().unwrap_or(-1);
+    if rc < 0 {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<LsmLabelChangeEvent>()?;
+
+    event.init_from_current_task(Type::LsmLabelChange)?;
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
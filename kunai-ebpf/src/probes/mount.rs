@@ -0,0 +1,264 @@
+use super::*;
+
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::{ProbeContext, TracePointContext};
+use co_re::task_struct;
+use kunai_common::syscalls::SysExitArgs;
+
+#[map]
+static mut MOUNT_TRACKING: LruHashMap<u64, MountEvent> = LruHashMap::with_max_entries(1024, 0);
+
+#[inline(always)]
+unsafe fn current_mnt_ns() -> ProbeResult<u32> {
+    Ok(core_read_kernel!(
+        task_struct::current(),
+        nsproxy,
+        mnt_ns,
+        ns,
+        inum
+    )?)
+}
+
+// security_sb_mount is called by the mount syscall with dev_name
+// and type already copied into kernel memory
+#[kprobe(function = "security_sb_mount")]
+pub fn fs_security_sb_mount(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_sb_mount(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_sb_mount(ctx: &ProbeContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::Mount, ());
+
+    let dev_name: *const u8 = kprobe_arg!(ctx, 0)?;
+    let path = co_re::path::from_ptr(kprobe_arg!(ctx, 1)?);
+    let ty: *const u8 = kprobe_arg!(ctx, 2)?;
+    let flags: u64 = kprobe_arg!(ctx, 3)?;
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<MountEvent>()?;
+
+    event.init_from_current_task(Type::Mount)?;
+
+    // dev_name and type may be NULL (bind mounts, remounts ...)
+    if !dev_name.is_null() {
+        ignore_result!(inspect_err!(
+            event.data.dev_name.read_kernel_str_bytes(dev_name),
+            |_| warn!(ctx, "failed to read dev_name")
+        ));
+    }
+
+    if !ty.is_null() {
+        ignore_result!(inspect_err!(
+            event.data.ty.read_kernel_str_bytes(ty),
+            |_| warn!(ctx, "failed to read mount type")
+        ));
+    }
+
+    ignore_result!(inspect_err!(
+        event.data.path.core_resolve(&path, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve mountpoint", (*e).into())
+    ));
+
+    event.data.flags = flags;
+    event.data.mnt_ns = current_mnt_ns()?;
+
+    MOUNT_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[tracepoint(name = "sys_exit_mount", category = "syscalls")]
+pub fn fs_syscalls_sys_exit_mount(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_mount(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+// mount(2) MS_MOVE flag, reported for move_mount(2) as
+// both operations attach an existing mount to a new location
+const MS_MOVE: u64 = 8192;
+
+// security_move_mount is called by the move_mount syscall (since 5.2)
+// used by the new mount API (fsopen/fsmount/move_mount). Mounts made
+// that way (recent util-linux and systemd) never go through security_sb_mount
+#[kprobe(function = "security_move_mount")]
+pub fn fs_security_move_mount(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_move_mount(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_move_mount(ctx: &ProbeContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::Mount, ());
+
+    let from_path = co_re::path::from_ptr(kprobe_arg!(ctx, 0)?);
+    let to_path = co_re::path::from_ptr(kprobe_arg!(ctx, 1)?);
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<MountEvent>()?;
+
+    event.init_from_current_task(Type::Mount)?;
+
+    // the mount being moved is the one created by fsmount
+    if let Some(mnt) = from_path.mnt() {
+        if let Some(dev_name) = mnt.mount().mnt_devname() {
+            ignore_result!(inspect_err!(
+                event.data.dev_name.read_kernel_str_bytes(dev_name),
+                |_| warn!(ctx, "failed to read dev_name")
+            ));
+        }
+
+        if let Ok(ty) = core_read_kernel!(mnt, mnt_sb, s_type, name) {
+            ignore_result!(inspect_err!(
+                event.data.ty.read_kernel_str_bytes(ty),
+                |_| warn!(ctx, "failed to read mount type")
+            ));
+        }
+    }
+
+    ignore_result!(inspect_err!(
+        event.data.path.core_resolve(&to_path, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve mountpoint", (*e).into())
+    ));
+
+    event.data.flags = MS_MOVE;
+    event.data.mnt_ns = current_mnt_ns()?;
+
+    MOUNT_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[tracepoint(name = "sys_exit_move_mount", category = "syscalls")]
+pub fn fs_syscalls_sys_exit_move_mount(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_mount(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+// security_sb_umount is called by the umount2 syscall once
+// the mount to detach has been looked up
+#[kprobe(function = "security_sb_umount")]
+pub fn fs_security_sb_umount(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_sb_umount(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_sb_umount(ctx: &ProbeContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::Umount, ());
+
+    let mnt = co_re::vfsmount::from_ptr(kprobe_arg!(ctx, 0)?);
+    let flags: u64 = kprobe_arg!(ctx, 1)?;
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<MountEvent>()?;
+
+    event.init_from_current_task(Type::Umount)?;
+
+    if let Some(dev_name) = mnt.mount().mnt_devname() {
+        ignore_result!(inspect_err!(
+            event.data.dev_name.read_kernel_str_bytes(dev_name),
+            |_| warn!(ctx, "failed to read dev_name")
+        ));
+    }
+
+    if let Ok(ty) = core_read_kernel!(mnt, mnt_sb, s_type, name) {
+        ignore_result!(inspect_err!(
+            event.data.ty.read_kernel_str_bytes(ty),
+            |_| warn!(ctx, "failed to read mount type")
+        ));
+    }
+
+    ignore_result!(inspect_err!(
+        event.data.path.core_resolve_vfsmount(&mnt, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve mountpoint", (*e).into())
+    ));
+
+    event.data.flags = flags;
+    event.data.mnt_ns = current_mnt_ns()?;
+
+    MOUNT_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+// umount2 syscall tracepoint is named after the kernel syscall definition
+#[tracepoint(name = "sys_exit_umount", category = "syscalls")]
+pub fn fs_syscalls_sys_exit_umount(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_mount(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_exit_mount(ctx: &TracePointContext) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+    let args = SysExitArgs::from_context(ctx)?;
+
+    if let Some(event) = MOUNT_TRACKING.get_ptr_mut(&key) {
+        let event = &mut (*event);
+        event.data.rc = args.ret as i32;
+        pipe_event(ctx, event);
+    }
+
+    // we remove item from map
+    ignore_result!(MOUNT_TRACKING.remove(&key));
+
+    Ok(())
+}
@@ -0,0 +1,392 @@
+use super::*;
+
+use aya_ebpf::helpers::bpf_probe_read_kernel;
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::{ProbeContext, RetProbeContext, TracePointContext};
+use aya_ebpf::EbpfContext;
+use co_re::{file, task_struct};
+use kunai_common::syscalls::SysExitArgs;
+
+/*
+Both process_vm_readv/writev syscalls and opening /proc/<pid>/mem
+check access to the target process memory with mm_access. The latter
+ends up calling security_ptrace_access_check, which is where we grab
+the target task (see ptrace probe). Accesses within the same thread
+group do not reach that hook so they are naturally filtered out.
+
+process_vm_readv/writev are reported at syscall exit. For /proc/<pid>/mem
+the target is known when the file is opened (security_file_open followed
+by the access check done by mem_open) and it is kept along with the file.
+Reads and writes are then reported at mem_read/mem_write return, those
+are the file operations of /proc/<pid>/mem so they are called only for
+such files. Opens denied by the access check are reported as well.
+*/
+
+const PROC_SUPER_MAGIC: u64 = 0x9fa0;
+const FMODE_WRITE: u32 = 0x2;
+
+const PTRACE_MODE_ATTACH: u32 = 0x02;
+const PTRACE_MODE_NOAUDIT: u32 = 0x04;
+const PTRACE_MODE_FSCREDS: u32 = 0x08;
+
+// memory accesses in progress
+#[map]
+static mut MEM_ACCESS_TRACKING: LruHashMap<u64, ProcessMemoryAccessEvent> =
+    LruHashMap::with_max_entries(1024, 0);
+
+// /proc/<pid>/mem files being opened by a task
+#[map]
+static mut PROC_MEM_OPENING: LruHashMap<u64, u64> = LruHashMap::with_max_entries(1024, 0);
+
+// targets of the opened /proc/<pid>/mem files
+#[map]
+static mut PROC_MEM_FILES: LruHashMap<u64, TaskInfo> = LruHashMap::with_max_entries(4096, 0);
+
+/// Sets the target of any memory access being tracked
+/// for the current task. This function is meant to be
+/// called from security_ptrace_access_check probe.
+#[inline(always)]
+pub(crate) unsafe fn set_memory_access_target(target: task_struct, mode: u32) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+
+    if let Some(event) = MEM_ACCESS_TRACKING.get_ptr_mut(&key) {
+        let event = &mut (*event);
+        let expected = match event.data.method {
+            // mem_open checks access with PTRACE_MODE_ATTACH_FSCREDS
+            MemoryAccessMethod::ProcPidMem => {
+                mode & !PTRACE_MODE_NOAUDIT == PTRACE_MODE_ATTACH | PTRACE_MODE_FSCREDS
+            }
+            _ => mode & PTRACE_MODE_ATTACH == PTRACE_MODE_ATTACH,
+        };
+
+        if expected {
+            event.data.target.from_task(target)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[inline(always)]
+unsafe fn track_process_vm(write: bool) -> ProbeResult<()> {
+    if_disabled_return!(Type::ProcessMemoryAccess, ());
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<ProcessMemoryAccessEvent>()?;
+
+    event.init_from_current_task(Type::ProcessMemoryAccess)?;
+
+    event.data.write = write;
+    event.data.method = if write {
+        MemoryAccessMethod::ProcessVmWritev
+    } else {
+        MemoryAccessMethod::ProcessVmReadv
+    };
+
+    MEM_ACCESS_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[inline(always)]
+unsafe fn pipe_memory_access<C: EbpfContext>(ctx: &C, rc: i64) {
+    let key = bpf_task_tracking_id();
+
+    if let Some(event) = MEM_ACCESS_TRACKING.get_ptr_mut(&key) {
+        let event = &mut (*event);
+        // we don't report accesses denied before the target was known
+        if event.data.has_target() {
+            event.data.rc = rc;
+            pipe_event(ctx, event);
+        }
+    }
+
+    // we remove item from map
+    ignore_result!(MEM_ACCESS_TRACKING.remove(&key));
+}
+
+#[tracepoint(name = "sys_enter_process_vm_readv", category = "syscalls")]
+pub fn syscalls_sys_enter_process_vm_readv(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { track_process_vm(false) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[tracepoint(name = "sys_enter_process_vm_writev", category = "syscalls")]
+pub fn syscalls_sys_enter_process_vm_writev(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { track_process_vm(true) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[tracepoint(name = "sys_exit_process_vm_readv", category = "syscalls")]
+pub fn syscalls_sys_exit_process_vm_readv(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_process_vm(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[tracepoint(name = "sys_exit_process_vm_writev", category = "syscalls")]
+pub fn syscalls_sys_exit_process_vm_writev(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_process_vm(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_exit_process_vm(ctx: &TracePointContext) -> ProbeResult<()> {
+    let args = SysExitArgs::from_context(ctx)?;
+    pipe_memory_access(ctx, args.ret);
+    Ok(())
+}
+
+#[kprobe(function = "security_file_open")]
+pub fn process_memory_security_file_open(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_file_open(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn is_mem_file(file: &file) -> ProbeResult<bool> {
+    let name = core_read_kernel!(file, f_path, dentry, d_name)?;
+
+    if core_read_kernel!(name, len)? != 3 {
+        return Ok(false);
+    }
+
+    let name = core_read_kernel!(name, name)? as *const [u8; 3];
+    Ok(bpf_probe_read_kernel(name)
+        .map(|n| &n == b"mem")
+        .unwrap_or_default())
+}
+
+unsafe fn try_security_file_open(ctx: &ProbeContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::ProcessMemoryAccess, ());
+
+    let file = file::from_ptr(kprobe_arg!(ctx, 0)?);
+
+    // not a procfs file
+    if core_read_kernel!(file, f_inode, i_sb, s_magic)? != PROC_SUPER_MAGIC {
+        return Ok(());
+    }
+
+    let key = bpf_task_tracking_id();
+
+    if !is_mem_file(&file)? {
+        // any other procfs file being opened invalidates a previous
+        // /proc/<pid>/mem open which did not reach the access check
+        // (i.e. process opened its own memory file)
+        if PROC_MEM_OPENING.get(&key).is_some() {
+            ignore_result!(PROC_MEM_OPENING.remove(&key));
+            ignore_result!(MEM_ACCESS_TRACKING.remove(&key));
+        }
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<ProcessMemoryAccessEvent>()?;
+
+    event.init_from_current_task(Type::ProcessMemoryAccess)?;
+
+    event.data.method = MemoryAccessMethod::ProcPidMem;
+    event.data.write = core_read_kernel!(file, f_mode)? & FMODE_WRITE == FMODE_WRITE;
+
+    MEM_ACCESS_TRACKING
+        .insert(&key, event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    PROC_MEM_OPENING
+        .insert(&key, &(file.as_ptr() as u64), 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+// access check done when opening /proc/<pid>/mem, a denied open is
+// reported right away otherwise the target is attached to the file
+#[kretprobe(function = "security_ptrace_access_check")]
+pub fn process_memory_exit_ptrace_access_check(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_exit_ptrace_access_check(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn try_exit_ptrace_access_check(ctx: &RetProbeContext) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+
+    let Some(file) = PROC_MEM_OPENING.get(&key).copied() else {
+        return Ok(());
+    };
+
+    let Some(event) = MEM_ACCESS_TRACKING.get(&key) else {
+        return Ok(());
+    };
+
+    if event.data.method != MemoryAccessMethod::ProcPidMem || !event.data.has_target() {
+        return Ok(());
+    }
+
+    let rc: i32 = ctx.ret().unwrap_or(-1);
+
+    ignore_result!(PROC_MEM_OPENING.remove(&key));
+
+    if rc < 0 {
+        pipe_memory_access(ctx, rc as i64);
+        return Ok(());
+    }
+
+    let res = PROC_MEM_FILES.insert(&file, &event.data.target, 0);
+
+    ignore_result!(MEM_ACCESS_TRACKING.remove(&key));
+
+    res.map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[kprobe(function = "mem_read")]
+pub fn process_memory_enter_mem_read(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_mem_rw(&ctx, false) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[kprobe(function = "mem_write")]
+pub fn process_memory_enter_mem_write(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_mem_rw(&ctx, true) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn try_enter_mem_rw(ctx: &ProbeContext, write: bool) -> ProbeResult<()> {
+    if_disabled_return!(Type::ProcessMemoryAccess, ());
+
+    let file = file::from_ptr(kprobe_arg!(ctx, 0)?);
+
+    // file of the current process or opened before we started
+    let Some(target) = PROC_MEM_FILES.get(&(file.as_ptr() as u64)) else {
+        return Ok(());
+    };
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<ProcessMemoryAccessEvent>()?;
+
+    event.init_from_current_task(Type::ProcessMemoryAccess)?;
+
+    event.data.method = MemoryAccessMethod::ProcPidMem;
+    event.data.write = write;
+    event.data.target = *target;
+
+    MEM_ACCESS_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+// return value is the number of bytes read or written
+#[kretprobe(function = "mem_read")]
+pub fn process_memory_exit_mem_read(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    let rc: i64 = ctx.ret().unwrap_or(-1);
+    unsafe { pipe_memory_access(&ctx, rc) };
+
+    errors::BPF_PROG_SUCCESS
+}
+
+#[kretprobe(function = "mem_write")]
+pub fn process_memory_exit_mem_write(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    let rc: i64 = ctx.ret().unwrap_or(-1);
+    unsafe { pipe_memory_access(&ctx, rc) };
+
+    errors::BPF_PROG_SUCCESS
+}
+
+// file is released, its address may be reused by another file
+#[kprobe(function = "mem_release")]
+pub fn process_memory_mem_release(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    if let Some(file) = ctx.arg::<u64>(1) {
+        ignore_result!(unsafe { PROC_MEM_FILES.remove(&file) });
+    }
+
+    errors::BPF_PROG_SUCCESS
+}
@@ -1,5 +1,6 @@
 use aya_ebpf::{cty::c_uint, programs::ProbeContext};
 
+use super::process_memory::set_memory_access_target;
 use super::*;
 
 #[kprobe(function = "security_ptrace_access_check")]
@@ -22,6 +23,12 @@ unsafe fn try_ptrace_access_check(ctx: &ProbeContext) -> Result<(), ProbeError>
     let target = co_re::task_struct::from_ptr(kprobe_arg!(ctx, 0)?);
     let mode: c_uint = kprobe_arg!(ctx, 1)?;
 
+    // target of process_vm_readv/writev or /proc/<pid>/mem
+    ignore_result!(inspect_err!(set_memory_access_target(target, mode), |_| warn!(
+        ctx,
+        "failed to set memory access target"
+    )));
+
     // only catch PTRACE_MODE_ATTACH
     if mode & 0x2 != 0x2 {
         return Ok(());
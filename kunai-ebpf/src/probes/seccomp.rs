@@ -0,0 +1,179 @@
+use super::*;
+
+use aya_ebpf::helpers::bpf_probe_read_user;
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::TracePointContext;
+use co_re::sock_fprog_kern;
+use kunai_common::syscalls::{SysEnterArgs, SysExitArgs};
+
+// seccomp syscall operations
+const SECCOMP_SET_MODE_STRICT: u64 = 0;
+const SECCOMP_SET_MODE_FILTER: u64 = 1;
+
+// prctl option to set seccomp mode
+const PR_SET_SECCOMP: u64 = 22;
+
+#[map]
+static mut SECCOMP_TRACKING: LruHashMap<u64, SeccompEvent> = LruHashMap::with_max_entries(256, 0);
+
+#[repr(C)]
+struct SeccompArgs {
+    op: u64,
+    flags: u64,
+    uargs: u64,
+}
+
+#[repr(C)]
+struct PrctlArgs {
+    option: u64,
+    arg2: u64,
+    arg3: u64,
+}
+
+// userland struct sock_fprog
+#[repr(C)]
+struct SockFprog {
+    len: u16,
+    filter: u64,
+}
+
+#[tracepoint(name = "sys_enter_seccomp", category = "syscalls")]
+pub fn seccomp_syscalls_sys_enter_seccomp(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_seccomp(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_seccomp(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::Seccomp, ());
+
+    let args = SysEnterArgs::<SeccompArgs>::from_context(ctx)?.args;
+
+    let mode = match args.op {
+        SECCOMP_SET_MODE_STRICT => SECCOMP_MODE_STRICT,
+        SECCOMP_SET_MODE_FILTER => SECCOMP_MODE_FILTER,
+        // other operations do not change seccomp state
+        _ => return Ok(()),
+    };
+
+    track_seccomp(mode, args.flags as u32, args.uargs)
+}
+
+#[tracepoint(name = "sys_enter_prctl", category = "syscalls")]
+pub fn seccomp_syscalls_sys_enter_prctl(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_enter_prctl(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_enter_prctl(ctx: &TracePointContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::Seccomp, ());
+
+    let args = SysEnterArgs::<PrctlArgs>::from_context(ctx)?.args;
+
+    if args.option != PR_SET_SECCOMP {
+        return Ok(());
+    }
+
+    let mode = args.arg2 as u32;
+    if mode != SECCOMP_MODE_STRICT && mode != SECCOMP_MODE_FILTER {
+        return Ok(());
+    }
+
+    track_seccomp(mode, 0, args.arg3)
+}
+
+#[inline(always)]
+unsafe fn track_seccomp(mode: u32, flags: u32, uargs: u64) -> ProbeResult<()> {
+    alloc::init()?;
+    let event = alloc::alloc_zero::<SeccompEvent>()?;
+
+    event.init_from_current_task(Type::Seccomp)?;
+
+    event.data.mode = mode;
+    event.data.flags = flags;
+
+    if event.data.is_filter() {
+        // filter may be invalid, the syscall will fail and
+        // the event will be sent without filter
+        if let Ok(fprog) = bpf_probe_read_user(uargs as *const SockFprog) {
+            let size = sock_fprog_kern::byte_size_from_len(fprog.len);
+            if event
+                .data
+                .filter
+                .read_user_at(fprog.filter as *const u8, size as u32)
+                .is_ok()
+            {
+                event.data.filter_len = fprog.len;
+                event.data.filter_truncated = size > MAX_SECCOMP_FILTER_SIZE;
+            }
+        }
+    }
+
+    SECCOMP_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[tracepoint(name = "sys_exit_seccomp", category = "syscalls")]
+pub fn seccomp_syscalls_sys_exit_seccomp(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_seccomp(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[tracepoint(name = "sys_exit_prctl", category = "syscalls")]
+pub fn seccomp_syscalls_sys_exit_prctl(ctx: TracePointContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_sys_exit_seccomp(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_sys_exit_seccomp(ctx: &TracePointContext) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+
+    // only tracked calls have an event to send
+    if let Some(event) = SECCOMP_TRACKING.get_ptr_mut(&key) {
+        let event = &mut (*event);
+        event.data.rc = SysExitArgs::from_context(ctx)?.ret;
+        pipe_event(ctx, event);
+        // we remove item from map
+        ignore_result!(SECCOMP_TRACKING.remove(&key));
+    }
+
+    Ok(())
+}
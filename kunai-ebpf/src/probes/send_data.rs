@@ -2,7 +2,7 @@ use super::*;
 use aya_ebpf::programs::ProbeContext;
 use kunai_common::{
     buffer::Buffer,
-    net::{SaFamily, SockAddr, SocketInfo},
+    net::{SaFamily, SockAddr, SockType, SocketInfo},
 };
 
 /*
@@ -14,6 +14,10 @@ idea came of a more generic event giving more high level information,
 such as the shannon entropy, of the data sent over the network.
  */
 
+// enough to check TLS record and handshake headers (6 bytes)
+// and the longest HTTP method prefix ("OPTIONS ")
+const PEEK_SIZE: usize = 8;
+
 #[kprobe(function = "security_socket_sendmsg")]
 pub fn net_security_socket_sendmsg(ctx: ProbeContext) -> u32 {
     if is_current_loader_task() {
@@ -30,12 +34,18 @@ pub fn net_security_socket_sendmsg(ctx: ProbeContext) -> u32 {
 }
 
 unsafe fn try_sock_send_data(ctx: &ProbeContext) -> ProbeResult<()> {
-    // returns early if event is disabled
-    if_disabled_return!(Type::SendData, ());
-
     // we get bpf configuration
     let c = get_cfg!()?;
 
+    let send_data = c.is_event_enabled(Type::SendData);
+    let tls_client_hello = c.is_event_enabled(Type::TlsClientHello);
+    let http_request = c.is_event_enabled(Type::HttpRequest);
+
+    // returns early if events are disabled
+    if !send_data && !tls_client_hello && !http_request {
+        return Ok(());
+    }
+
     let socket = co_re::socket::from_ptr(kprobe_arg!(ctx, 0)?);
 
     let pmsg = co_re::msghdr::from_ptr(kprobe_arg!(ctx, 1)?);
@@ -53,15 +63,38 @@ unsafe fn try_sock_send_data(ctx: &ProbeContext) -> ProbeResult<()> {
 
     alloc::init()?;
 
-    let iov_buf = alloc::alloc_zero::<Buffer<ENCRYPT_DATA_MAX_BUFFER_SIZE>>()?;
-
     let msg_size = core_read_kernel!(iov_iter, count)?;
 
-    // if iov_iter contains enough bytes to trigger event
-    if msg_size < c.send_data_min_len {
+    // ClientHello and HTTP requests are parsed from the beginning of
+    // the data so they do not need to be bigger than send_data_min_len
+    let send_data = send_data && msg_size >= c.send_data_min_len;
+    let stream = si.is_type(SockType::SOCK_STREAM);
+    let mut tls_client_hello = tls_client_hello && stream;
+    let mut http_request = http_request && stream;
+
+    if !send_data && !tls_client_hello && !http_request {
         return Ok(());
     }
 
+    // we peek at the beginning of the data so that we copy the
+    // whole message only if it is a ClientHello or an HTTP request
+    if tls_client_hello || http_request {
+        let head = alloc::alloc_zero::<Buffer<PEEK_SIZE>>()?;
+        if let Err(e) = head.fill_from_iov_iter::<2>(iov_iter, Some(PEEK_SIZE)) {
+            match e {
+                kunai_common::buffer::Error::BufferFull => {}
+                e => return Err(e.into()),
+            }
+        }
+
+        tls_client_hello = tls_client_hello && is_tls_client_hello(head);
+        http_request = http_request && is_http_request(head);
+
+        if !send_data && !tls_client_hello && !http_request {
+            return Ok(());
+        }
+    }
+
     let dst_ip_port = {
         // handle this particular case: https://elixir.bootlin.com/linux/v6.9.5/source/net/socket.c#L2180
         if pmsg.has_msg_name() {
@@ -74,6 +107,8 @@ unsafe fn try_sock_send_data(ctx: &ProbeContext) -> ProbeResult<()> {
 
     let src_ip_port = SockAddr::src_from_sock_common(sk_common)?;
 
+    let iov_buf = alloc::alloc_zero::<Buffer<ENCRYPT_DATA_MAX_BUFFER_SIZE>>()?;
+
     let iov_iter = core_read_kernel!(pmsg, msg_iter)?;
     if let Err(e) = iov_buf.fill_from_iov_iter::<128>(iov_iter, None) {
         match e {
@@ -83,10 +118,47 @@ unsafe fn try_sock_send_data(ctx: &ProbeContext) -> ProbeResult<()> {
         }
     }
 
+    // IORING_OP_SEND and IORING_OP_SENDMSG also go through this hook
+    let io_uring = is_io_uring_context();
+
+    if tls_client_hello && is_tls_client_hello(iov_buf) {
+        let event = alloc::alloc_zero::<TlsClientHelloEvent>()?;
+
+        event.init_from_current_task(Type::TlsClientHello)?;
+
+        event.data.socket = si;
+        event.data.src = src_ip_port;
+        event.data.dst = dst_ip_port;
+        event.data.data.copy(iov_buf);
+        event.data.io_uring = io_uring;
+
+        pipe_event(ctx, event);
+    }
+
+    if http_request && is_http_request(iov_buf) {
+        let event = alloc::alloc_zero::<HttpRequestEvent>()?;
+
+        event.init_from_current_task(Type::HttpRequest)?;
+
+        event.data.socket = si;
+        event.data.src = src_ip_port;
+        event.data.dst = dst_ip_port;
+        event.data.data.copy(iov_buf);
+        event.data.io_uring = io_uring;
+
+        pipe_event(ctx, event);
+    }
+
+    if !send_data {
+        return Ok(());
+    }
+
     let event = alloc::alloc_zero::<SendEntropyEvent>()?;
 
     event.init_from_current_task(Type::SendData)?;
 
+    event.data.io_uring = io_uring;
+
     // setting events' data
     event.data.socket = si;
     event.data.src = src_ip_port;
@@ -0,0 +1,74 @@
+use super::*;
+use aya_ebpf::helpers::bpf_get_current_comm;
+use aya_ebpf::programs::RetProbeContext;
+
+/*
+Uretprobe attached (from userland) to the functions interactive shells
+use to read command lines (readline in bash and zleread in zsh). It
+allows catching commands which never go through execve (i.e. builtins).
+
+When bash is dynamically linked against libreadline, readline is hooked
+in the library instead. As the library is shared with other programs
+(python, gdb ...) only calls made by bash are reported.
+ */
+
+#[uretprobe]
+pub fn shell_exit_readline(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_exit_readline(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[uretprobe]
+pub fn shell_exit_lib_readline(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    // library is shared with other programs
+    if !bpf_get_current_comm().is_ok_and(|c| c.starts_with(b"bash\0")) {
+        return 0;
+    }
+
+    match unsafe { try_exit_readline(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_exit_readline(ctx: &RetProbeContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::ShellCommand, ());
+
+    // NULL is returned on EOF
+    let line = ctx.ret::<u64>().unwrap_or_default();
+    if line == 0 {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<ShellCommandEvent>()?;
+
+    event.data.line.read_user_str_bytes(line as *const u8)?;
+
+    // we do not report empty lines
+    if event.data.line.is_empty() {
+        return Ok(());
+    }
+
+    event.init_from_current_task(Type::ShellCommand)?;
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use super::*;
+use aya_ebpf::programs::ProbeContext;
+use kunai_common::net::{SaFamily, SockType, SocketInfo};
+
+// security_socket_post_create is called once the socket
+// has successfully been created so we do not need to track
+// syscall return value
+#[kprobe(function = "security_socket_post_create")]
+pub fn net_security_socket_post_create(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_security_socket_post_create(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_security_socket_post_create(ctx: &ProbeContext) -> ProbeResult<()> {
+    // returns early if event is disabled
+    if_disabled_return!(Type::SocketCreate, ());
+
+    // we get bpf configuration
+    let c = get_cfg!()?;
+
+    let family: i32 = kprobe_arg!(ctx, 1)?;
+    let ty: i32 = kprobe_arg!(ctx, 2)?;
+    let protocol: i32 = kprobe_arg!(ctx, 3)?;
+    let kern: i32 = kprobe_arg!(ctx, 4)?;
+
+    // we skip sockets created by the kernel for itself
+    if kern != 0 {
+        return Ok(());
+    }
+
+    let si = SocketInfo {
+        domain: family as u16,
+        ty: ty as u16,
+        proto: protocol as u16,
+        ..Default::default()
+    };
+
+    // by default we only report sockets giving access to raw
+    // traffic or to kernel interfaces
+    if !c.socket_create_all_families
+        && !si.is_family(SaFamily::AF_PACKET)
+        && !si.is_family(SaFamily::AF_NETLINK)
+        && !si.is_type(SockType::SOCK_RAW)
+        && !si.is_type(SockType::SOCK_PACKET)
+    {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<SocketCreateEvent>()?;
+
+    event.init_from_current_task(Type::SocketCreate)?;
+
+    event.data.socket = si;
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use super::*;
+
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::{ProbeContext, RetProbeContext};
+use co_re::proc_inode;
+use kunai_common::kernel;
+use kunai_common::version::kernel_version;
+
+/*
+Sysctl writes are caught in proc_sys_write, the write file operation of
+/proc/sys entries, so it is called only for such writes. Being a file
+operation it always exists as a symbol and is never inlined.
+*/
+
+const PROC_SUPER_MAGIC: u64 = 0x9fa0;
+
+#[map]
+static mut SYSCTL_WRITE_TRACKING: LruHashMap<u64, SysctlWriteEvent> =
+    LruHashMap::with_max_entries(256, 0);
+
+#[kprobe(function = "proc_sys_write")]
+pub fn sysctl_enter_proc_sys_write(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_proc_sys_write(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+unsafe fn try_enter_proc_sys_write(ctx: &ProbeContext) -> ProbeResult<()> {
+    if_disabled_return!(Type::SysctlWrite, ());
+
+    // proc_sys_write takes a kiocb and an iov_iter since 5.10
+    if kernel_version() < kernel!(5, 10) {
+        let file = co_re::file::from_ptr(kprobe_arg!(ctx, 0)?);
+        let buf: *const u8 = kprobe_arg!(ctx, 1)?;
+        let count: u64 = kprobe_arg!(ctx, 2)?;
+        return track_sysctl_write(ctx, file, buf, count);
+    }
+
+    let iocb = co_re::kiocb::from_ptr(kprobe_arg!(ctx, 0)?);
+    let iter = co_re::iov_iter::from_ptr(kprobe_arg!(ctx, 1)?);
+    let file = core_read_kernel!(iocb, ki_filp)?;
+
+    // we only report the first segment of vectored writes
+    let buf = if iter.is_iter_ubuf() {
+        core_read_kernel!(iter, ubuf)?
+    } else if iter.is_iter_iovec() {
+        core_read_kernel!(iter, iov, iov_base)?
+    } else {
+        // kernel buffer (i.e. kernel_write)
+        core::ptr::null_mut()
+    };
+    let count = core_read_kernel!(iter, count)?;
+
+    track_sysctl_write(ctx, file, buf as *const u8, count)
+}
+
+#[inline(always)]
+unsafe fn track_sysctl_write(
+    ctx: &ProbeContext,
+    file: co_re::file,
+    buf: *const u8,
+    count: u64,
+) -> ProbeResult<()> {
+    let inode = core_read_kernel!(file, f_inode)?;
+
+    // not a procfs file
+    if core_read_kernel!(inode, i_sb, s_magic)? != PROC_SUPER_MAGIC {
+        return Ok(());
+    }
+
+    // only files under /proc/sys have a ctl_table entry
+    let table = core_read_kernel!(proc_inode::from_inode(&inode), sysctl_entry)?;
+    if table.is_null() {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<SysctlWriteEvent>()?;
+
+    event.init_from_current_task(Type::SysctlWrite)?;
+
+    ignore_result!(inspect_err!(
+        event.data.path.core_resolve_file(&file, MAX_PATH_DEPTH),
+        |e: &path::Error| warn!(ctx, "failed to resolve filename", (*e).into())
+    ));
+
+    // buffer may come from kernel_write, in such case we fail reading
+    ignore_result!(event
+        .data
+        .new_value
+        .read_user_at(buf, count.min(SYSCTL_VALUE_MAX as u64) as u32));
+
+    // some sysctls have a custom handler not backed by data
+    let data = core_read_kernel!(table, data)?;
+    if !data.is_null() {
+        let maxlen = core_read_kernel!(table, maxlen)?;
+        ignore_result!(event
+            .data
+            .old_value
+            .read_kernel_at(data, maxlen.clamp(0, SYSCTL_VALUE_MAX as i32) as u32));
+    }
+
+    event.data.handler = core_read_kernel!(table, proc_handler)? as u64;
+
+    SYSCTL_WRITE_TRACKING
+        .insert(&bpf_task_tracking_id(), event, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[kretprobe(function = "proc_sys_write")]
+pub fn sysctl_exit_proc_sys_write(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    let key = bpf_task_tracking_id();
+
+    if let Some(event) = unsafe { SYSCTL_WRITE_TRACKING.get_ptr_mut(&key) } {
+        let event = unsafe { &mut (*event) };
+        event.data.rc = ctx.ret::<i64>().unwrap_or(-1);
+        pipe_event(&ctx, event);
+    }
+
+    // we remove item from map
+    ignore_result!(unsafe { SYSCTL_WRITE_TRACKING.remove(&key) });
+
+    errors::BPF_PROG_SUCCESS
+}
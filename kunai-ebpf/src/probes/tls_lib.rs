@@ -0,0 +1,281 @@
+use super::*;
+use aya_ebpf::helpers::bpf_probe_read_user;
+use aya_ebpf::maps::LruHashMap;
+use aya_ebpf::programs::{ProbeContext, RetProbeContext};
+use kunai_common::net::{SaFamily, SockAddr, SocketInfo};
+
+/*
+Uprobes attached (from userland) to the read and write functions
+of TLS libraries (OpenSSL and GnuTLS) found in process memory maps.
+Both libraries share the same prototype for those functions
+(i.e. fn(ctx, buf, len)) so the same programs are used for both.
+
+As the TLS context does not tell us which socket is used, socket
+information is taken from the socket hooks called in between library
+function entry and exit. Only the beginning of plaintext data is
+taken and it is reported only if it looks like an HTTP request.
+
+OpenSSL SSL_read_ex/SSL_write_ex (i.e. fn(ctx, buf, len, *processed))
+return 1 on success and store the number of bytes processed at the
+address given as last argument, this is what we use as length.
+ */
+
+#[repr(C)]
+struct TlsLibCall {
+    buf: u64,
+    // pointer to the size_t holding processed bytes (SSL_*_ex only)
+    processed: u64,
+    inbound: bool,
+    has_socket: bool,
+    socket: SocketInfo,
+    local: SockAddr,
+    peer: SockAddr,
+}
+
+#[map]
+static mut TLS_LIB_TRACKING: LruHashMap<u64, TlsLibCall> = LruHashMap::with_max_entries(4096, 0);
+
+#[uprobe]
+pub fn tls_lib_enter_write(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_tls_lib_call(&ctx, false, false) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[uprobe]
+pub fn tls_lib_enter_read(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_tls_lib_call(&ctx, true, false) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[uprobe]
+pub fn tls_lib_enter_write_ex(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_tls_lib_call(&ctx, false, true) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[uprobe]
+pub fn tls_lib_enter_read_ex(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_enter_tls_lib_call(&ctx, true, true) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn try_enter_tls_lib_call(ctx: &ProbeContext, inbound: bool, ex: bool) -> ProbeResult<()> {
+    if_disabled_return!(Type::HttpRequest, ());
+
+    let buf: u64 = kprobe_arg!(ctx, 1)?;
+    let processed: u64 = if ex { kprobe_arg!(ctx, 3)? } else { 0 };
+
+    if buf == 0 || (ex && processed == 0) {
+        return Ok(());
+    }
+
+    let call = TlsLibCall {
+        buf,
+        processed,
+        inbound,
+        has_socket: false,
+        socket: SocketInfo::default(),
+        local: SockAddr::default(),
+        peer: SockAddr::default(),
+    };
+
+    TLS_LIB_TRACKING
+        .insert(&bpf_task_tracking_id(), &call, 0)
+        .map_err(|_| MapError::InsertFailure)?;
+
+    Ok(())
+}
+
+#[kprobe(function = "security_socket_sendmsg")]
+pub fn tls_lib_security_socket_sendmsg(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_track_socket(&ctx, false) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[kprobe(function = "security_socket_recvmsg")]
+pub fn tls_lib_security_socket_recvmsg(ctx: ProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_track_socket(&ctx, true) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn try_track_socket(ctx: &ProbeContext, inbound: bool) -> ProbeResult<()> {
+    // we are not inside a TLS library call
+    let Some(call) = TLS_LIB_TRACKING.get_ptr_mut(&bpf_task_tracking_id()) else {
+        return Ok(());
+    };
+    let call = &mut (*call);
+
+    // we keep the first socket used during the call
+    if call.has_socket || call.inbound != inbound {
+        return Ok(());
+    }
+
+    let socket = co_re::socket::from_ptr(kprobe_arg!(ctx, 0)?);
+    let sock = core_read_kernel!(socket, sk)?;
+    let sk_common = core_read_kernel!(sock, sk_common)?;
+    let si = SocketInfo::try_from(sock)?;
+
+    // we process only IPv4 and IPv6
+    if !si.is_family(SaFamily::AF_INET) && !si.is_family(SaFamily::AF_INET6) {
+        return Ok(());
+    }
+
+    call.socket = si;
+    call.local = SockAddr::src_from_sock_common(sk_common)?;
+    call.peer = SockAddr::dst_from_sock_common(sk_common)?;
+    call.has_socket = true;
+
+    Ok(())
+}
+
+#[uretprobe]
+pub fn tls_lib_exit_write(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_exit_tls_lib_call(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[uretprobe]
+pub fn tls_lib_exit_read(ctx: RetProbeContext) -> u32 {
+    if is_current_loader_task() {
+        return 0;
+    }
+
+    match unsafe { try_exit_tls_lib_call(&ctx) } {
+        Ok(_) => errors::BPF_PROG_SUCCESS,
+        Err(s) => {
+            error!(&ctx, s);
+            errors::BPF_PROG_FAILURE
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn try_exit_tls_lib_call(ctx: &RetProbeContext) -> ProbeResult<()> {
+    let key = bpf_task_tracking_id();
+
+    let res = report_tls_lib_call(ctx, &key);
+
+    // we remove item from map
+    ignore_result!(TLS_LIB_TRACKING.remove(&key));
+
+    res
+}
+
+#[inline(always)]
+unsafe fn report_tls_lib_call(ctx: &RetProbeContext, key: &u64) -> ProbeResult<()> {
+    let Some(call) = TLS_LIB_TRACKING.get(key) else {
+        return Ok(());
+    };
+
+    // SSL_read/SSL_write return an int while GnuTLS functions
+    // return a ssize_t so we need to truncate return value
+    let mut ret = ctx.ret::<i64>().unwrap_or_default() as i32;
+
+    // SSL_*_ex functions return 1 on success
+    if call.processed != 0 && ret == 1 {
+        let processed: u64 = bpf_probe_read_user(call.processed as *const u64)
+            .map_err(|_| ProbeError::BpfProbeReadFailure)?;
+        ret = processed.min(i32::MAX as u64) as i32;
+    }
+
+    // nothing was sent or received or we could not find the socket
+    if ret <= 0 || !call.has_socket {
+        return Ok(());
+    }
+
+    alloc::init()?;
+    let event = alloc::alloc_zero::<HttpRequestEvent>()?;
+
+    // we read only the beginning of the plaintext
+    event
+        .data
+        .data
+        .read_user_at(call.buf as *const u8, ret as u32)?;
+
+    if !is_http_request(&event.data.data) {
+        return Ok(());
+    }
+
+    event.init_from_current_task(Type::HttpRequest)?;
+
+    event.data.socket = call.socket;
+    // data flows from src to dst
+    if call.inbound {
+        event.data.src = call.peer;
+        event.data.dst = call.local;
+    } else {
+        event.data.src = call.local;
+        event.data.dst = call.peer;
+    }
+    event.data.tls = true;
+    event.data.inbound = call.inbound;
+
+    pipe_event(ctx, event);
+
+    Ok(())
+}
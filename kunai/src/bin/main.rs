@@ -13,25 +13,35 @@ use fs_walk::WalkOptions;
 use gene::rules::{CompiledRule, MAX_SEVERITY};
 use gene::{Compiler, Engine};
 use huby::ByteSize;
-use kunai::containers::Container;
+use kunai::containers::{cri, docker, resolver, Container};
+use kunai::dns::{self, ResolutionCache};
 use kunai::events::StartData;
 use kunai::events::{
     agent::AgentEventInfo, BpfProgLoadData, BpfProgTypeInfo, BpfSocketFilterData, CloneData,
-    ConnectData, DnsQueryData, ErrorData, EventInfo, ExecveData, ExitData, FileData,
-    FileRenameData, FileScanData, FilterInfo, InitModuleData, KillData, KunaiEvent, LossData,
-    MmapExecData, MprotectData, NetworkInfo, PrctlData, PtraceData, ScanResult, SendDataData,
-    SockAddr, SocketInfo, TargetTask, TaskSection, UnlinkData, UserEvent,
+    ConnectData, DeleteModuleData, DnsQueryData, EncryptedDnsData, ErrorData, EventInfo,
+    ExecveData, ExecveFailedData, ExitData, FdInfo, FileChmodData, FileChownData, FileData,
+    FileLinkData, FileRenameData, FileScanData, FileSymlinkData, FileUtimesData, FileXattrData,
+    FilterInfo, GeoInfo, HttpRequestData, InitModuleData, IoUringSetupData, KexecLoadData,
+    KillData, KunaiEvent, LossData, MmapExecData, MountData, MprotectData, NetworkInfo, PrctlData,
+    ProcessMemoryAccessData, PtraceData, RebootData, ScanResult, SeccompData, SendDataData,
+    ShellCommandData, SockAddr, SocketCreateData, SocketInfo, SysctlWriteData, TargetTask,
+    TaskSection, TlsClientHelloData, UnlinkData, UserEvent, UtcDateTime,
 };
+use kunai::geoip::GeoIp;
 use kunai::info::{AdditionalInfo, ProcKey, StdEventInfo, TaskAdditionalInfo};
 use kunai::ioc::IoC;
+use kunai::uprobes::{UprobeTarget, Uprobes};
+use kunai::util::lsm::LabelReader;
+use kunai::util::netns::LocalAddrs;
 use kunai::util::uname::Utsname;
 
 use kunai::yara::{Scanner, SourceCode};
 use kunai::{cache, util};
 use kunai_common::bpf_events::{
-    self, event, mut_event, EncodedEvent, Event, PrctlOption, Signal, TaskInfo, Type,
+    self, event, mut_event, EncodedEvent, Event, PrctlOption, RebootCmd, Signal, TaskInfo, Type,
     MAX_BPF_EVENT_SIZE,
 };
+use kunai_common::buffer::argv_from_bytes;
 use kunai_common::config::Filter;
 use kunai_common::{inspect_err, kernel};
 
@@ -50,11 +60,12 @@ use std::borrow::Cow;
 use std::cmp::max;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
 use std::fs::{self, DirBuilder, File};
 use std::io::{self, BufRead, BufReader, Read, Write};
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 
+use std::os::unix::ffi::OsStringExt;
 use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
 use std::path::{Path, PathBuf};
 
@@ -78,12 +89,15 @@ use kunai::cache::*;
 
 use kunai::config::{self, Config};
 use kunai::util::namespace::unshare;
+use kunai::util::redact::Redactor;
 use kunai::util::*;
 
 use communityid::{Flow, Protocol};
 
 const PAGE_SIZE: usize = 4096;
 const KERNEL_IMAGE: &str = "kernel";
+// port used by DNS over TLS and DNS over QUIC
+const ENCRYPTED_DNS_PORT: u16 = 853;
 
 #[derive(Debug, Clone)]
 struct Process {
@@ -92,8 +106,9 @@ struct Process {
     pid: i32,
     // process flags PF_* defined in sched.h
     flags: u32,
-    resolved: HashMap<IpAddr, String>,
     container: Option<Container>,
+    // container id parsed from cgroups
+    container_id: Option<String>,
     // needs to be vec because of procfs
     cgroups: Vec<String>,
     nodename: Option<String>,
@@ -110,6 +125,13 @@ struct Process {
     exit: bool,
     // zombie state of the task
     zombie: bool,
+    // LSM label of the task read after exec and label changes
+    lsm_label: Option<String>,
+    // session id and session leader executable
+    // lazily resolved and refreshed if sid changes
+    session_leader: Option<(i32, Option<PathBuf>)>,
+    // number of connect events seen
+    connections: u64,
 }
 
 impl Process {
@@ -127,9 +149,6 @@ impl Process {
     // run on task exit
     #[inline(always)]
     fn on_exit(&mut self) {
-        // this does not allocate the new map
-        self.resolved.clear();
-        self.resolved.shrink_to_fit();
         self.exit = true;
     }
 }
@@ -261,6 +280,15 @@ impl std::fmt::Display for Action {
     }
 }
 
+/// Execve information rebuilt from the chunks sent before the event
+struct ExecveRebuilt {
+    command_line: Vec<String>,
+    executable: PathBuf,
+    interpreter: PathBuf,
+    env: HashMap<String, String>,
+    truncated: bool,
+}
+
 struct EventConsumer<'s> {
     system_info: SystemInfo,
     config: Config,
@@ -270,13 +298,30 @@ struct EventConsumer<'s> {
     random: u32,
     cache: cache::Cache,
     processes: HashMap<ProcKey, Process>,
-    resolved: HashMap<IpAddr, String>,
+    // host wide DNS resolutions
+    resolved: ResolutionCache,
+    // addresses of network namespaces interfaces
+    local_addrs: LocalAddrs,
+    // enriches public IPs if databases are configured
+    geoip: Option<GeoIp>,
+    // resolves Kubernetes metadata if a CRI socket is configured
+    cri: Option<cri::Resolver>,
+    // resolves container name and image if a Docker socket is configured
+    docker: Option<docker::Resolver>,
+    // reads processes' LSM labels if any LSM labels tasks
+    lsm_labels: Option<LabelReader>,
     killed_tasks: LruHashSet<String>,
     exited_tasks: u64,
     output: Output,
     file_scanner: Option<Scanner<'s>>,
     // used to check if we must generate FileScan events
     scan_events_enabled: bool,
+    // sysctl handlers resolved from kallsyms, lazily initialized
+    sysctl_handlers: Option<HashMap<u64, String>>,
+    // chunks waiting for their execve event
+    execve_chunks: HashMap<ProcKey, bpf_events::ExecveChunks>,
+    // redacts secrets from shell commands
+    shell_redactor: Redactor,
 }
 
 impl EventConsumer<'_> {
@@ -338,6 +383,37 @@ impl EventConsumer<'_> {
 
         let filter = Filter::try_from(&config)?;
 
+        let shell_redactor = Redactor::new(&config.shell_command_redact)
+            .map_err(|e| anyhow!("invalid shell_command_redact pattern: {e}"))?;
+
+        let geoip = if config.geoip_databases.is_empty() {
+            None
+        } else {
+            Some(
+                GeoIp::open(&config.geoip_databases)
+                    .map_err(|e| anyhow!("failed to open geoip database: {e}"))?,
+            )
+        };
+
+        let cri = match config.cri_socket.as_ref() {
+            Some(socket) => Some(
+                cri::Resolver::new(socket, resolver::DEFAULT_CACHE_SIZE)
+                    .map_err(|e| anyhow!("failed to create CRI resolver: {e}"))?,
+            ),
+            None => None,
+        };
+
+        let docker = match config.docker_socket.as_ref() {
+            Some(socket) => Some(
+                docker::Resolver::new(socket, resolver::DEFAULT_CACHE_SIZE)
+                    .map_err(|e| anyhow!("failed to create Docker resolver: {e}"))?,
+            ),
+            None => None,
+        };
+
+        let lsm_labels =
+            LabelReader::new().map_err(|e| anyhow!("failed to create LSM label reader: {e}"))?;
+
         let mut ep = Self {
             system_info,
             config,
@@ -349,10 +425,19 @@ impl EventConsumer<'_> {
             processes: HashMap::with_capacity(512),
             killed_tasks: LruHashSet::with_max_entries(512),
             exited_tasks: 0,
-            resolved: HashMap::new(),
+            resolved: ResolutionCache::default(),
+            local_addrs: LocalAddrs::with_max_entries(512)
+                .map_err(|e| anyhow!("failed to create local addresses resolver: {e}"))?,
+            geoip,
+            cri,
+            docker,
+            lsm_labels,
             output,
             file_scanner: None,
             scan_events_enabled,
+            sysctl_handlers: None,
+            execve_chunks: HashMap::new(),
+            shell_redactor,
         };
 
         // initializing yara rules
@@ -670,13 +755,15 @@ impl EventConsumer<'_> {
             .map(|cg| cg.pathname)
             .collect::<Vec<String>>();
 
+        let container_id = Container::id_from_cgroups(&cgroups).map(String::from);
+
         let task = Process {
             image,
             command_line: p.cmdline().unwrap_or(vec!["?".into()]),
             pid: p.pid,
             flags: stat.flags,
-            resolved: HashMap::new(),
             container: None,
+            container_id,
             cgroups,
             nodename: None,
             real_parent_key: parent_key,
@@ -685,6 +772,9 @@ impl EventConsumer<'_> {
             procfs: true,
             exit: false,
             zombie: false,
+            lsm_label: util::read_lsm_label(p),
+            session_leader: None,
+            connections: 0,
         };
 
         self.processes.insert(tk, task);
@@ -766,44 +856,70 @@ impl EventConsumer<'_> {
     }
 
     #[inline(always)]
-    fn update_resolved(&mut self, ip: IpAddr, resolved: &str, i: &StdEventInfo) {
-        let ck = i.process_key();
-
-        // update local resolve table
-        self.processes.get_mut(&ck).map(|c| {
-            c.resolved
-                .entry(ip)
-                .and_modify(|r| *r = resolved.to_owned())
-                .or_insert(resolved.to_owned())
-        });
+    fn net_ns(i: &StdEventInfo) -> u32 {
+        i.task_info()
+            .namespaces
+            .map(|ns| ns.net)
+            .unwrap_or_default()
+    }
 
-        // update global resolve table
+    #[inline(always)]
+    fn update_resolved(&mut self, ip: IpAddr, resolved: &str, ttl: Option<u32>, i: &StdEventInfo) {
         self.resolved
-            .entry(ip)
-            .and_modify(|r| *r = resolved.to_owned())
-            .or_insert(resolved.to_owned());
+            .insert(Self::net_ns(i), ip, resolved, ttl, i.process_key());
     }
 
     #[inline(always)]
-    fn get_resolved(&self, ip: IpAddr, i: &StdEventInfo) -> Cow<'_, str> {
-        let ck = i.process_key();
+    fn get_resolved(&mut self, ip: IpAddr, i: &StdEventInfo) -> (String, Option<dns::Source>) {
+        match self.resolved.get(Self::net_ns(i), ip, i.process_key()) {
+            Some((domain, src)) => (domain.into(), Some(src)),
+            // default value
+            None => ("?".into(), None),
+        }
+    }
 
-        // we lookup in the local table
-        if let Some(domain) = self
-            .processes
-            .get(&ck)
-            .and_then(|c| c.resolved.get(&ip).map(Cow::from))
-        {
-            return domain;
+    /// Returns true if `ip` is public and is not one of the addresses
+    /// of network namespace `netns`. If `netns` is unknown (zero) the
+    /// network namespace of the task is used. As long as namespace's
+    /// addresses are not known, a public `ip` is considered remote.
+    #[inline(always)]
+    fn is_public_ip_in_ns(&mut self, ip: IpAddr, netns: u32, i: &StdEventInfo) -> bool {
+        if !is_public_ip(ip) {
+            return false;
         }
 
-        // we lookup in the global table
-        if let Some(domain) = self.resolved.get(&ip) {
-            return domain.into();
+        let netns = if netns == 0 { Self::net_ns(i) } else { netns };
+
+        !self
+            .local_addrs
+            .is_local(netns, i.task_info().tgid, ip)
+            .unwrap_or_default()
+    }
+
+    /// Returns geolocation information of `ip`. Only public IPs are looked up.
+    #[inline(always)]
+    fn geo_info(&mut self, ip: IpAddr, public: bool) -> Option<GeoInfo> {
+        if !public {
+            return None;
         }
+        self.geoip.as_mut().and_then(|g| g.lookup(ip))
+    }
 
-        // default value
-        "?".into()
+    /// Builds the [NetworkInfo] of a remote endpoint a task communicates
+    /// with through a socket living in network namespace `netns`
+    #[inline(always)]
+    fn remote_network_info(&mut self, addr: SockAddr, netns: u32, i: &StdEventInfo) -> NetworkInfo {
+        let (hostname, src) = self.get_resolved(addr.ip, i);
+        let public = self.is_public_ip_in_ns(addr.ip, netns, i);
+        NetworkInfo {
+            hostname: Some(hostname),
+            hostname_source: src.map(|s| s.as_str().into()),
+            ip: addr.ip,
+            port: addr.port,
+            public,
+            is_v6: addr.ip.is_ipv6(),
+            geo: self.geo_info(addr.ip, public),
+        }
     }
 
     #[inline(always)]
@@ -867,11 +983,73 @@ impl EventConsumer<'_> {
         }
     }
 
+    #[inline(always)]
+    fn rebuild_execve(
+        &mut self,
+        info: &StdEventInfo,
+        event: &bpf_events::ExecveEvent,
+    ) -> ExecveRebuilt {
+        // chunks left by a previous execve which failed are ignored
+        let chunks = self
+            .execve_chunks
+            .remove(&info.process_key())
+            .filter(|c| c.belongs_to(event.data.chunk_id))
+            .unwrap_or_default();
+
+        let argv = chunks
+            .argv
+            .append_to(event.data.argv.as_slice(), self.config.execve_max_len);
+
+        let path = |head: &kunai_common::path::Path, c: &bpf_events::Chunks| {
+            PathBuf::from(OsString::from_vec(c.join_path(head.as_slice())))
+        };
+
+        // environment is empty if its capture is disabled
+        let env = chunks
+            .env
+            .append_to(event.data.env.as_slice(), event.data.env_len as usize);
+
+        ExecveRebuilt {
+            // some data is missing if it was too big or if chunks got lost
+            truncated: event.data.truncated
+                || argv.len() < event.data.argv_len as usize
+                || env.len() < event.data.env_len as usize
+                || !chunks.executable.is_complete()
+                || !chunks.interpreter.is_complete(),
+            env: self.config.execve_env_from_bytes(&env),
+            command_line: argv_from_bytes(&argv),
+            executable: path(&event.data.executable, &chunks.executable),
+            interpreter: path(&event.data.interpreter, &chunks.interpreter),
+        }
+    }
+
+    #[inline]
+    fn fd_info(&mut self, info: &StdEventInfo, fd: &bpf_events::StdioFd) -> FdInfo {
+        let mut fi = FdInfo {
+            ty: fd.kind().into(),
+            ..Default::default()
+        };
+
+        if fd.is_sock() {
+            fi.socket = fd.socket.map(SocketInfo::from);
+            let netns = fd.socket.map(|s| s.netns).unwrap_or_default();
+            fi.dst = fd
+                .dst
+                .map(SockAddr::from)
+                .map(|dst| self.remote_network_info(dst, netns, info));
+        } else if !fd.path.is_empty() {
+            fi.path = Some(fd.path.to_path_buf());
+        }
+
+        fi
+    }
+
     #[inline(always)]
     fn execve_event(
         &mut self,
         info: StdEventInfo,
         event: &bpf_events::ExecveEvent,
+        rebuilt: ExecveRebuilt,
     ) -> UserEvent<ExecveData> {
         let ancestors = self.get_ancestors_string(&info);
         let cli = self.get_command_line(info.process_key());
@@ -882,13 +1060,30 @@ impl EventConsumer<'_> {
             ancestors,
             parent_exe: self.get_parent_image(&info),
             command_line: cli,
-            exe: self.get_hashes_in_ns(opt_mnt_ns, &cache::Path::from(&event.data.executable)),
+            exe: self.get_hashes_in_ns(
+                opt_mnt_ns,
+                &cache::Path::Bpf {
+                    path: rebuilt.executable,
+                    ebpf_meta: event.data.executable.metadata,
+                },
+            ),
             interpreter: None,
+            env: rebuilt.env,
+            truncated: rebuilt.truncated,
+            cwd: event.data.cwd.to_path_buf(),
+            stdin: self.fd_info(&info, &event.data.stdio[0]),
+            stdout: self.fd_info(&info, &event.data.stdio[1]),
+            stderr: self.fd_info(&info, &event.data.stdio[2]),
         };
 
         if event.data.executable != event.data.interpreter {
-            data.interpreter =
-                Some(self.get_hashes_in_ns(opt_mnt_ns, &cache::Path::from(&event.data.interpreter)))
+            data.interpreter = Some(self.get_hashes_in_ns(
+                opt_mnt_ns,
+                &cache::Path::Bpf {
+                    path: rebuilt.interpreter,
+                    ebpf_meta: event.data.interpreter.metadata,
+                },
+            ))
         }
 
         UserEvent::new(data, info)
@@ -972,6 +1167,175 @@ impl EventConsumer<'_> {
         UserEvent::new(data, info)
     }
 
+    #[inline(always)]
+    fn process_memory_access_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::ProcessMemoryAccessEvent,
+    ) -> UserEvent<ProcessMemoryAccessData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        // we need to set uuid part of target task
+        let mut target = event.data.target;
+        target.set_uuid_random(self.random);
+
+        let tk = ProcKey::from(target.tg_uuid);
+        let tai =
+            Self::mnt_ns_from_task(&target).map(|ns| self.build_task_additional_info(ns, &target));
+
+        let data = ProcessMemoryAccessData {
+            ancestors: self.get_ancestors_string(&info),
+            exe: exe.into(),
+            command_line,
+            method: event.data.method.as_str().into(),
+            direction: if event.data.write { "write" } else { "read" }.into(),
+            bytes: event.data.rc.max(0) as u64,
+            success: event.data.rc >= 0,
+            target: TargetTask {
+                command_line: self.get_command_line(tk),
+                exe: self.get_exe(tk).into(),
+                task: TaskSection::from_task_info_with_addition(target, tai.unwrap_or_default()),
+            },
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn execve_failed_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::ExecveFailedEvent,
+    ) -> UserEvent<ExecveFailedData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let filename = PathBuf::from(event.data.filename.as_str());
+        // relative paths are looked up from cwd or execveat dirfd
+        let path = if filename.is_relative() && !event.data.dir.is_empty() {
+            event.data.dir.to_path_buf().join(filename)
+        } else {
+            filename
+        };
+
+        let argc = (event.data.argc as usize).min(bpf_events::MAX_FAILED_ARGS);
+        let argv = event.data.argv[..argc]
+            .iter()
+            .map(|a| a.as_str().as_bytes())
+            .collect::<Vec<&[u8]>>()
+            .join(&b'\0');
+
+        // rc is a negative errno
+        let errno = -event.data.rc as i32;
+
+        let data = ExecveFailedData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            path,
+            argv: argv_from_bytes(&argv).join(" "),
+            truncated: event.data.truncated,
+            errno,
+            error: util::execve_errno_name(errno).map(String::from),
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn shell_command_event(
+        &self,
+        info: StdEventInfo,
+        event: &bpf_events::ShellCommandEvent,
+    ) -> UserEvent<ShellCommandData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        // line may not be valid UTF-8 so we cannot use as_str
+        let bytes = &event.data.line.s[..event.data.line.len()];
+        let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+        // zsh line editor returns metafied lines
+        let is_zsh = exe
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with("zsh"));
+        let line = if is_zsh {
+            String::from_utf8_lossy(&util::zsh_unmetafy(bytes)).into_owned()
+        } else {
+            String::from_utf8_lossy(bytes).into_owned()
+        };
+        let line = line.trim_end_matches('\n');
+
+        let redacted = self.shell_redactor.redact(line);
+
+        let data = ShellCommandData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            redacted: matches!(redacted, Cow::Owned(_)),
+            line: redacted.into_owned(),
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn seccomp_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::SeccompEvent,
+    ) -> UserEvent<SeccompData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let filter = (event.data.is_filter() && event.data.filter_len > 0).then(|| FilterInfo {
+            md5: md5_data(event.data.filter.as_slice()),
+            sha1: sha1_data(event.data.filter.as_slice()),
+            sha256: sha256_data(event.data.filter.as_slice()),
+            sha512: sha512_data(event.data.filter.as_slice()),
+            len: event.data.filter_len, // size in filter sock_filter blocks
+            size: event.data.filter.len(), // size in bytes
+        });
+
+        let mode = match event.data.mode {
+            bpf_events::SECCOMP_MODE_STRICT => "strict",
+            bpf_events::SECCOMP_MODE_FILTER => "filter",
+            _ => "unknown",
+        };
+
+        let data = SeccompData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            mode: mode.into(),
+            flags: event.data.flags,
+            filter,
+            filter_truncated: event.data.filter_truncated,
+            success: event.data.rc == 0,
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn io_uring_setup_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::IoUringSetupEvent,
+    ) -> UserEvent<IoUringSetupData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let data = IoUringSetupData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            entries: event.data.entries,
+            flags: event.data.flags,
+            sq_poll: event.data.is_sq_poll(),
+            sq_thread_cpu: event.data.sq_thread_cpu,
+            sq_thread_idle: event.data.sq_thread_idle,
+            success: event.data.rc >= 0,
+        };
+
+        UserEvent::new(data, info)
+    }
+
     #[inline(always)]
     fn ptrace_event(
         &mut self,
@@ -1052,6 +1416,8 @@ impl EventConsumer<'_> {
 
         let responses = event.data.answers().unwrap_or_default();
         let ancestors = self.get_ancestors_string(&info);
+        let public = self.is_public_ip_in_ns(dst.ip, event.data.socket.netns, &info);
+        let geo = self.geo_info(dst.ip, public);
 
         for r in responses {
             let mut data = DnsQueryData::new().with_responses(r.answers);
@@ -1063,18 +1429,21 @@ impl EventConsumer<'_> {
             data.src = src;
             data.dns_server = NetworkInfo {
                 hostname: None,
+                hostname_source: None,
                 ip: dst.ip,
                 port: dst.port,
-                public: is_public_ip(dst.ip),
+                public,
                 is_v6: dst.ip.is_ipv6(),
+                geo: geo.clone(),
             };
             data.community_id = community_id.clone();
+            data.io_uring = event.data.io_uring;
 
             // update the resolution map
             data.responses().iter().for_each(|a| {
                 // if we manage to parse IpAddr
                 if let Ok(ip) = a.parse::<IpAddr>() {
-                    self.update_resolved(ip, &r.question, &info);
+                    self.update_resolved(ip, &r.question, r.ttl, &info);
                 }
             });
 
@@ -1097,6 +1466,7 @@ impl EventConsumer<'_> {
             command_line,
             exe: exe.into(),
             path: event.data.path.to_path_buf(),
+            io_uring: event.data.io_uring,
         };
 
         UserEvent::new(data, info)
@@ -1121,6 +1491,126 @@ impl EventConsumer<'_> {
         UserEvent::new(data, info)
     }
 
+    #[inline(always)]
+    fn mount_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::MountEvent,
+    ) -> UserEvent<MountData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let data = MountData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            dev_name: event.data.dev_name.to_string(),
+            path: event.data.path.to_path_buf(),
+            fs_type: event.data.ty.to_string(),
+            flags: event.data.flags,
+            mnt_ns: event.data.mnt_ns,
+            success: event.data.rc == 0,
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn file_chmod_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::FileChmodEvent,
+    ) -> UserEvent<FileChmodData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let data = FileChmodData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            path: event.data.path.to_path_buf(),
+            old_mode: event.data.old_mode,
+            new_mode: event.data.new_mode,
+            became_executable: event.data.became_executable(),
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn file_chown_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::FileChownEvent,
+    ) -> UserEvent<FileChownData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        // (uid_t)-1 means the id is left unchanged
+        let data = FileChownData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            path: event.data.path.to_path_buf(),
+            old_uid: event.data.old_uid,
+            old_gid: event.data.old_gid,
+            new_uid: (event.data.new_uid != u32::MAX).then_some(event.data.new_uid),
+            new_gid: (event.data.new_gid != u32::MAX).then_some(event.data.new_gid),
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn file_xattr_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::FileXattrEvent,
+    ) -> UserEvent<FileXattrData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let data = FileXattrData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            path: event.data.path.to_path_buf(),
+            name: event.data.name.to_string(),
+            size: event.data.size,
+            flags: event.data.flags,
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn file_utimes_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::FileUtimesEvent,
+    ) -> UserEvent<FileUtimesData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let to_utc = |t: &kunai_common::time::Time| match t.nsec {
+            bpf_events::UTIME_OMIT => None,
+            bpf_events::UTIME_NOW => Some(info.utc_timestamp.into()),
+            nsec => UtcDateTime::from_timestamp(t.sec, nsec as u32),
+        };
+
+        let meta = event.data.path.metadata;
+
+        let data = FileUtimesData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            path: event.data.path.to_path_buf(),
+            old_atime: meta
+                .and_then(|m| UtcDateTime::from_timestamp(m.atime.sec, m.atime.nsec as u32)),
+            old_mtime: meta
+                .and_then(|m| UtcDateTime::from_timestamp(m.mtime.sec, m.mtime.nsec as u32)),
+            atime: to_utc(&event.data.atime),
+            mtime: to_utc(&event.data.mtime),
+        };
+
+        UserEvent::new(data, info)
+    }
+
     #[inline(always)]
     fn bpf_prog_load_event(
         &mut self,
@@ -1192,6 +1682,24 @@ impl EventConsumer<'_> {
         UserEvent::new(data, info)
     }
 
+    #[inline(always)]
+    fn socket_create_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::SocketCreateEvent,
+    ) -> UserEvent<SocketCreateData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let data = SocketCreateData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            socket: SocketInfo::from(event.data.socket),
+        };
+
+        UserEvent::new(data, info)
+    }
+
     #[inline(always)]
     fn mprotect_event(
         &self,
@@ -1213,7 +1721,7 @@ impl EventConsumer<'_> {
 
     #[inline(always)]
     fn connect_event(
-        &self,
+        &mut self,
         info: StdEventInfo,
         event: &bpf_events::ConnectEvent,
     ) -> UserEvent<ConnectData> {
@@ -1235,23 +1743,61 @@ impl EventConsumer<'_> {
             exe: exe.into(),
             socket: SocketInfo::from(event.data.socket),
             src,
-            dst: NetworkInfo {
-                hostname: Some(self.get_resolved(dst.ip, &info).into()),
-                ip: dst.ip,
-                port: dst.port,
-                public: is_public_ip(dst.ip),
-                is_v6: dst.ip.is_ipv6(),
-            },
+            dst: self.remote_network_info(dst, event.data.socket.netns, &info),
             community_id: flow.community_id_v1(0).base64(),
             connected: event.data.connected,
+            io_uring: event.data.io_uring,
         };
 
         UserEvent::new(data, info)
     }
 
+    /// Builds an encrypted_dns event if a connection is made to a
+    /// DNS over TLS (or DNS over QUIC) server
     #[inline(always)]
-    fn send_data_event(
+    fn encrypted_dns_event(
         &self,
+        connect: &UserEvent<ConnectData>,
+    ) -> Option<UserEvent<EncryptedDnsData>> {
+        let c = &connect.data;
+
+        if c.dst.port != ENCRYPTED_DNS_PORT {
+            return None;
+        }
+
+        let protocol = match c.socket.proto.as_str() {
+            "TCP" => "dot",
+            "UDP" => "doq",
+            _ => return None,
+        };
+
+        let data = EncryptedDnsData {
+            ancestors: c.ancestors.clone(),
+            command_line: c.command_line.clone(),
+            exe: c.exe.path.clone().into(),
+            socket: c.socket.clone(),
+            src: c.src,
+            dns_server: NetworkInfo {
+                hostname: c.dst.hostname.clone(),
+                hostname_source: c.dst.hostname_source.clone(),
+                ip: c.dst.ip,
+                port: c.dst.port,
+                public: c.dst.public,
+                is_v6: c.dst.is_v6,
+                geo: c.dst.geo.clone(),
+            },
+            community_id: c.community_id.clone(),
+            protocol: protocol.into(),
+            connected: c.connected,
+        };
+
+        let info = EventInfo::from_other_with_type(connect.info.clone(), Type::EncryptedDns);
+        Some(UserEvent::with_data_and_info(data, info))
+    }
+
+    #[inline(always)]
+    fn send_data_event(
+        &mut self,
         info: StdEventInfo,
         event: &bpf_events::SendEntropyEvent,
     ) -> UserEvent<SendDataData> {
@@ -1273,21 +1819,105 @@ impl EventConsumer<'_> {
             command_line,
             socket: SocketInfo::from(event.data.socket),
             src: event.data.src.into(),
-            dst: NetworkInfo {
-                hostname: Some(self.get_resolved(dst.ip, &info).into()),
-                ip: dst.ip,
-                port: dst.port,
-                public: is_public_ip(dst.ip),
-                is_v6: dst.ip.is_ipv6(),
-            },
+            dst: self.remote_network_info(dst, event.data.socket.netns, &info),
             community_id: flow.community_id_v1(0).base64(),
             data_entropy: event.shannon_entropy(),
             data_size: event.data.real_data_size,
+            io_uring: event.data.io_uring,
         };
 
         UserEvent::new(data, info)
     }
 
+    #[inline(always)]
+    fn tls_client_hello_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::TlsClientHelloEvent,
+    ) -> Option<UserEvent<TlsClientHelloData>> {
+        let ch = util::tls::ClientHello::parse(event.data.data.as_slice())
+            .inspect_err(|e| debug!("failed to parse tls client hello: {e}"))
+            .ok()?;
+
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+        let dst: SockAddr = event.data.dst.into();
+        let src: SockAddr = event.data.src.into();
+
+        let flow = Flow::new(
+            Protocol::from(event.data.socket.proto as u8),
+            src.ip,
+            src.port,
+            dst.ip,
+            dst.port,
+        );
+
+        let data = TlsClientHelloData {
+            ancestors: self.get_ancestors_string(&info),
+            exe: exe.into(),
+            command_line,
+            socket: SocketInfo::from(event.data.socket),
+            src,
+            dst: self.remote_network_info(dst, event.data.socket.netns, &info),
+            community_id: flow.community_id_v1(0).base64(),
+            tls_version: ch.version_str(),
+            ja3: ch.ja3(),
+            ja4: ch.ja4(),
+            partial: ch.partial,
+            sni: ch.sni,
+            alpn: ch.alpn,
+            io_uring: event.data.io_uring,
+        };
+
+        Some(UserEvent::new(data, info))
+    }
+
+    #[inline(always)]
+    fn http_request_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::HttpRequestEvent,
+    ) -> Option<UserEvent<HttpRequestData>> {
+        let req = util::http::Request::parse(event.data.data.as_slice())
+            .inspect_err(|e| debug!("failed to parse http request: {e}"))
+            .ok()?;
+
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+        let dst: SockAddr = event.data.dst.into();
+        let src: SockAddr = event.data.src.into();
+
+        let flow = Flow::new(
+            Protocol::from(event.data.socket.proto as u8),
+            src.ip,
+            src.port,
+            dst.ip,
+            dst.port,
+        );
+
+        let data = HttpRequestData {
+            ancestors: self.get_ancestors_string(&info),
+            exe: exe.into(),
+            command_line,
+            socket: SocketInfo::from(event.data.socket),
+            src,
+            dst: self.remote_network_info(dst, event.data.socket.netns, &info),
+            community_id: flow.community_id_v1(0).base64(),
+            host: req.host().map(String::from),
+            path: req.path().into(),
+            url: req.url(
+                &SocketAddr::new(dst.ip, dst.port).to_string(),
+                event.data.tls,
+            ),
+            user_agent: req.user_agent,
+            method: req.method,
+            version: req.version,
+            io_uring: event.data.io_uring,
+            tls: event.data.tls,
+            inbound: event.data.inbound,
+        };
+
+        Some(UserEvent::new(data, info))
+    }
+
     #[inline(always)]
     fn init_module_event(
         &self,
@@ -1309,6 +1939,121 @@ impl EventConsumer<'_> {
         UserEvent::new(data, info)
     }
 
+    #[inline(always)]
+    fn delete_module_event(
+        &self,
+        info: StdEventInfo,
+        event: &bpf_events::DeleteModuleEvent,
+    ) -> UserEvent<DeleteModuleData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let data = DeleteModuleData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            module_name: event.data.name.to_string(),
+            flags: event.data.flags,
+            force: event.data.is_forced(),
+            success: event.data.rc == 0,
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn kexec_load_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::KexecLoadEvent,
+    ) -> UserEvent<KexecLoadData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let kernel = if event.data.kernel.is_empty() {
+            None
+        } else {
+            Some(self.get_hashes_in_ns(
+                Self::task_mnt_ns(&event.info),
+                &cache::Path::from(&event.data.kernel),
+            ))
+        };
+
+        let data = KexecLoadData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            syscall: event.data.syscall_name().into(),
+            kernel,
+            cmdline: event.data.cmdline.to_string(),
+            nr_segments: event.data.nr_segments,
+            flags: event.data.flags,
+            success: event.data.rc == 0,
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn reboot_event(
+        &self,
+        info: StdEventInfo,
+        event: &bpf_events::RebootEvent,
+    ) -> UserEvent<RebootData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let cmd = RebootCmd::try_from_uint(event.data.cmd)
+            .map(|c| c.as_str().into())
+            .unwrap_or(format!("unknown({:#x})", event.data.cmd));
+
+        let data = RebootData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            cmd,
+            magic1: event.data.magic1,
+            magic2: event.data.magic2,
+            valid_magic: event.data.has_valid_magic(),
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn sysctl_write_event(
+        &mut self,
+        info: StdEventInfo,
+        event: &bpf_events::SysctlWriteEvent,
+    ) -> UserEvent<SysctlWriteData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let handlers = self.sysctl_handlers.get_or_insert_with(|| {
+            util::sysctl::handlers()
+                .inspect_err(|e| warn!("failed to read sysctl handlers: {e}"))
+                .unwrap_or_default()
+        });
+
+        let old_value = handlers
+            .get(&event.data.handler)
+            .and_then(|h| util::sysctl::decode_value(h, event.data.old_value.as_slice()));
+
+        let path = event.data.path.to_path_buf();
+        let value = String::from_utf8_lossy(event.data.new_value.as_slice())
+            .trim_end_matches(['\0', '\n'])
+            .to_string();
+
+        let data = SysctlWriteData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            name: util::sysctl::name_from_path(&path),
+            path,
+            value,
+            old_value,
+            success: event.data.rc >= 0,
+        };
+
+        UserEvent::new(data, info)
+    }
+
     #[inline(always)]
     fn file_rename_event(
         &self,
@@ -1328,6 +2073,55 @@ impl EventConsumer<'_> {
         UserEvent::new(data, info)
     }
 
+    #[inline(always)]
+    fn file_link_event(
+        &self,
+        info: StdEventInfo,
+        event: &bpf_events::FileLinkEvent,
+    ) -> UserEvent<FileLinkData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let data = FileLinkData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            link: event.data.link.into(),
+            target: event.data.target.into(),
+            target_uid: event.data.uid,
+            target_gid: event.data.gid,
+            target_mode: event.data.mode,
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    #[inline(always)]
+    fn file_symlink_event(
+        &self,
+        info: StdEventInfo,
+        event: &bpf_events::FileSymlinkEvent,
+    ) -> UserEvent<FileSymlinkData> {
+        let (exe, command_line) = self.get_exe_and_command_line(&info);
+
+        let data = FileSymlinkData {
+            ancestors: self.get_ancestors_string(&info),
+            command_line,
+            exe: exe.into(),
+            link: event.data.link.into(),
+            target: event.data.target.to_string().into(),
+        };
+
+        UserEvent::new(data, info)
+    }
+
+    /// Updates activity counters of the process an event comes from
+    #[inline(always)]
+    fn count_activity<F: FnOnce(&mut Process)>(&mut self, info: &StdEventInfo, f: F) {
+        if let Some(p) = self.processes.get_mut(&info.process_key()) {
+            f(p)
+        }
+    }
+
     #[inline(always)]
     fn exit_event(
         &mut self,
@@ -1336,11 +2130,29 @@ impl EventConsumer<'_> {
     ) -> UserEvent<ExitData> {
         let (exe, command_line) = self.get_exe_and_command_line(&info);
 
+        let connections = self
+            .processes
+            .get(&info.process_key())
+            .map(|p| p.connections)
+            .unwrap_or_default();
+
+        // maxrss is in pages
+        let page_size = util::page_size().unwrap_or(PAGE_SIZE as i64) as u64;
+
         let data = ExitData {
             ancestors: self.get_ancestors_string(&info),
             command_line,
             exe: exe.into(),
             error_code: event.data.error_code,
+            exit_code: event.data.exit_status(),
+            signal: event.data.signal().map(Signal::from_uint_to_string),
+            core_dumped: event.data.core_dumped(),
+            lifetime_ms: event.data.lifetime / 1_000_000,
+            utime_ms: event.data.utime / 1_000_000,
+            stime_ms: event.data.stime / 1_000_000,
+            max_rss_kb: (event.data.maxrss > 0).then_some(event.data.maxrss * page_size / 1024),
+            connections,
+            file_writes: event.data.file_writes,
         };
 
         let etype = event.ty();
@@ -1363,6 +2175,9 @@ impl EventConsumer<'_> {
                 }
             }
 
+            // chunks of a failed execve are never consumed
+            self.execve_chunks.remove(&pk);
+
             // we trigger some very specific cleanup
             if self.exited_tasks % 1000 == 0 {
                 let shadow_proc = self.find_shadow_procs();
@@ -1561,6 +2376,13 @@ impl EventConsumer<'_> {
         };
 
         let mut container_type = Container::from_cgroups(&cgroups);
+        let container_id = Container::id_from_cgroups(&cgroups).map(String::from);
+
+        // we resolve container metadata ahead of time so that they
+        // are hopefully available for the next events of the task
+        if let Some(id) = container_id.as_ref() {
+            self.request_container_info(id, false);
+        }
 
         if container_type.is_none() {
             let ancestors = self.get_ancestors(parent_key, 0);
@@ -1581,14 +2403,19 @@ impl EventConsumer<'_> {
             e.children.insert(info.process_key());
         });
 
+        // label is read in the background and set once available
+        if let Some(r) = self.lsm_labels.as_ref() {
+            r.request(info.task_info().tgid, pk);
+        }
+
         // we insert only if not existing
         self.processes.entry(pk).or_insert(Process {
             image,
             command_line: event.data.argv.to_argv(),
             pid: info.task_info().tgid,
             flags: info.task_info().flags,
-            resolved: HashMap::new(),
             container: container_type,
+            container_id,
             cgroups,
             nodename: event.data.nodename(),
             real_parent_key: Some(parent_key),
@@ -1597,6 +2424,9 @@ impl EventConsumer<'_> {
             procfs: false,
             exit: false,
             zombie: false,
+            lsm_label: None,
+            session_leader: None,
+            connections: 0,
         });
     }
 
@@ -1637,57 +2467,196 @@ impl EventConsumer<'_> {
             std_info.bpf.process.zombie = true
         }
 
-        // we must set the zombie flag of the parent if needed
-        if self
-            .processes
-            .get(&std_info.parent_key())
-            .map(|t| t.zombie)
-            .unwrap_or_default()
-        {
-            std_info.bpf.parent.zombie = true
+        // we must set the zombie flag of the parent if needed
+        if self
+            .processes
+            .get(&std_info.parent_key())
+            .map(|t| t.zombie)
+            .unwrap_or_default()
+        {
+            std_info.bpf.parent.zombie = true
+        }
+    }
+
+    #[inline(always)]
+    fn build_task_additional_info(
+        &mut self,
+        mnt_ns: Mnt,
+        ti: &bpf_events::TaskInfo,
+    ) -> TaskAdditionalInfo {
+        let res = match self.cache.get_user_group_in_ns(mnt_ns, ti.uid, ti.gid) {
+            Ok(o) => Ok(o),
+            Err(e) => match e {
+                Error::Namespace(ns) => {
+                    if ns.is_other_and_io_kind(io::ErrorKind::NotFound) {
+                        self.cache
+                            .get_user_group_in_ns(self.system_info.mount_ns, ti.uid, ti.gid)
+                    } else {
+                        Err(ns.into())
+                    }
+                }
+                _ => Err(e),
+            },
+        };
+
+        // getting user and group information for task
+        let (user, group) = res
+            .inspect_err(|e| {
+                let mut ti = *ti;
+                // fixes the random part to have a searchable uuid for error investigation
+                ti.set_uuid_random(self.random);
+                // make this debug as it can be quite verbose in some cases
+                debug!(
+                    "failed to get task guuid={} user/group: {e}",
+                    ti.tg_uuid.into_uuid()
+                )
+            })
+            .unwrap_or_default();
+
+        let (user, group) = (user.cloned(), group.cloned());
+
+        let lsm_label = self
+            .processes
+            .get(&ProcKey::from(ti.tg_uuid))
+            .and_then(|p| p.lsm_label.clone());
+
+        // login user is looked up on the host as it is
+        // inherited from the login process
+        let login_user = (ti.loginuid != u32::MAX)
+            .then(|| {
+                self.cache
+                    .get_user_in_ns(self.system_info.mount_ns, ti.loginuid)
+                    .ok()
+                    .and_then(|u| u.cloned())
+            })
+            .flatten();
+
+        TaskAdditionalInfo {
+            user,
+            group,
+            lsm_label,
+            login_user,
+            session_leader_exe: self.session_leader_exe(ti),
+        }
+    }
+
+    #[inline(always)]
+    fn session_leader_exe(&mut self, ti: &bpf_events::TaskInfo) -> Option<PathBuf> {
+        // kernel threads have no session
+        if ti.sid <= 0 {
+            return None;
+        }
+
+        let p = self.processes.get(&ProcKey::from(ti.tg_uuid))?;
+
+        if let Some((sid, exe)) = p.session_leader.as_ref() {
+            if *sid == ti.sid {
+                return exe.clone();
+            }
+        }
+
+        // session leader is the task itself
+        let exe = if ti.sid == ti.tgid {
+            Some(p.image.clone())
+        } else {
+            // session leader is usually an ancestor we are already tracking,
+            // we walk ancestors by task uuid so that a recycled pid cannot
+            // be confused with the real leader. If the leader is not found
+            // we prefer leaving the field empty.
+            self.session_leader_from_ancestors(p, ti.sid)
+        };
+
+        self.processes
+            .entry(ProcKey::from(ti.tg_uuid))
+            .and_modify(|p| p.session_leader = Some((ti.sid, exe.clone())));
+
+        exe
+    }
+
+    #[inline]
+    fn session_leader_from_ancestors(&self, p: &Process, sid: i32) -> Option<PathBuf> {
+        let mut tk = p.real_parent_key;
+
+        while let Some(ancestor) = tk.and_then(|k| self.processes.get(&k)) {
+            // an ancestor already resolved the session leader
+            if let Some((asid, exe)) = ancestor.session_leader.as_ref() {
+                if *asid == sid {
+                    return exe.clone();
+                }
+            }
+
+            if ancestor.pid == sid {
+                return Some(ancestor.image.clone());
+            }
+
+            tk = ancestor.real_parent_key;
+        }
+
+        None
+    }
+
+    /// Requests container `id` metadata to be resolved by the runtimes' APIs.
+    /// If `invalidate` is true, what we already know about the container is
+    /// discarded first.
+    #[inline]
+    fn request_container_info(&mut self, id: &str, invalidate: bool) {
+        if let Some(r) = self.cri.as_mut() {
+            if invalidate {
+                r.invalidate(id);
+            }
+            r.request(id);
+        }
+
+        if let Some(r) = self.docker.as_mut() {
+            if invalidate {
+                r.invalidate(id);
+            }
+            r.request(id);
+        }
+    }
+
+    #[inline]
+    fn handle_cgroup_event(&mut self, etype: Type, e: &bpf_events::CgroupEvent) {
+        let cgroup = e.data.cgroup.to_string();
+
+        let Some(id) = Container::id_from_cgroup(&cgroup) else {
+            return;
+        };
+
+        match etype {
+            // container is (re)started, its metadata might have changed
+            Type::CgroupMkdir => self.request_container_info(id, true),
+            // container is stopped
+            Type::CgroupRmdir => {
+                if let Some(r) = self.cri.as_mut() {
+                    r.invalidate(id);
+                }
+                if let Some(r) = self.docker.as_mut() {
+                    r.invalidate(id);
+                }
+            }
+            _ => {}
         }
     }
 
-    #[inline(always)]
-    fn build_task_additional_info(
-        &mut self,
-        mnt_ns: Mnt,
-        ti: &bpf_events::TaskInfo,
-    ) -> TaskAdditionalInfo {
-        let res = match self.cache.get_user_group_in_ns(mnt_ns, ti.uid, ti.gid) {
-            Ok(o) => Ok(o),
-            Err(e) => match e {
-                Error::Namespace(ns) => {
-                    if ns.is_other_and_io_kind(io::ErrorKind::NotFound) {
-                        self.cache
-                            .get_user_group_in_ns(self.system_info.mount_ns, ti.uid, ti.gid)
-                    } else {
-                        Err(ns.into())
-                    }
-                }
-                _ => Err(e),
-            },
+    /// Sets the LSM labels read in the background so far
+    #[inline]
+    fn update_lsm_labels(&mut self) {
+        let Some(r) = self.lsm_labels.as_ref() else {
+            return;
         };
 
-        // getting user and group information for task
-        let (user, group) = res
-            .inspect_err(|e| {
-                let mut ti = *ti;
-                // fixes the random part to have a searchable uuid for error investigation
-                ti.set_uuid_random(self.random);
-                // make this debug as it can be quite verbose in some cases
-                debug!(
-                    "failed to get task guuid={} user/group: {e}",
-                    ti.tg_uuid.into_uuid()
-                )
-            })
-            .unwrap_or_default();
-
-        TaskAdditionalInfo::new(user.cloned(), group.cloned())
+        for (pk, label) in r.responses() {
+            if let (Some(p), Some(label)) = (self.processes.get_mut(&pk), label) {
+                p.lsm_label = Some(label);
+            }
+        }
     }
 
     #[inline(always)]
     fn build_std_event_info(&mut self, i: bpf_events::EventInfo) -> StdEventInfo {
+        self.update_lsm_labels();
+
         let opt_mnt_ns = Self::task_mnt_ns(&i);
         let opt_parent_ns = Self::parent_mnt_ns(&i);
 
@@ -1705,9 +2674,20 @@ impl EventConsumer<'_> {
         if let Some(mnt_ns) = opt_mnt_ns {
             if mnt_ns != self.system_info.mount_ns {
                 let t = self.processes.get(&std_info.process_key());
+                let id = t.and_then(|t| t.container_id.as_ref());
                 container = Some(kunai::info::ContainerInfo {
                     name: t.and_then(|t| t.nodename.clone()).unwrap_or("?".into()),
                     ty: t.and_then(|cd| cd.container),
+                    cri: self
+                        .cri
+                        .as_mut()
+                        .zip(id)
+                        .and_then(|(r, id)| r.get(id).cloned()),
+                    docker: self
+                        .docker
+                        .as_mut()
+                        .zip(id)
+                        .and_then(|(r, id)| r.get(id).cloned()),
                 });
             }
             // getting task additional info
@@ -1992,11 +2972,15 @@ impl EventConsumer<'_> {
             | Type::Max
             | Type::EndConfigurable
             | Type::TaskSched
-            | Type::FileScan => {}
+            | Type::FileScan
+            | Type::EncryptedDns => {}
 
             Type::Execve | Type::ExecveScript => {
                 match event!(enc_event, bpf_events::ExecveEvent) {
                     Ok(e) => {
+                        // argv and paths not fitting in the event come as chunks
+                        let rebuilt = self.rebuild_execve(&std_info, e);
+
                         // this event is used for correlation but cannot be processed
                         // asynchronously so we have to handle correlation here
                         self.handle_correlation_event(
@@ -2004,11 +2988,17 @@ impl EventConsumer<'_> {
                             &bpf_events::CorrelationEvent::from(e),
                         );
 
+                        // correlation must know the complete command line and image
+                        if let Some(p) = self.processes.get_mut(&std_info.process_key()) {
+                            p.image = rebuilt.executable.clone();
+                            p.command_line = rebuilt.command_line.clone();
+                        }
+
                         if self.filter.is_enabled(std_info.bpf.etype) {
                             // we have to rebuild std_info as it has it is uses correlation
                             // information
                             let std_info = self.build_std_event_info(std_info.bpf);
-                            let mut e = self.execve_event(std_info, e);
+                            let mut e = self.execve_event(std_info, e, rebuilt);
 
                             self.scan_and_print(&mut e);
                         }
@@ -2062,6 +3052,48 @@ impl EventConsumer<'_> {
                 Err(e) => error!("failed to decode {} event: {:?}", etype, e),
             },
 
+            Type::ProcessMemoryAccess => {
+                match event!(enc_event, bpf_events::ProcessMemoryAccessEvent) {
+                    Ok(e) => {
+                        let mut e = self.process_memory_access_event(std_info, e);
+                        self.scan_and_print(&mut e);
+                    }
+                    Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+                }
+            }
+
+            Type::ExecveFailed => match event!(enc_event, bpf_events::ExecveFailedEvent) {
+                Ok(e) => {
+                    let mut e = self.execve_failed_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::ShellCommand => match event!(enc_event, bpf_events::ShellCommandEvent) {
+                Ok(e) => {
+                    let mut e = self.shell_command_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::Seccomp => match event!(enc_event, bpf_events::SeccompEvent) {
+                Ok(e) => {
+                    let mut e = self.seccomp_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::IoUringSetup => match event!(enc_event, bpf_events::IoUringSetupEvent) {
+                Ok(e) => {
+                    let mut e = self.io_uring_setup_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
             Type::MmapExec => match event!(enc_event, bpf_events::MmapExecEvent) {
                 Ok(e) => {
                     let mut e = self.mmap_exec_event(std_info, e);
@@ -2080,8 +3112,20 @@ impl EventConsumer<'_> {
 
             Type::Connect => match event!(enc_event, bpf_events::ConnectEvent) {
                 Ok(e) => {
+                    self.count_activity(&std_info, |p| p.connections += 1);
                     let mut e = self.connect_event(std_info, e);
-                    self.scan_and_print(&mut e);
+
+                    if self.filter.is_enabled(Type::EncryptedDns) {
+                        if let Some(mut dns) = self.encrypted_dns_event(&e) {
+                            self.scan_and_print(&mut dns);
+                        }
+                    }
+
+                    // connect events may reach consumer only to generate
+                    // encrypted_dns events so we need to check it here
+                    if self.filter.is_enabled(Type::Connect) {
+                        self.scan_and_print(&mut e);
+                    }
                 }
                 Err(e) => error!("failed to decode {} event: {:?}", etype, e),
             },
@@ -2103,6 +3147,24 @@ impl EventConsumer<'_> {
                 Err(e) => error!("failed to decode {} event: {:?}", etype, e),
             },
 
+            Type::TlsClientHello => match event!(enc_event, bpf_events::TlsClientHelloEvent) {
+                Ok(e) => {
+                    if let Some(mut e) = self.tls_client_hello_event(std_info, e) {
+                        self.scan_and_print(&mut e);
+                    }
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::HttpRequest => match event!(enc_event, bpf_events::HttpRequestEvent) {
+                Ok(e) => {
+                    if let Some(mut e) = self.http_request_event(std_info, e) {
+                        self.scan_and_print(&mut e);
+                    }
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
             Type::InitModule => match event!(enc_event, bpf_events::InitModuleEvent) {
                 Ok(e) => {
                     let mut e = self.init_module_event(std_info, e);
@@ -2111,6 +3173,38 @@ impl EventConsumer<'_> {
                 Err(e) => error!("failed to decode {} event: {:?}", etype, e),
             },
 
+            Type::DeleteModule => match event!(enc_event, bpf_events::DeleteModuleEvent) {
+                Ok(e) => {
+                    let mut e = self.delete_module_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::KexecLoad => match event!(enc_event, bpf_events::KexecLoadEvent) {
+                Ok(e) => {
+                    let mut e = self.kexec_load_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::Reboot => match event!(enc_event, bpf_events::RebootEvent) {
+                Ok(e) => {
+                    let mut e = self.reboot_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::SysctlWrite => match event!(enc_event, bpf_events::SysctlWriteEvent) {
+                Ok(e) => {
+                    let mut e = self.sysctl_write_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
             Type::WriteConfig
             | Type::Write
             | Type::ReadConfig
@@ -2132,6 +3226,64 @@ impl EventConsumer<'_> {
                 Err(e) => error!("failed to decode {} event: {:?}", etype, e),
             },
 
+            Type::Mount | Type::Umount => match event!(enc_event, bpf_events::MountEvent) {
+                Ok(e) => {
+                    let mut e = self.mount_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::FileChmod => match event!(enc_event, bpf_events::FileChmodEvent) {
+                Ok(e) => {
+                    let mut e = self.file_chmod_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::FileChown => match event!(enc_event, bpf_events::FileChownEvent) {
+                Ok(e) => {
+                    let mut e = self.file_chown_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::FileSetxattr | Type::FileRemovexattr => {
+                match event!(enc_event, bpf_events::FileXattrEvent) {
+                    Ok(e) => {
+                        let mut e = self.file_xattr_event(std_info, e);
+                        self.scan_and_print(&mut e);
+                    }
+                    Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+                }
+            }
+
+            Type::FileLink => match event!(enc_event, bpf_events::FileLinkEvent) {
+                Ok(e) => {
+                    let mut e = self.file_link_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::FileSymlink => match event!(enc_event, bpf_events::FileSymlinkEvent) {
+                Ok(e) => {
+                    let mut e = self.file_symlink_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
+            Type::FileUtimes => match event!(enc_event, bpf_events::FileUtimesEvent) {
+                Ok(e) => {
+                    let mut e = self.file_utimes_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
             Type::FileRename => match event!(enc_event, bpf_events::FileRenameEvent) {
                 Ok(e) => {
                     let mut e = self.file_rename_event(std_info, e);
@@ -2156,6 +3308,14 @@ impl EventConsumer<'_> {
                 Err(e) => error!("failed to decode {} event: {:?}", etype, e),
             },
 
+            Type::SocketCreate => match event!(enc_event, bpf_events::SocketCreateEvent) {
+                Ok(e) => {
+                    let mut e = self.socket_create_event(std_info, e);
+                    self.scan_and_print(&mut e);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
+
             Type::Exit | Type::ExitGroup => match event!(enc_event, bpf_events::ExitEvent) {
                 Ok(e) => {
                     let ty = std_info.bpf.etype;
@@ -2212,6 +3372,30 @@ impl EventConsumer<'_> {
             },
 
             Type::SyscoreResume => { /*  just ignore it */ }
+
+            Type::CgroupMkdir | Type::CgroupRmdir => {
+                match event!(enc_event, bpf_events::CgroupEvent) {
+                    Ok(e) => self.handle_cgroup_event(etype, e),
+                    Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+                }
+            }
+
+            // label we know is outdated so we read it again
+            Type::LsmLabelChange => {
+                if let Some(r) = self.lsm_labels.as_ref() {
+                    r.request(std_info.task_info().tgid, std_info.process_key());
+                }
+            }
+
+            Type::Chunk => match event!(enc_event, bpf_events::ChunkEvent) {
+                Ok(e) => {
+                    self.execve_chunks
+                        .entry(std_info.process_key())
+                        .or_default()
+                        .insert(&e.data);
+                }
+                Err(e) => error!("failed to decode {} event: {:?}", etype, e),
+            },
         }
     }
 }
@@ -2279,6 +3463,8 @@ struct EventProducer {
     agent_evt_info: AgentEventInfo,
     // flag to be set when the producer needs to reload
     reload: bool,
+    // binaries and libraries found in events uprobes must be attached to
+    uprobe_targets: Vec<PathBuf>,
 }
 
 #[inline(always)]
@@ -2315,6 +3501,7 @@ impl EventProducer {
             tasks: vec![],
             stop: false,
             reload: false,
+            uprobe_targets: vec![],
         })
     }
 
@@ -2365,6 +3552,72 @@ impl EventProducer {
         self.pipe.push_back(EncodedEvent::from_event(event));
     }
 
+    /// Keeps track of binaries and libraries we must attach uprobes to.
+    /// Returns `true` if `path` has been tracked.
+    #[inline(always)]
+    fn track_uprobe_target(&mut self, tgid: i32, comm: &str, path: PathBuf) -> bool {
+        let Some(target) = UprobeTarget::from_path(&path) else {
+            return false;
+        };
+
+        // readline library is used by many programs, we only care
+        // about the one used by bash. We rely on comm (as the probe
+        // does) so that we never have to hit procfs from here.
+        if target == UprobeTarget::Readline && comm != "bash" {
+            return false;
+        }
+
+        let enabled = (target.is_tls_library()
+            && self.config.tls_uprobes
+            && self.config.is_event_enabled(Type::HttpRequest))
+            || (target.is_shell() && self.config.is_event_enabled(Type::ShellCommand));
+
+        // target must be found before the process exits so we
+        // take its path from the root of the process' mount namespace
+        if enabled {
+            let root = PathBuf::from(format!("/proc/{tgid}/root"));
+            self.uprobe_targets
+                .push(root.join(path.strip_prefix("/").unwrap_or(&path)));
+        }
+
+        enabled
+    }
+
+    /// Keeps track of the uprobe targets already mapped by running
+    /// processes, those are not seen through mmap_exec events
+    fn track_running_uprobe_targets(&mut self) {
+        let Ok(procs) = procfs::process::all_processes()
+            .inspect_err(|e| warn!("failed to list processes: {e}"))
+        else {
+            return;
+        };
+
+        // the same file is mapped by many processes
+        let mut seen = HashSet::new();
+
+        for p in procs.flatten() {
+            let (Ok(maps), Ok(stat)) = (p.maps(), p.stat()) else {
+                continue;
+            };
+
+            for m in maps {
+                if !m.perms.contains(procfs::process::MMPermissions::EXECUTE)
+                    || seen.contains(&(m.dev, m.inode))
+                {
+                    continue;
+                }
+
+                // a file is marked as seen only once tracked as it might
+                // be rejected for a process and accepted for another one
+                if let procfs::process::MMapPath::Path(path) = m.pathname {
+                    if self.track_uprobe_target(p.pid, &stat.comm, path) {
+                        seen.insert((m.dev, m.inode));
+                    }
+                }
+            }
+        }
+    }
+
     /// function used to pre-process some targetted events where time is critical and for which
     /// processing can be done in EventReader
     /// this function must return true if main processing loop has to pass to the next event
@@ -2380,6 +3633,10 @@ impl EventProducer {
                 if event.data.interpreter != event.data.executable {
                     event.info.etype = Type::ExecveScript
                 }
+                let tgid = event.info.process.tgid;
+                let comm = event.info.process.comm_str();
+                let path = event.data.executable.to_path_buf();
+                self.track_uprobe_target(tgid, comm, path);
             }
             Type::BpfProgLoad => {
                 let event = mut_event!(e, bpf_events::BpfProgLoadEvent).unwrap();
@@ -2424,6 +3681,13 @@ impl EventProducer {
                 // we don't need to process such event further
                 return true;
             }
+            Type::MmapExec => {
+                let event = event!(e, bpf_events::MmapExecEvent).unwrap();
+                let tgid = event.info.process.tgid;
+                let comm = event.info.process.comm_str();
+                let path = event.data.filename.to_path_buf();
+                self.track_uprobe_target(tgid, comm, path);
+            }
             Type::SyscoreResume => {
                 debug!("received syscore_resume event");
                 self.reload = true;
@@ -2646,9 +3910,14 @@ impl EventProducer {
                         // info_unchecked can be used here as we are sure info is valid
                         let etype = unsafe { dec.info_unchecked() }.etype;
 
+                        // connect events are needed to generate encrypted_dns events
+                        let needed =
+                            etype == Type::Connect && ep.filter.is_enabled(Type::EncryptedDns);
+
                         // filtering out unwanted events but let Execve/Clone go as those are used
                         // for correlation on consumer side.
                         if ep.filter.is_disabled(etype)
+                            && !needed
                             && !matches!(
                                 etype,
                                 Type::Execve
@@ -2891,6 +4160,22 @@ struct RunOpt {
     #[arg(long)]
     send_data_min_len: Option<u64>,
 
+    /// Report socket_create events for all socket families instead
+    /// of raw, packet and netlink sockets only.
+    #[arg(long)]
+    socket_create_all_families: bool,
+
+    /// Environment variables to report in execve events (comma separated).
+    /// Entries ending with `*` match by prefix, an empty value disables
+    /// environment collection. Supersedes configuration file.
+    #[arg(long)]
+    execve_env: Option<String>,
+
+    /// Maximum length of argv and paths reported in execve events.
+    /// Supersedes configuration file.
+    #[arg(long)]
+    execve_max_len: Option<usize>,
+
     /// Detection/filtering rule file. Supersedes configuration file.
     #[arg(short, long, value_name = "FILE")]
     rule_file: Option<Vec<String>>,
@@ -2960,6 +4245,25 @@ impl TryFrom<RunOpt> for Config {
         // we configure min len for send_data events
         conf.send_data_min_len = opt.send_data_min_len;
 
+        // supersedes configuration if true
+        if opt.socket_create_all_families {
+            conf.socket_create_all_families = true;
+        }
+
+        // supersedes configuration
+        if let Some(execve_max_len) = opt.execve_max_len {
+            conf.execve_max_len = execve_max_len;
+        }
+
+        // supersedes configuration
+        if let Some(execve_env) = opt.execve_env {
+            conf.execve_env = execve_env
+                .split(',')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+        }
+
         // we exclude events
         if let Some(exclude) = opt.exclude {
             let exclude: Vec<&str> = exclude.split(',').collect();
@@ -3080,22 +4384,43 @@ fn time_it<F: FnMut()>(mut f: F) -> Duration {
 // Enum used to deserialize and process events for
 // replay and test commands.
 enum ReplayEvent {
-    Execve(UserEvent<ExecveData>),
+    // boxed as it is much bigger than other events
+    Execve(Box<UserEvent<ExecveData>>),
     Clone(UserEvent<CloneData>),
     Prctl(UserEvent<PrctlData>),
     Kill(UserEvent<KillData>),
     Ptrace(UserEvent<PtraceData>),
+    IoUringSetup(UserEvent<IoUringSetupData>),
+    ProcessMemoryAccess(UserEvent<ProcessMemoryAccessData>),
+    Seccomp(UserEvent<SeccompData>),
+    ExecveFailed(UserEvent<ExecveFailedData>),
+    ShellCommand(UserEvent<ShellCommandData>),
     MmapExec(UserEvent<MmapExecData>),
     MprotectExec(UserEvent<MprotectData>),
     Connect(UserEvent<ConnectData>),
     DnsQuery(UserEvent<DnsQueryData>),
     SendData(UserEvent<SendDataData>),
+    EncryptedDns(UserEvent<EncryptedDnsData>),
+    TlsClientHello(UserEvent<TlsClientHelloData>),
+    HttpRequest(UserEvent<HttpRequestData>),
     InitModule(UserEvent<InitModuleData>),
+    DeleteModule(UserEvent<DeleteModuleData>),
+    KexecLoad(UserEvent<KexecLoadData>),
+    Reboot(UserEvent<RebootData>),
+    SysctlWrite(UserEvent<SysctlWriteData>),
     File(UserEvent<FileData>),
     FileUnlink(UserEvent<UnlinkData>),
     FileRename(UserEvent<FileRenameData>),
+    Mount(UserEvent<MountData>),
+    FileChmod(UserEvent<FileChmodData>),
+    FileChown(UserEvent<FileChownData>),
+    FileXattr(UserEvent<FileXattrData>),
+    FileUtimes(UserEvent<FileUtimesData>),
+    FileLink(UserEvent<FileLinkData>),
+    FileSymlink(UserEvent<FileSymlinkData>),
     BpfProgLoad(UserEvent<BpfProgLoadData>),
     BpfSocketFilter(UserEvent<BpfSocketFilterData>),
+    SocketCreate(UserEvent<SocketCreateData>),
     Exit(UserEvent<ExitData>),
     FileScan(UserEvent<FileScanData>),
     Error(UserEvent<ErrorData>),
@@ -3109,22 +4434,42 @@ impl ReplayEvent {
     #[inline]
     fn scan(&mut self, c: &mut EventConsumer) -> Option<ScanResult> {
         match self {
-            Self::Execve(u) => c.scan(u),
+            Self::Execve(u) => c.scan(u.as_mut()),
             Self::Clone(u) => c.scan(u),
             Self::Prctl(u) => c.scan(u),
             Self::Kill(u) => c.scan(u),
             Self::Ptrace(u) => c.scan(u),
+            Self::IoUringSetup(u) => c.scan(u),
+            Self::ProcessMemoryAccess(u) => c.scan(u),
+            Self::Seccomp(u) => c.scan(u),
+            Self::ExecveFailed(u) => c.scan(u),
+            Self::ShellCommand(u) => c.scan(u),
             Self::MmapExec(u) => c.scan(u),
             Self::MprotectExec(u) => c.scan(u),
             Self::Connect(u) => c.scan(u),
             Self::DnsQuery(u) => c.scan(u),
             Self::SendData(u) => c.scan(u),
+            Self::EncryptedDns(u) => c.scan(u),
+            Self::TlsClientHello(u) => c.scan(u),
+            Self::HttpRequest(u) => c.scan(u),
             Self::InitModule(u) => c.scan(u),
+            Self::DeleteModule(u) => c.scan(u),
+            Self::KexecLoad(u) => c.scan(u),
+            Self::Reboot(u) => c.scan(u),
+            Self::SysctlWrite(u) => c.scan(u),
             Self::File(u) => c.scan(u),
             Self::FileUnlink(u) => c.scan(u),
             Self::FileRename(u) => c.scan(u),
+            Self::Mount(u) => c.scan(u),
+            Self::FileChmod(u) => c.scan(u),
+            Self::FileChown(u) => c.scan(u),
+            Self::FileXattr(u) => c.scan(u),
+            Self::FileUtimes(u) => c.scan(u),
+            Self::FileLink(u) => c.scan(u),
+            Self::FileSymlink(u) => c.scan(u),
             Self::BpfProgLoad(u) => c.scan(u),
             Self::BpfSocketFilter(u) => c.scan(u),
+            Self::SocketCreate(u) => c.scan(u),
             Self::Exit(u) => c.scan(u),
             Self::FileScan(u) => c.scan(u),
             Self::Error(u) => c.scan(u),
@@ -3136,22 +4481,42 @@ impl ReplayEvent {
     #[inline]
     fn scan_and_print(&mut self, c: &mut EventConsumer) -> bool {
         match self {
-            Self::Execve(u) => c.scan_and_print(u),
+            Self::Execve(u) => c.scan_and_print(u.as_mut()),
             Self::Clone(u) => c.scan_and_print(u),
             Self::Prctl(u) => c.scan_and_print(u),
             Self::Kill(u) => c.scan_and_print(u),
             Self::Ptrace(u) => c.scan_and_print(u),
+            Self::IoUringSetup(u) => c.scan_and_print(u),
+            Self::ProcessMemoryAccess(u) => c.scan_and_print(u),
+            Self::Seccomp(u) => c.scan_and_print(u),
+            Self::ExecveFailed(u) => c.scan_and_print(u),
+            Self::ShellCommand(u) => c.scan_and_print(u),
             Self::MmapExec(u) => c.scan_and_print(u),
             Self::MprotectExec(u) => c.scan_and_print(u),
             Self::Connect(u) => c.scan_and_print(u),
             Self::DnsQuery(u) => c.scan_and_print(u),
             Self::SendData(u) => c.scan_and_print(u),
+            Self::EncryptedDns(u) => c.scan_and_print(u),
+            Self::TlsClientHello(u) => c.scan_and_print(u),
+            Self::HttpRequest(u) => c.scan_and_print(u),
             Self::InitModule(u) => c.scan_and_print(u),
+            Self::DeleteModule(u) => c.scan_and_print(u),
+            Self::KexecLoad(u) => c.scan_and_print(u),
+            Self::Reboot(u) => c.scan_and_print(u),
+            Self::SysctlWrite(u) => c.scan_and_print(u),
             Self::File(u) => c.scan_and_print(u),
             Self::FileUnlink(u) => c.scan_and_print(u),
             Self::FileRename(u) => c.scan_and_print(u),
+            Self::Mount(u) => c.scan_and_print(u),
+            Self::FileChmod(u) => c.scan_and_print(u),
+            Self::FileChown(u) => c.scan_and_print(u),
+            Self::FileXattr(u) => c.scan_and_print(u),
+            Self::FileUtimes(u) => c.scan_and_print(u),
+            Self::FileLink(u) => c.scan_and_print(u),
+            Self::FileSymlink(u) => c.scan_and_print(u),
             Self::BpfProgLoad(u) => c.scan_and_print(u),
             Self::BpfSocketFilter(u) => c.scan_and_print(u),
+            Self::SocketCreate(u) => c.scan_and_print(u),
             Self::Exit(u) => c.scan_and_print(u),
             Self::FileScan(u) => c.scan_and_print(u),
             Self::Error(u) => c.scan_and_print(u),
@@ -3186,17 +4551,34 @@ impl TryFrom<serde_json::Value> for ReplayEvent {
 
         // exhaustive pattern matching so that we don't miss new events
         match t {
-            Type::Execve | Type::ExecveScript => event_enum!(ExecveData, ReplayEvent::Execve),
+            Type::Execve | Type::ExecveScript => {
+                event_enum!(ExecveData, |e| ReplayEvent::Execve(Box::new(e)))
+            }
             Type::Clone => event_enum!(CloneData, ReplayEvent::Clone),
             Type::Prctl => event_enum!(PrctlData, ReplayEvent::Prctl),
             Type::Kill => event_enum!(KillData, ReplayEvent::Kill),
             Type::Ptrace => event_enum!(PtraceData, ReplayEvent::Ptrace),
+            Type::IoUringSetup => event_enum!(IoUringSetupData, ReplayEvent::IoUringSetup),
+            Type::ProcessMemoryAccess => {
+                event_enum!(ProcessMemoryAccessData, ReplayEvent::ProcessMemoryAccess)
+            }
+            Type::Seccomp => event_enum!(SeccompData, ReplayEvent::Seccomp),
+            Type::ExecveFailed => event_enum!(ExecveFailedData, ReplayEvent::ExecveFailed),
+            Type::ShellCommand => event_enum!(ShellCommandData, ReplayEvent::ShellCommand),
             Type::MmapExec => event_enum!(MmapExecData, ReplayEvent::MmapExec),
             Type::MprotectExec => event_enum!(MprotectData, ReplayEvent::MprotectExec),
             Type::Connect => event_enum!(ConnectData, ReplayEvent::Connect),
             Type::DnsQuery => event_enum!(DnsQueryData, ReplayEvent::DnsQuery),
             Type::SendData => event_enum!(SendDataData, ReplayEvent::SendData),
+            Type::TlsClientHello => {
+                event_enum!(TlsClientHelloData, ReplayEvent::TlsClientHello)
+            }
+            Type::HttpRequest => event_enum!(HttpRequestData, ReplayEvent::HttpRequest),
             Type::InitModule => event_enum!(InitModuleData, ReplayEvent::InitModule),
+            Type::DeleteModule => event_enum!(DeleteModuleData, ReplayEvent::DeleteModule),
+            Type::KexecLoad => event_enum!(KexecLoadData, ReplayEvent::KexecLoad),
+            Type::Reboot => event_enum!(RebootData, ReplayEvent::Reboot),
+            Type::SysctlWrite => event_enum!(SysctlWriteData, ReplayEvent::SysctlWrite),
             Type::WriteConfig
             | Type::Write
             | Type::ReadConfig
@@ -3207,12 +4589,23 @@ impl TryFrom<serde_json::Value> for ReplayEvent {
             }
             Type::FileUnlink => event_enum!(UnlinkData, ReplayEvent::FileUnlink),
             Type::FileRename => event_enum!(FileRenameData, ReplayEvent::FileRename),
+            Type::Mount | Type::Umount => event_enum!(MountData, ReplayEvent::Mount),
+            Type::FileChmod => event_enum!(FileChmodData, ReplayEvent::FileChmod),
+            Type::FileChown => event_enum!(FileChownData, ReplayEvent::FileChown),
+            Type::FileSetxattr | Type::FileRemovexattr => {
+                event_enum!(FileXattrData, ReplayEvent::FileXattr)
+            }
+            Type::FileUtimes => event_enum!(FileUtimesData, ReplayEvent::FileUtimes),
+            Type::FileLink => event_enum!(FileLinkData, ReplayEvent::FileLink),
+            Type::FileSymlink => event_enum!(FileSymlinkData, ReplayEvent::FileSymlink),
             Type::BpfProgLoad => event_enum!(BpfProgLoadData, ReplayEvent::BpfProgLoad),
             Type::BpfSocketFilter => {
                 event_enum!(BpfSocketFilterData, ReplayEvent::BpfSocketFilter)
             }
+            Type::SocketCreate => event_enum!(SocketCreateData, ReplayEvent::SocketCreate),
             Type::Exit | Type::ExitGroup => event_enum!(ExitData, ReplayEvent::Exit),
             Type::FileScan => event_enum!(FileScanData, ReplayEvent::FileScan),
+            Type::EncryptedDns => event_enum!(EncryptedDnsData, ReplayEvent::EncryptedDns),
             Type::Error => event_enum!(ErrorData, ReplayEvent::Error),
             Type::Start => event_enum!(StartData, ReplayEvent::Start),
             Type::Loss => event_enum!(LossData, ReplayEvent::Loss),
@@ -3225,6 +4618,10 @@ impl TryFrom<serde_json::Value> for ReplayEvent {
             | Type::EndConfigurable
             | Type::TaskSched
             | Type::SyscoreResume
+            | Type::Chunk
+            | Type::CgroupMkdir
+            | Type::CgroupRmdir
+            | Type::LsmLabelChange
             | Type::Max => Err(anyhow!("event type={t} not handled")),
         }
     }
@@ -3499,6 +4896,11 @@ impl Command {
         runtime.block_on(async move {
             // we spawn a task to reload producer when needed
             let main = async move {
+                // targets we attached uprobes to, kept across reloads
+                let mut uprobes = Uprobes::new();
+                // running processes are scanned only once at startup
+                let mut startup = true;
+
                 loop {
                     info!("Starting event producer");
                     // we start producer
@@ -3506,6 +4908,11 @@ impl Command {
                     let mut prod =
                         EventProducer::with_params(&mut bpf, conf.clone(), sender.clone())?;
 
+                    if startup {
+                        prod.track_running_uprobe_targets();
+                        startup = false;
+                    }
+
                     // we create and pipe a start event
                     if let Ok(start) = prod
                         .agent_evt_info
@@ -3520,7 +4927,35 @@ impl Command {
                     // we load and attach bpf programs
                     kunai::load_and_attach_bpf(&conf, current_kernel, &mut bpf)?;
 
+                    // uprobes were detached when programs got dropped
+                    for (path, e) in uprobes.reattach(&mut bpf) {
+                        warn!(
+                            "failed to attach uprobes to {}: {e}",
+                            path.to_string_lossy()
+                        );
+                    }
+
                     loop {
+                        // we attach uprobes to newly found targets
+                        let targets = std::mem::take(&mut arc_prod.lock().await.uprobe_targets);
+                        for path in targets {
+                            match uprobes.attach(&mut bpf, &path) {
+                                Ok(true) => {
+                                    info!("attached uprobes to: {}", path.to_string_lossy())
+                                }
+                                Ok(false) => {}
+                                // target is gone, most likely the process exited
+                                Err(kunai::uprobes::Error::Io(e)) => debug!(
+                                    "failed to stat uprobe target {}: {e}",
+                                    path.to_string_lossy()
+                                ),
+                                Err(e) => warn!(
+                                    "failed to attach uprobes to {}: {e}",
+                                    path.to_string_lossy()
+                                ),
+                            }
+                        }
+
                         // block make sure lock is dropped before sleeping
                         if arc_prod.lock().await.reload {
                             info!("Reloading event producer");
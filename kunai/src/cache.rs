@@ -306,6 +306,40 @@ impl Cache {
 
     /// Get a [User] structure corresponding to user id `uid`
     #[inline(always)]
+    pub fn get_user_in_ns(&mut self, ns: Mnt, uid: u32) -> Result<Option<&User>, Error> {
+        let Some(mnt_ns) = self.mnt_namespaces.get(&ns) else {
+            return Err(Error::UnknownMntNs(ns));
+        };
+
+        let user = mnt_ns.do_in_namespace(|| {
+            let user_path = PathBuf::from(Users::sys_path());
+
+            if !user_path.exists() {
+                return Err(namespace::Error::other(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "user file not found",
+                )));
+            }
+
+            let ukey =
+                Key::from_path_in_ns(ns, &user_path.into()).map_err(namespace::Error::other)?;
+
+            if !self.users.contains_key(&ukey) {
+                self.users.insert(
+                    ukey.clone(),
+                    Users::from_sys().map_err(namespace::Error::other)?,
+                );
+            }
+
+            Ok(self.users.get(&ukey).and_then(|u| u.get_by_uid(uid)))
+        })?;
+
+        Ok(user)
+    }
+
+    /// Get a [User] and [Group] structures corresponding to user id `uid`
+    /// and group id `gid`
+    #[inline(always)]
     pub fn get_user_group_in_ns(
         &mut self,
         ns: Mnt,
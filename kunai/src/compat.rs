@@ -225,6 +225,13 @@ impl<'a> Program<'a> {
         self
     }
 
+    /// Returns true if program is an uprobe or an uretprobe. Such programs
+    /// have no attach point known at load time and must be attached separately.
+    #[inline]
+    pub fn is_uprobe(&self) -> bool {
+        matches!(self.prog(), programs::Program::UProbe(_))
+    }
+
     pub fn prog_type(&self) -> ProgramType {
         self.program.prog_type()
     }
@@ -283,6 +290,9 @@ impl<'a> Program<'a> {
                 let attach = hook.unwrap();
                 p.load(&attach, btf)?;
             }
+            programs::Program::UProbe(p) => {
+                p.load()?;
+            }
             _ => {
                 unimplemented!()
             }
@@ -304,6 +314,9 @@ impl<'a> Program<'a> {
             programs::Program::Lsm(p) => {
                 p.unload()?;
             }
+            programs::Program::UProbe(p) => {
+                p.unload()?;
+            }
             _ => {
                 unimplemented!()
             }
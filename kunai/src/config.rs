@@ -1,13 +1,14 @@
 use huby::ByteSize;
 use kunai_common::{
     bpf_events,
-    config::{BpfConfig, Filter, Loader},
+    config::{BpfConfig, DnsPorts, Filter, Loader, MAX_DNS_PORTS},
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     fs,
     ops::{Div, Mul},
+    path::PathBuf,
 };
 use thiserror::Error;
 
@@ -15,6 +16,7 @@ use crate::util::sha256_data;
 
 pub const DEFAULT_SEND_DATA_MIN_LEN: u64 = 256;
 pub const DEFAULT_MAX_BUFFERED_EVENTS: u16 = 1024;
+pub const DEFAULT_EXECVE_MAX_LEN: usize = 16384;
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -22,6 +24,8 @@ pub enum Error {
     InvalidOutput(String),
     #[error("invalid event {0}")]
     InvalidEvent(String),
+    #[error("too many dns ports: {0} (max={max})", max = MAX_DNS_PORTS)]
+    TooManyDnsPorts(usize),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -71,6 +75,45 @@ pub struct Config {
     pub max_eps_fs: Option<u64>,
     pub workers: Option<usize>,
     pub send_data_min_len: Option<u64>,
+    /// Report sockets of any family, not only raw, packet and netlink ones
+    #[serde(default)]
+    pub socket_create_all_families: bool,
+    /// Environment variables to report in execve events.
+    /// Entries ending with `*` match variables by prefix.
+    #[serde(default = "default_execve_env")]
+    pub execve_env: Vec<String>,
+    /// Maximum length of argv and paths reported in execve events.
+    /// Data not fitting in events is sent in several chunks.
+    #[serde(default = "default_execve_max_len")]
+    pub execve_max_len: usize,
+    /// Attach uprobes to OpenSSL and GnuTLS libraries mapped by processes
+    /// to report plaintext HTTP requests as http_request events. Libraries
+    /// are discovered through mmap_exec events.
+    #[serde(default)]
+    pub tls_uprobes: bool,
+    /// Destination ports DNS traffic is monitored on
+    #[serde(default = "default_dns_ports")]
+    pub dns_ports: Vec<u16>,
+    /// Regular expressions matching secrets to redact from shell_command
+    /// events. If a pattern has a `secret` capture group only that part
+    /// of the match is redacted.
+    #[serde(default = "default_shell_command_redact")]
+    pub shell_command_redact: Vec<String>,
+    /// MaxMind DB files (i.e. GeoLite2 ASN and Country) used to add
+    /// country and ASN information to public destinations of network
+    /// events. Files are reloaded when they change.
+    #[serde(default)]
+    pub geoip_databases: Vec<PathBuf>,
+    /// Path to the CRI socket of the container runtime (i.e. containerd
+    /// or CRI-O) used to add Kubernetes pod and image information to the
+    /// events of containerized processes.
+    #[serde(default)]
+    pub cri_socket: Option<PathBuf>,
+    /// Path to the Docker (or Podman compatible) API socket used to add
+    /// container name, image and compose project to the events of
+    /// containerized processes.
+    #[serde(default)]
+    pub docker_socket: Option<PathBuf>,
     pub harden: bool,
     pub output: Output,
     pub scanner: Scanner,
@@ -84,7 +127,11 @@ impl Default for Config {
             // some events get disabled by default because there are too many
             let en = !matches!(
                 v,
-                bpf_events::Type::Read | bpf_events::Type::Write | bpf_events::Type::WriteClose
+                bpf_events::Type::Read
+                    | bpf_events::Type::Write
+                    | bpf_events::Type::WriteClose
+                    | bpf_events::Type::ExecveFailed
+                    | bpf_events::Type::ShellCommand
             );
 
             if v.is_configurable() {
@@ -105,6 +152,15 @@ impl Default for Config {
             max_eps_fs: Some(DEFAULT_MAX_BUFFERED_EVENTS as u64 * 2),
             workers: None,
             send_data_min_len: None,
+            socket_create_all_families: false,
+            execve_env: default_execve_env(),
+            execve_max_len: DEFAULT_EXECVE_MAX_LEN,
+            tls_uprobes: false,
+            dns_ports: default_dns_ports(),
+            shell_command_redact: default_shell_command_redact(),
+            geoip_databases: vec![],
+            cri_socket: None,
+            docker_socket: None,
             scanner: Scanner {
                 rules: vec![],
                 iocs: vec![],
@@ -118,6 +174,48 @@ impl Default for Config {
     }
 }
 
+fn default_execve_env() -> Vec<String> {
+    [
+        "LD_*",
+        "HISTFILE",
+        "http_proxy",
+        "https_proxy",
+        "HTTP_PROXY",
+        "HTTPS_PROXY",
+        "all_proxy",
+        "ALL_PROXY",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_dns_ports() -> Vec<u16> {
+    vec![53]
+}
+
+fn default_shell_command_redact() -> Vec<String> {
+    [
+        // KEY=value or key: value where key looks like a secret
+        r#"(?i)(?:pass(?:wd|word)?|secret|token|api[_-]?key|access[_-]?key)[a-z_]*\s*[=:]\s*(?P<secret>'[^']*'|"[^"]*"|[^\s'"]+)"#,
+        // command line options
+        r"(?i)--(?:pass(?:wd|word)?|secret|token|api[_-]?key)[= ](?P<secret>\S+)",
+        // HTTP authorization headers
+        r#"(?i)authorization:\s*(?:(?:bearer|basic|token)\s+)?(?P<secret>[^\s'"]+)"#,
+        // credentials in URLs
+        r"://[^/\s:@]+:(?P<secret>[^/\s@]+)@",
+        // AWS access key ids
+        r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_execve_max_len() -> usize {
+    DEFAULT_EXECVE_MAX_LEN
+}
+
 fn host_uuid() -> Option<uuid::Uuid> {
     if let Ok(machine_id) = fs::read_to_string("/etc/machine-id") {
         let machine_id = machine_id.trim_end();
@@ -182,6 +280,35 @@ impl Config {
         self.events.iter_mut().for_each(|(_, e)| e.disable())
     }
 
+    /// Returns `true` if event type `ty` is enabled
+    pub fn is_event_enabled(&self, ty: bpf_events::Type) -> bool {
+        self.events.get(&ty).is_some_and(|e| e.is_enabled())
+    }
+
+    /// Returns `true` if environment variable `name`
+    /// must be reported in execve events
+    pub fn is_execve_env_wanted(&self, name: &str) -> bool {
+        self.execve_env.iter().any(|e| match e.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == e,
+        })
+    }
+
+    /// Parses an environment block (NUL terminated KEY=VALUE strings)
+    /// and returns the variables to report in execve events. A variable
+    /// not terminated by NUL has been cut by truncation so it is ignored.
+    pub fn execve_env_from_bytes(&self, env: &[u8]) -> HashMap<String, String> {
+        env.split_inclusive(|&b| b == 0)
+            .filter_map(|var| var.strip_suffix(b"\0"))
+            .filter_map(|var| {
+                let var = String::from_utf8_lossy(var);
+                let (k, v) = var.split_once('=')?;
+                self.is_execve_env_wanted(k)
+                    .then(|| (k.to_string(), v.to_string()))
+            })
+            .collect()
+    }
+
     /// Serialize the configuration in yaml then
     /// computes the sha256 of it
     pub fn sha256(&self) -> Result<String, serde_yaml::Error> {
@@ -226,12 +353,20 @@ impl TryFrom<&Config> for BpfConfig {
     type Error = Error;
 
     fn try_from(value: &Config) -> Result<Self, Error> {
+        if value.dns_ports.len() > MAX_DNS_PORTS {
+            return Err(Error::TooManyDnsPorts(value.dns_ports.len()));
+        }
+
         Ok(Self {
             loader: Loader::from_own_pid(),
             filter: value.try_into()?,
             glob_max_eps_fs: value.max_eps_fs,
             task_max_eps_fs: value.max_eps_fs.map(|m| m.mul(2).div(3)),
             send_data_min_len: value.send_data_min_len.unwrap_or(DEFAULT_SEND_DATA_MIN_LEN),
+            socket_create_all_families: value.socket_create_all_families,
+            execve_env: !value.execve_env.is_empty(),
+            execve_max_len: value.execve_max_len.try_into().unwrap_or(u32::MAX),
+            dns_ports: DnsPorts::from_slice(&value.dns_ports),
         })
     }
 }
@@ -263,6 +398,62 @@ mod test {
         println!("{}", serde_yaml::to_string(&config).unwrap());
     }
 
+    #[test]
+    fn test_execve_env_wanted() {
+        let config = Config {
+            execve_env: vec!["LD_*".into(), "HISTFILE".into()],
+            ..Default::default()
+        };
+
+        assert!(config.is_execve_env_wanted("LD_PRELOAD"));
+        assert!(config.is_execve_env_wanted("HISTFILE"));
+        assert!(!config.is_execve_env_wanted("HISTFILESIZE"));
+        assert!(!config.is_execve_env_wanted("PATH"));
+    }
+
+    #[test]
+    fn test_execve_env_from_bytes() {
+        let config = Config {
+            execve_env: vec!["LD_*".into(), "HISTFILE".into()],
+            ..Default::default()
+        };
+
+        let env = config.execve_env_from_bytes(
+            b"PATH=/usr/bin\0LD_PRELOAD=/tmp/x.so\0HISTFILE=/dev/null\0EMPTY=\0NOVALUE\0",
+        );
+        assert_eq!(env.len(), 2);
+        assert_eq!(env["LD_PRELOAD"], "/tmp/x.so");
+        assert_eq!(env["HISTFILE"], "/dev/null");
+
+        // value may contain =
+        let env = config.execve_env_from_bytes(b"LD_DEBUG=a=b\0");
+        assert_eq!(env["LD_DEBUG"], "a=b");
+
+        // truncated last variable is not reported
+        let env = config.execve_env_from_bytes(b"HISTFILE=/dev/null\0LD_PRELOAD=/tmp/x");
+        assert_eq!(env.len(), 1);
+        assert_eq!(env["HISTFILE"], "/dev/null");
+
+        assert!(config.execve_env_from_bytes(b"").is_empty());
+    }
+
+    #[test]
+    fn test_dns_ports() {
+        let mut config = Config {
+            dns_ports: vec![53, 5353],
+            ..Default::default()
+        };
+
+        let bpf_config = BpfConfig::try_from(&config).unwrap();
+        assert!(bpf_config.dns_ports.contains(5353));
+
+        config.dns_ports = (1..=MAX_DNS_PORTS as u16 + 1).collect();
+        assert!(matches!(
+            BpfConfig::try_from(&config),
+            Err(Error::TooManyDnsPorts(_))
+        ));
+    }
+
     #[test]
     fn test_machine_uuid() {
         let uuid = host_uuid();
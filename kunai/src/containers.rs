@@ -5,6 +5,13 @@ use kunai_macros::StrEnum;
 use serde::{Deserialize, Serialize};
 use std::path::{self};
 
+pub mod cri;
+pub mod docker;
+pub mod resolver;
+
+// prefixes of the cgroup names given to containers by runtimes
+const CONTAINER_ID_PREFIXES: &[&str] = &["cri-containerd-", "crio-", "docker-", "libpod-"];
+
 #[derive(StrEnum, Debug, PartialEq, Clone, Copy)]
 pub enum Container {
     #[str("lxc")]
@@ -99,6 +106,25 @@ impl Container {
         None
     }
 
+    /// Returns the id of the container a cgroup path belongs to, if any.
+    /// Both systemd (i.e. `.../cri-containerd-<id>.scope`) and cgroupfs
+    /// (i.e. `/kubepods/besteffort/pod<uid>/<id>`) layouts are supported.
+    pub fn id_from_cgroup(cgroup: &str) -> Option<&str> {
+        let last = cgroup.rsplit(path::MAIN_SEPARATOR).next()?;
+        let last = last.strip_suffix(".scope").unwrap_or(last);
+        let id = CONTAINER_ID_PREFIXES
+            .iter()
+            .find_map(|p| last.strip_prefix(p))
+            .unwrap_or(last);
+
+        (id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit())).then_some(id)
+    }
+
+    #[inline]
+    pub fn id_from_cgroups(cgroups: &[String]) -> Option<&str> {
+        cgroups.iter().find_map(|c| Self::id_from_cgroup(c))
+    }
+
     #[inline]
     pub fn from_ancestors(ancestors: &Vec<String>) -> Option<Container> {
         for a in ancestors {
@@ -133,4 +159,32 @@ mod test {
         // this is an unknown variant so we should get an error
         assert!(serde_json::from_str::<'_, Container>(r#""lxk""#).is_err());
     }
+
+    #[test]
+    fn test_id_from_cgroup() {
+        let id = "4f66ad9a0b2e8c6a6d1c7b0d3f1e7a0c4e9b2d5f8a1c3e6b9d2f5a8c1e4b7d0a";
+
+        for cgroup in [
+            format!("/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod7e3a5c1b.slice/cri-containerd-{id}.scope"),
+            format!("/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod7e3a5c1b.slice/crio-{id}.scope"),
+            format!("/kubepods/besteffort/pod7e3a5c1b/{id}"),
+            format!("/system.slice/docker-{id}.scope"),
+            format!("/docker/{id}"),
+        ] {
+            assert_eq!(Container::id_from_cgroup(&cgroup), Some(id), "{cgroup}");
+        }
+
+        assert_eq!(
+            Container::id_from_cgroup("/user.slice/user-1000.slice/session-2.scope"),
+            None
+        );
+        assert_eq!(
+            Container::id_from_cgroup(&format!("/system.slice/docker-{}.scope", &id[..12])),
+            None
+        );
+        assert_eq!(
+            Container::id_from_cgroups(&["/".into(), format!("/docker/{id}")]),
+            Some(id)
+        );
+    }
 }
@@ -0,0 +1,489 @@
+//! Minimal client of the Container Runtime Interface (CRI) exposed by
+//! containerd and CRI-O on their unix sockets. It is used to enrich
+//! events coming from Kubernetes containers with pod and image metadata.
+
+use std::{collections::HashMap, io, path::Path, time::Duration};
+
+use hyper_util::rt::TokioIo;
+use thiserror::Error;
+use tokio::net::UnixStream;
+use tonic::{
+    client::Grpc,
+    codec::ProstCodec,
+    codegen::http::uri::PathAndQuery,
+    transport::{Channel, Endpoint, Uri},
+    Code, Request, Status,
+};
+use tower::service_fn;
+
+use super::resolver::{self, RuntimeClient};
+
+const CONTAINER_STATUS: &str = "/runtime.v1.RuntimeService/ContainerStatus";
+const LIST_POD_SANDBOX: &str = "/runtime.v1.RuntimeService/ListPodSandbox";
+
+// labels set by the kubelet on containers and pod sandboxes
+const LABEL_POD_NAME: &str = "io.kubernetes.pod.name";
+const LABEL_POD_NAMESPACE: &str = "io.kubernetes.pod.namespace";
+const LABEL_POD_UID: &str = "io.kubernetes.pod.uid";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Subset of the messages of the `runtime.v1` CRI API we need
+pub mod proto {
+    use std::collections::HashMap;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ContainerStatusRequest {
+        #[prost(string, tag = "1")]
+        pub container_id: String,
+        #[prost(bool, tag = "2")]
+        pub verbose: bool,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ContainerStatusResponse {
+        #[prost(message, optional, tag = "1")]
+        pub status: Option<ContainerStatus>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ContainerMetadata {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(uint32, tag = "2")]
+        pub attempt: u32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ImageSpec {
+        #[prost(string, tag = "1")]
+        pub image: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ContainerStatus {
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(message, optional, tag = "2")]
+        pub metadata: Option<ContainerMetadata>,
+        #[prost(message, optional, tag = "8")]
+        pub image: Option<ImageSpec>,
+        #[prost(string, tag = "9")]
+        pub image_ref: String,
+        #[prost(map = "string, string", tag = "12")]
+        pub labels: HashMap<String, String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct PodSandboxFilter {
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(map = "string, string", tag = "3")]
+        pub label_selector: HashMap<String, String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ListPodSandboxRequest {
+        #[prost(message, optional, tag = "1")]
+        pub filter: Option<PodSandboxFilter>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct PodSandboxMetadata {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, tag = "2")]
+        pub uid: String,
+        #[prost(string, tag = "3")]
+        pub namespace: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct PodSandbox {
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(message, optional, tag = "2")]
+        pub metadata: Option<PodSandboxMetadata>,
+        #[prost(map = "string, string", tag = "5")]
+        pub labels: HashMap<String, String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ListPodSandboxResponse {
+        #[prost(message, repeated, tag = "1")]
+        pub items: Vec<PodSandbox>,
+    }
+}
+
+use proto::*;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Transport(#[from] tonic::transport::Error),
+    #[error("{0}")]
+    Status(#[from] Status),
+}
+
+/// Metadata of a Kubernetes container
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CriInfo {
+    pub pod_name: Option<String>,
+    pub pod_namespace: Option<String>,
+    pub pod_labels: HashMap<String, String>,
+    pub image: Option<String>,
+    pub image_digest: Option<String>,
+}
+
+#[inline]
+fn non_empty(s: String) -> Option<String> {
+    (!s.is_empty()).then_some(s)
+}
+
+/// Extracts the image digest from the image reference found in
+/// container status (i.e. `docker.io/library/nginx@sha256:...`).
+/// Some runtimes report a bare `sha256:...` which is the image ID
+/// (digest of the image config) and not the digest of the manifest
+/// so it is not returned.
+#[inline]
+fn image_digest(image_ref: &str) -> Option<String> {
+    match image_ref.rsplit_once('@') {
+        Some((repo, digest)) if !repo.is_empty() && digest.starts_with("sha256:") => {
+            Some(digest.into())
+        }
+        _ => None,
+    }
+}
+
+/// Async CRI client connected to a runtime unix socket
+pub struct Client {
+    grpc: Grpc<Channel>,
+}
+
+impl RuntimeClient for Client {
+    const NAME: &'static str = "CRI";
+
+    type Info = CriInfo;
+    type Error = Error;
+
+    async fn connect(socket: &Path) -> Result<Self, Error> {
+        let socket = socket.to_path_buf();
+
+        // the URI is not used as we connect through the unix socket
+        let channel = Endpoint::from_static("http://localhost")
+            .timeout(REQUEST_TIMEOUT)
+            .connect_timeout(REQUEST_TIMEOUT)
+            .connect_with_connector(service_fn(move |_: Uri| {
+                let socket = socket.clone();
+                async move { Ok::<_, io::Error>(TokioIo::new(UnixStream::connect(socket).await?)) }
+            }))
+            .await?;
+
+        Ok(Self {
+            grpc: Grpc::new(channel),
+        })
+    }
+
+    async fn container_info(&mut self, id: &str) -> Result<Option<CriInfo>, Error> {
+        let Some(mut status) = self.container_status(id).await? else {
+            return Ok(None);
+        };
+
+        let mut info = CriInfo {
+            pod_name: status.labels.remove(LABEL_POD_NAME),
+            pod_namespace: status.labels.remove(LABEL_POD_NAMESPACE),
+            image: status.image.map(|i| i.image).and_then(non_empty),
+            image_digest: image_digest(&status.image_ref),
+            ..Default::default()
+        };
+
+        // pod labels are only found on the pod sandbox
+        if let Some(uid) = status.labels.remove(LABEL_POD_UID) {
+            let filter = PodSandboxFilter {
+                label_selector: HashMap::from([(LABEL_POD_UID.into(), uid)]),
+                ..Default::default()
+            };
+
+            if let Some(sandbox) = self.list_pod_sandbox(filter).await?.into_iter().next() {
+                if let Some(md) = sandbox.metadata {
+                    info.pod_name = non_empty(md.name).or(info.pod_name);
+                    info.pod_namespace = non_empty(md.namespace).or(info.pod_namespace);
+                }
+                // we don't keep labels added by the kubelet
+                info.pod_labels = sandbox
+                    .labels
+                    .into_iter()
+                    .filter(|(k, _)| !k.starts_with("io.kubernetes."))
+                    .collect();
+            }
+        }
+
+        Ok(Some(info))
+    }
+}
+
+impl Client {
+    async fn unary<Req, Resp>(&mut self, path: &'static str, req: Req) -> Result<Resp, Error>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        self.grpc.ready().await?;
+        Ok(self
+            .grpc
+            .unary(
+                Request::new(req),
+                PathAndQuery::from_static(path),
+                ProstCodec::default(),
+            )
+            .await?
+            .into_inner())
+    }
+
+    pub async fn container_status(&mut self, id: &str) -> Result<Option<ContainerStatus>, Error> {
+        let req = ContainerStatusRequest {
+            container_id: id.into(),
+            verbose: false,
+        };
+
+        match self
+            .unary::<_, ContainerStatusResponse>(CONTAINER_STATUS, req)
+            .await
+        {
+            Ok(resp) => Ok(resp.status),
+            Err(Error::Status(s)) if s.code() == Code::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn list_pod_sandbox(
+        &mut self,
+        filter: PodSandboxFilter,
+    ) -> Result<Vec<PodSandbox>, Error> {
+        let req = ListPodSandboxRequest {
+            filter: Some(filter),
+        };
+
+        Ok(self
+            .unary::<_, ListPodSandboxResponse>(LIST_POD_SANDBOX, req)
+            .await?
+            .items)
+    }
+}
+
+/// Non blocking CRI metadata resolver
+pub type Resolver = resolver::Resolver<Client>;
+
+#[cfg(test)]
+mod test {
+    use std::{
+        convert::Infallible,
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+        thread,
+        time::Instant,
+    };
+
+    use tokio::net::UnixListener;
+    use tokio_stream::wrappers::UnixListenerStream;
+    use tonic::{
+        body::BoxBody,
+        codegen::http,
+        server::{Grpc, NamedService, UnaryService},
+        transport::Server,
+        Response,
+    };
+    use tower::Service;
+
+    use super::*;
+
+    const ID: &str = "4f66ad9a0b2e8c6a6d1c7b0d3f1e7a0c4e9b2d5f8a1c3e6b9d2f5a8c1e4b7d0a";
+
+    type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+    // fake runtime knowing about a single container
+    #[derive(Clone)]
+    struct FakeCri;
+
+    impl NamedService for FakeCri {
+        const NAME: &'static str = "runtime.v1.RuntimeService";
+    }
+
+    struct StatusSvc;
+
+    impl UnaryService<ContainerStatusRequest> for StatusSvc {
+        type Response = ContainerStatusResponse;
+        type Future = BoxFuture<Result<Response<Self::Response>, tonic::Status>>;
+
+        fn call(&mut self, req: Request<ContainerStatusRequest>) -> Self::Future {
+            Box::pin(async move {
+                if req.get_ref().container_id != ID {
+                    return Err(tonic::Status::not_found("container not found"));
+                }
+                Ok(Response::new(ContainerStatusResponse {
+                    status: Some(ContainerStatus {
+                        id: ID.into(),
+                        metadata: Some(ContainerMetadata {
+                            name: "nginx".into(),
+                            attempt: 0,
+                        }),
+                        image: Some(ImageSpec {
+                            image: "docker.io/library/nginx:1.27".into(),
+                        }),
+                        image_ref: "docker.io/library/nginx@sha256:0123abcd".into(),
+                        labels: HashMap::from([
+                            (LABEL_POD_NAME.into(), "web-0".into()),
+                            (LABEL_POD_NAMESPACE.into(), "default".into()),
+                            (LABEL_POD_UID.into(), "7e3a5c1b".into()),
+                        ]),
+                    }),
+                }))
+            })
+        }
+    }
+
+    struct ListSandboxSvc;
+
+    impl UnaryService<ListPodSandboxRequest> for ListSandboxSvc {
+        type Response = ListPodSandboxResponse;
+        type Future = BoxFuture<Result<Response<Self::Response>, tonic::Status>>;
+
+        fn call(&mut self, req: Request<ListPodSandboxRequest>) -> Self::Future {
+            Box::pin(async move {
+                let uid = req
+                    .into_inner()
+                    .filter
+                    .and_then(|mut f| f.label_selector.remove(LABEL_POD_UID));
+
+                let items = if uid.as_deref() == Some("7e3a5c1b") {
+                    vec![PodSandbox {
+                        id: "sandbox".into(),
+                        metadata: Some(PodSandboxMetadata {
+                            name: "web-0".into(),
+                            uid: "7e3a5c1b".into(),
+                            namespace: "default".into(),
+                        }),
+                        labels: HashMap::from([
+                            ("app".into(), "web".into()),
+                            (LABEL_POD_UID.into(), "7e3a5c1b".into()),
+                        ]),
+                    }]
+                } else {
+                    vec![]
+                };
+
+                Ok(Response::new(ListPodSandboxResponse { items }))
+            })
+        }
+    }
+
+    impl Service<http::Request<BoxBody>> for FakeCri {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<BoxBody>) -> Self::Future {
+            Box::pin(async move {
+                Ok(match req.uri().path() {
+                    CONTAINER_STATUS => {
+                        Grpc::new(ProstCodec::default()).unary(StatusSvc, req).await
+                    }
+                    LIST_POD_SANDBOX => {
+                        Grpc::new(ProstCodec::default())
+                            .unary(ListSandboxSvc, req)
+                            .await
+                    }
+                    _ => tonic::Status::unimplemented("").into_http(),
+                })
+            })
+        }
+    }
+
+    fn serve(socket: &Path) {
+        let listener = std::os::unix::net::UnixListener::bind(socket).unwrap();
+        listener.set_nonblocking(true).unwrap();
+
+        thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async move {
+                    let incoming =
+                        UnixListenerStream::new(UnixListener::from_std(listener).unwrap());
+                    Server::builder()
+                        .add_service(FakeCri)
+                        .serve_with_incoming(incoming)
+                        .await
+                        .unwrap()
+                })
+        });
+    }
+
+    #[test]
+    fn test_image_digest() {
+        assert_eq!(
+            image_digest("docker.io/library/nginx@sha256:0123abcd"),
+            Some("sha256:0123abcd".into())
+        );
+        // bare image ID
+        assert_eq!(image_digest("sha256:0123abcd"), None);
+        assert_eq!(image_digest("@sha256:0123abcd"), None);
+        assert_eq!(image_digest("docker.io/library/nginx:latest"), None);
+    }
+
+    #[test]
+    fn test_cri_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("cri.sock");
+        serve(&socket);
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        rt.block_on(async {
+            let mut c = Client::connect(&socket).await.unwrap();
+
+            assert_eq!(
+                c.container_info(ID).await.unwrap(),
+                Some(CriInfo {
+                    pod_name: Some("web-0".into()),
+                    pod_namespace: Some("default".into()),
+                    pod_labels: HashMap::from([("app".into(), "web".into())]),
+                    image: Some("docker.io/library/nginx:1.27".into()),
+                    image_digest: Some("sha256:0123abcd".into()),
+                })
+            );
+
+            assert_eq!(c.container_info("unknown").await.unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn test_cri_resolver() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("cri.sock");
+        serve(&socket);
+
+        let mut r = Resolver::new(&socket, 8).unwrap();
+
+        // first lookup is never blocking
+        assert!(r.get(ID).is_none());
+
+        let start = Instant::now();
+        while r.get(ID).is_none() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(r.get(ID).unwrap().pod_name.as_deref(), Some("web-0"));
+        assert!(!r.is_pending(ID));
+    }
+}
@@ -0,0 +1,281 @@
+//! Minimal client of the Docker Engine API exposed on a unix socket.
+//! Podman exposes a compatible API, so both runtimes are supported.
+//! It is used to enrich events coming from containers with the
+//! container name, image and compose project.
+
+use std::{collections::HashMap, io, path::Path, time::Duration};
+
+use bytes::Bytes;
+use http::{header, Request, StatusCode};
+use http_body_util::{BodyExt, Empty};
+use hyper::client::conn::http1::{self, SendRequest};
+use hyper_util::rt::TokioIo;
+use log::debug;
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::{net::UnixStream, time::timeout};
+
+use super::resolver::{self, RuntimeClient};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+// labels set by docker compose and podman-compose
+const LABEL_COMPOSE_PROJECT: &str = "com.docker.compose.project";
+const LABEL_PODMAN_COMPOSE_PROJECT: &str = "io.podman.compose.project";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("request timed out")]
+    Timeout(#[from] tokio::time::error::Elapsed),
+    #[error("{0}")]
+    Http(#[from] http::Error),
+    #[error("{0}")]
+    Hyper(#[from] hyper::Error),
+    #[error("unexpected status: {0}")]
+    Status(StatusCode),
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Metadata of a Docker or Podman container
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DockerInfo {
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub image_id: Option<String>,
+    pub compose_project: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct Config {
+    image: String,
+    labels: Option<HashMap<String, String>>,
+}
+
+// subset of the fields returned when inspecting a container
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct Inspect {
+    name: String,
+    image: String,
+    config: Config,
+}
+
+#[inline]
+fn non_empty(s: String) -> Option<String> {
+    (!s.is_empty()).then_some(s)
+}
+
+impl From<Inspect> for DockerInfo {
+    fn from(mut value: Inspect) -> Self {
+        let mut labels = value.config.labels.take().unwrap_or_default();
+
+        Self {
+            // container names are prefixed with a /
+            name: non_empty(value.name.trim_start_matches('/').into()),
+            image: non_empty(value.config.image),
+            image_id: non_empty(value.image),
+            compose_project: labels
+                .remove(LABEL_COMPOSE_PROJECT)
+                .or_else(|| labels.remove(LABEL_PODMAN_COMPOSE_PROJECT)),
+        }
+    }
+}
+
+/// Async Docker API client connected to a unix socket
+pub struct Client {
+    sender: SendRequest<Empty<Bytes>>,
+}
+
+impl RuntimeClient for Client {
+    const NAME: &'static str = "Docker";
+
+    type Info = DockerInfo;
+    type Error = Error;
+
+    async fn connect(socket: &Path) -> Result<Self, Error> {
+        let stream = timeout(REQUEST_TIMEOUT, UnixStream::connect(socket)).await??;
+        let (sender, conn) = http1::handshake(TokioIo::new(stream)).await?;
+
+        tokio::spawn(async move {
+            if let Err(e) = conn.await {
+                debug!("docker api connection error: {e}");
+            }
+        });
+
+        Ok(Self { sender })
+    }
+
+    async fn container_info(&mut self, id: &str) -> Result<Option<DockerInfo>, Error> {
+        // the host is not used as we connect through the unix socket
+        let req = Request::get(format!("/containers/{id}/json"))
+            .header(header::HOST, "localhost")
+            .body(Empty::new())?;
+
+        self.sender.ready().await?;
+        let resp = timeout(REQUEST_TIMEOUT, self.sender.send_request(req)).await??;
+
+        match resp.status() {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => return Ok(None),
+            s => return Err(Error::Status(s)),
+        }
+
+        let body = timeout(REQUEST_TIMEOUT, resp.into_body().collect())
+            .await??
+            .to_bytes();
+
+        Ok(Some(serde_json::from_slice::<Inspect>(&body)?.into()))
+    }
+}
+
+/// Non blocking Docker metadata resolver
+pub type Resolver = resolver::Resolver<Client>;
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        os::unix::net::UnixListener,
+        thread,
+        time::Instant,
+    };
+
+    use super::*;
+
+    const ID: &str = "8e1d7f0c5b3a9e2d4c6f8a0b1d3e5f7a9c2b4d6e8f0a1c3e5b7d9f2a4c6e8b0d";
+
+    const INSPECT: &str = r#"{
+        "Id": "8e1d7f0c5b3a9e2d4c6f8a0b1d3e5f7a9c2b4d6e8f0a1c3e5b7d9f2a4c6e8b0d",
+        "Name": "/app-web-1",
+        "Image": "sha256:5ef79149e0ec84a7a9f9284c3f91aa3c20608f8391f5445eabe92ef07dbda03c",
+        "State": {"Status": "running", "Pid": 4242},
+        "Config": {
+            "Hostname": "8e1d7f0c5b3a",
+            "Image": "nginx:1.27",
+            "Labels": {
+                "com.docker.compose.project": "app",
+                "com.docker.compose.service": "web"
+            }
+        }
+    }"#;
+
+    // fake API server answering HTTP/1.1 requests on keep-alive connections
+    fn serve(socket: &Path) {
+        let listener = UnixListener::bind(socket).unwrap();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                loop {
+                    let mut request_line = String::new();
+                    if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+                        break;
+                    }
+
+                    // skipping headers
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).is_ok_and(|n| n > 0) && line != "\r\n" {
+                        line.clear();
+                    }
+
+                    let (status, body) =
+                        if request_line.starts_with(&format!("GET /containers/{ID}/json ")) {
+                            ("200 OK", INSPECT)
+                        } else {
+                            ("404 Not Found", r#"{"message":"No such container"}"#)
+                        };
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_inspect_into_info() {
+        let i: Inspect = serde_json::from_str(
+            r#"{"Name":"/db","Config":{"Labels":{"io.podman.compose.project":"stack"}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            DockerInfo::from(i),
+            DockerInfo {
+                name: Some("db".into()),
+                compose_project: Some("stack".into()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_docker_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("docker.sock");
+        serve(&socket);
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        rt.block_on(async {
+            let mut c = Client::connect(&socket).await.unwrap();
+
+            assert_eq!(
+                c.container_info(ID).await.unwrap(),
+                Some(DockerInfo {
+                    name: Some("app-web-1".into()),
+                    image: Some("nginx:1.27".into()),
+                    image_id: Some(
+                        "sha256:5ef79149e0ec84a7a9f9284c3f91aa3c20608f8391f5445eabe92ef07dbda03c"
+                            .into()
+                    ),
+                    compose_project: Some("app".into()),
+                })
+            );
+
+            // the connection is kept alive between requests
+            assert_eq!(c.container_info("unknown").await.unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn test_docker_resolver_invalidate() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("docker.sock");
+        serve(&socket);
+
+        let mut r = Resolver::new(&socket, 8).unwrap();
+
+        let wait = |r: &mut Resolver| {
+            let start = Instant::now();
+            while r.get(ID).is_none() {
+                assert!(start.elapsed() < Duration::from_secs(10));
+                thread::sleep(Duration::from_millis(10));
+            }
+        };
+
+        wait(&mut r);
+        assert_eq!(r.get(ID).unwrap().name.as_deref(), Some("app-web-1"));
+
+        // container is restarted, metadata must be resolved again
+        r.invalidate(ID);
+        assert!(r.get(ID).is_none());
+        assert!(r.is_pending(ID));
+
+        wait(&mut r);
+        assert!(!r.is_pending(ID));
+    }
+}
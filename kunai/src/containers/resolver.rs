@@ -0,0 +1,235 @@
+//! Non blocking resolution of container metadata from the API
+//! of the container runtimes running on the host.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use log::{debug, warn};
+use lru_st::collections::LruHashMap;
+
+/// Default maximum number of containers kept in cache
+pub const DEFAULT_CACHE_SIZE: usize = 1024;
+
+// a container may not be known by the runtime yet when we
+// first see it, so failed resolutions are retried after a while
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Client of a container runtime API listening on a unix socket
+pub trait RuntimeClient: Sized {
+    /// Name of the API, used in logs
+    const NAME: &'static str;
+
+    type Info: Send + 'static;
+    type Error: Display;
+
+    /// Connects to the API listening on `socket`
+    fn connect(socket: &Path) -> impl Future<Output = Result<Self, Self::Error>>;
+
+    /// Returns the metadata of container `id` or `None` if
+    /// the container is not known by the runtime.
+    fn container_info(
+        &mut self,
+        id: &str,
+    ) -> impl Future<Output = Result<Option<Self::Info>, Self::Error>>;
+}
+
+struct Entry<T> {
+    info: Option<T>,
+    updated: Instant,
+}
+
+/// Container metadata resolver. Requests are processed in a
+/// background thread so that the caller never waits for the runtime.
+/// Resolved metadata are cached by container id.
+pub struct Resolver<C: RuntimeClient> {
+    requests: Sender<(u64, String)>,
+    responses: Receiver<(u64, String, Option<C::Info>)>,
+    cache: LruHashMap<String, Entry<C::Info>>,
+    // generation of the request in flight for a container, responses
+    // to older requests are outdated and must be dropped
+    pending: HashMap<String, u64>,
+    generation: u64,
+}
+
+impl<C: RuntimeClient> Resolver<C> {
+    /// Creates a new resolver querying the runtime listening on `socket`
+    pub fn new<P: AsRef<Path>>(socket: P, max_entries: usize) -> Result<Self, io::Error> {
+        let socket = socket.as_ref().to_path_buf();
+        let (requests, worker_requests) = mpsc::channel();
+        let (worker_responses, responses) = mpsc::channel();
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        thread::Builder::new()
+            .name(format!("kunai-{}", C::NAME.to_lowercase()))
+            .spawn(move || Self::worker(rt, socket, worker_requests, worker_responses))?;
+
+        Ok(Self {
+            requests,
+            responses,
+            cache: LruHashMap::with_max_entries(max_entries),
+            pending: HashMap::new(),
+            generation: 0,
+        })
+    }
+
+    fn worker(
+        rt: tokio::runtime::Runtime,
+        socket: PathBuf,
+        requests: Receiver<(u64, String)>,
+        responses: Sender<(u64, String, Option<C::Info>)>,
+    ) {
+        let mut client: Option<C> = None;
+
+        while let Ok((generation, id)) = requests.recv() {
+            let info = rt.block_on(async {
+                if client.is_none() {
+                    client = C::connect(&socket)
+                        .await
+                        .inspect_err(|e| {
+                            warn!(
+                                "failed to connect to {} socket {}: {e}",
+                                C::NAME,
+                                socket.display()
+                            )
+                        })
+                        .ok();
+                }
+
+                match client.as_mut()?.container_info(&id).await {
+                    Ok(info) => info,
+                    Err(e) => {
+                        debug!("failed to resolve container id={id} with {}: {e}", C::NAME);
+                        // we reconnect at next request
+                        client = None;
+                        None
+                    }
+                }
+            });
+
+            if responses.send((generation, id, info)).is_err() {
+                break;
+            }
+        }
+    }
+
+    // processes all the responses available without blocking
+    fn update(&mut self) {
+        while let Ok((generation, id, info)) = self.responses.try_recv() {
+            if self.pending.get(&id) != Some(&generation) {
+                continue;
+            }
+            self.pending.remove(&id);
+            self.cache.insert(
+                id,
+                Entry {
+                    info,
+                    updated: Instant::now(),
+                },
+            );
+        }
+    }
+
+    /// Asks for the metadata of container `id` to be resolved, if
+    /// needed. This can be used to resolve containers ahead of time.
+    pub fn request(&mut self, id: &str) {
+        self.update();
+
+        let id = id.to_string();
+        let resolved = self
+            .cache
+            .get(&id)
+            .is_some_and(|e| e.info.is_some() || e.updated.elapsed() < RETRY_INTERVAL);
+
+        if !resolved
+            && !self.pending.contains_key(&id)
+            && self.requests.send((self.generation, id.clone())).is_ok()
+        {
+            self.pending.insert(id, self.generation);
+            self.generation = self.generation.wrapping_add(1);
+        }
+    }
+
+    /// Returns the metadata of container `id` if already resolved.
+    /// If not, resolution is requested and `None` is returned.
+    pub fn get(&mut self, id: &str) -> Option<&C::Info> {
+        self.request(id);
+        self.cache
+            .get(&id.to_string())
+            .and_then(|e| e.info.as_ref())
+    }
+
+    /// Forgets what we know about container `id`, this must be
+    /// used when a container is started or stopped. A response to
+    /// a request already in flight is dropped. No new request is
+    /// made, the next lookup of a running container does it.
+    pub fn invalidate(&mut self, id: &str) {
+        self.update();
+        self.cache.remove(&id.to_string());
+        self.pending.remove(id);
+    }
+
+    #[inline]
+    pub fn is_pending(&self, id: &str) -> bool {
+        self.pending.contains_key(id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    // fake client returning the number of calls made so far
+    struct Counter;
+
+    impl RuntimeClient for Counter {
+        const NAME: &'static str = "counter";
+
+        type Info = usize;
+        type Error = String;
+
+        async fn connect(_socket: &Path) -> Result<Self, Self::Error> {
+            Ok(Self)
+        }
+
+        async fn container_info(&mut self, _id: &str) -> Result<Option<usize>, Self::Error> {
+            Ok(Some(CALLS.fetch_add(1, Ordering::SeqCst) + 1))
+        }
+    }
+
+    #[test]
+    fn test_invalidate_pending() {
+        const ID: &str = "b7e8d4c2a1f0";
+        let mut r = Resolver::<Counter>::new("/nonexistent", 8).unwrap();
+
+        // first request is still in flight or its response not processed
+        r.request(ID);
+        r.invalidate(ID);
+        // invalidation does not request again
+        assert!(!r.is_pending(ID));
+
+        let start = Instant::now();
+        while r.get(ID).is_none() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        // response to the first request must have been dropped
+        assert_eq!(r.get(ID), Some(&2));
+        assert!(!r.is_pending(ID));
+    }
+}
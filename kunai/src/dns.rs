@@ -0,0 +1,276 @@
+use std::{
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+use lru_st::collections::LruHashMap;
+
+use crate::info::ProcKey;
+
+/// Default maximum number of resolutions kept in cache
+pub const DEFAULT_CACHE_SIZE: usize = 16384;
+
+// TTL used when DNS answers did not carry any
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+// some resolvers answer with very low TTLs while the connections
+// made to the resolved IPs may happen, or last, a lot longer
+const MIN_TTL: Duration = Duration::from_secs(60);
+const MAX_TTL: Duration = Duration::from_secs(86400);
+// maximum number of resolvers we keep a resolution for the same IP
+const MAX_RESOLVERS: usize = 8;
+
+/// Tells where a resolution comes from, relatively to the
+/// process looking it up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    SameProcess,
+    OtherProcess,
+}
+
+impl Source {
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::SameProcess => "same_process",
+            Self::OtherProcess => "other_process",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    domain: String,
+    expires: Instant,
+    resolver: ProcKey,
+}
+
+/// Host wide cache of DNS resolutions. Resolutions are partitioned by
+/// network namespace, as the same IP may point to different hosts from
+/// within different namespaces. For a given IP, a resolution is kept
+/// per resolving process so that one process cannot overwrite what
+/// another one resolved. The cache is bounded and entries expire
+/// according to the TTL of the DNS answers they come from.
+pub struct ResolutionCache {
+    // entries are ordered from the least to the most recently inserted
+    entries: LruHashMap<(u32, IpAddr), Vec<Entry>>,
+}
+
+impl Default for ResolutionCache {
+    fn default() -> Self {
+        Self::with_max_entries(DEFAULT_CACHE_SIZE)
+    }
+}
+
+impl ResolutionCache {
+    pub fn with_max_entries(max_entries: usize) -> Self {
+        Self {
+            entries: LruHashMap::with_max_entries(max_entries),
+        }
+    }
+
+    #[inline]
+    fn ttl_duration(ttl: Option<u32>) -> Duration {
+        ttl.map(|t| Duration::from_secs(t as u64).clamp(MIN_TTL, MAX_TTL))
+            .unwrap_or(DEFAULT_TTL)
+    }
+
+    /// Inserts a resolution of `ip` into `domain`, done by process `resolver`
+    /// in network namespace `netns`. `ttl` is the TTL (in seconds) of the answer.
+    #[inline]
+    pub fn insert(
+        &mut self,
+        netns: u32,
+        ip: IpAddr,
+        domain: &str,
+        ttl: Option<u32>,
+        resolver: ProcKey,
+    ) {
+        self.insert_at(Instant::now(), netns, ip, domain, ttl, resolver)
+    }
+
+    fn insert_at(
+        &mut self,
+        now: Instant,
+        netns: u32,
+        ip: IpAddr,
+        domain: &str,
+        ttl: Option<u32>,
+        resolver: ProcKey,
+    ) {
+        let key = (netns, ip);
+        let entry = Entry {
+            domain: domain.into(),
+            expires: now + Self::ttl_duration(ttl),
+            resolver,
+        };
+
+        let Some(entries) = self.entries.get_mut(&key) else {
+            self.entries.insert(key, vec![entry]);
+            return;
+        };
+
+        entries.retain(|e| e.expires > now && e.resolver != resolver);
+        if entries.len() >= MAX_RESOLVERS {
+            entries.remove(0);
+        }
+        entries.push(entry);
+    }
+
+    /// Returns the domain `ip` resolves to in network namespace `netns`,
+    /// along with the [Source] of the resolution relatively to `requester`.
+    /// The resolution done by `requester` is preferred, if there is none
+    /// the most recent resolution done by another process is returned.
+    /// Expired resolutions are removed from the cache.
+    #[inline]
+    pub fn get(&mut self, netns: u32, ip: IpAddr, requester: ProcKey) -> Option<(&str, Source)> {
+        self.get_at(Instant::now(), netns, ip, requester)
+    }
+
+    fn get_at(
+        &mut self,
+        now: Instant,
+        netns: u32,
+        ip: IpAddr,
+        requester: ProcKey,
+    ) -> Option<(&str, Source)> {
+        let key = (netns, ip);
+
+        let entries = self.entries.get_mut(&key)?;
+        entries.retain(|e| e.expires > now);
+        if entries.is_empty() {
+            self.entries.remove(&key);
+            return None;
+        }
+
+        let entries = self.entries.get(&key)?;
+        if let Some(e) = entries.iter().find(|e| e.resolver == requester) {
+            return Some((e.domain.as_str(), Source::SameProcess));
+        }
+
+        entries
+            .last()
+            .map(|e| (e.domain.as_str(), Source::OtherProcess))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use kunai_common::uuid::ProcUuid;
+
+    use super::*;
+
+    fn key(pid: u32) -> ProcKey {
+        ProcKey::from(ProcUuid::new(42_000_000_000, 0, pid))
+    }
+
+    #[test]
+    fn test_resolution_source() {
+        let mut c = ResolutionCache::default();
+        let ip: IpAddr = "93.184.215.14".parse().unwrap();
+
+        c.insert(1, ip, "example.com", Some(3600), key(10));
+
+        assert_eq!(
+            c.get(1, ip, key(10)),
+            Some(("example.com", Source::SameProcess))
+        );
+        assert_eq!(
+            c.get(1, ip, key(11)),
+            Some(("example.com", Source::OtherProcess))
+        );
+        // other network namespace
+        assert_eq!(c.get(2, ip, key(10)), None);
+    }
+
+    #[test]
+    fn test_resolution_per_resolver() {
+        let mut c = ResolutionCache::default();
+        let ip: IpAddr = "104.16.0.1".parse().unwrap();
+
+        c.insert(1, ip, "a.example.com", Some(3600), key(10));
+        c.insert(1, ip, "b.example.com", Some(3600), key(11));
+
+        // each process gets what it resolved
+        assert_eq!(
+            c.get(1, ip, key(10)),
+            Some(("a.example.com", Source::SameProcess))
+        );
+        assert_eq!(
+            c.get(1, ip, key(11)),
+            Some(("b.example.com", Source::SameProcess))
+        );
+        // others get the most recent resolution
+        assert_eq!(
+            c.get(1, ip, key(12)),
+            Some(("b.example.com", Source::OtherProcess))
+        );
+
+        // a resolver updating its resolution becomes the most recent
+        c.insert(1, ip, "c.example.com", Some(3600), key(10));
+        assert_eq!(
+            c.get(1, ip, key(12)),
+            Some(("c.example.com", Source::OtherProcess))
+        );
+        assert_eq!(
+            c.get(1, ip, key(11)),
+            Some(("b.example.com", Source::SameProcess))
+        );
+
+        // the number of resolvers kept per IP is bounded
+        for pid in 20..20 + MAX_RESOLVERS as u32 {
+            c.insert(1, ip, "d.example.com", Some(3600), key(pid));
+        }
+        assert_eq!(
+            c.get(1, ip, key(10)),
+            Some(("d.example.com", Source::OtherProcess))
+        );
+    }
+
+    #[test]
+    fn test_resolution_expiry() {
+        let mut c = ResolutionCache::default();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let now = Instant::now();
+
+        // ttl is raised to MIN_TTL
+        c.insert_at(now, 1, ip, "short.lan", Some(1), key(10));
+        assert!(c
+            .get_at(now + Duration::from_secs(30), 1, ip, key(10))
+            .is_some());
+        assert!(c.get_at(now + MIN_TTL, 1, ip, key(10)).is_none());
+        // expired entries are removed
+        assert!(c.is_empty());
+
+        c.insert_at(now, 1, ip, "no.ttl", None, key(10));
+        assert!(c.get_at(now + DEFAULT_TTL, 1, ip, key(10)).is_none());
+    }
+
+    #[test]
+    fn test_resolution_bounded() {
+        let mut c = ResolutionCache::with_max_entries(2);
+
+        for i in 0..4u8 {
+            c.insert(
+                1,
+                IpAddr::from([10, 0, 0, i]),
+                "host.lan",
+                Some(60),
+                key(10),
+            );
+        }
+
+        assert_eq!(c.len(), 2);
+        assert!(c.get(1, IpAddr::from([10, 0, 0, 0]), key(10)).is_none());
+        assert!(c.get(1, IpAddr::from([10, 0, 0, 3]), key(10)).is_some());
+    }
+}
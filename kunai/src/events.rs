@@ -1,6 +1,6 @@
 use std::{
     borrow::Cow,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
 };
@@ -20,6 +20,7 @@ use crate::{
     cache::{FileMeta, Hashes},
     containers::Container,
     info::{ContainerInfo, StdEventInfo, TaskAdditionalInfo},
+    util::http::strip_port,
 };
 
 pub mod agent;
@@ -41,15 +42,41 @@ impl From<PathBuf> for File {
 #[getter(use_serde_rename)]
 pub struct ContainerSection {
     pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime_name: Option<String>,
     #[serde(rename = "type")]
     pub ty: Option<Container>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pod_labels: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_digest: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_project: Option<String>,
 }
 
 impl From<ContainerInfo> for ContainerSection {
     fn from(value: ContainerInfo) -> Self {
+        let cri = value.cri.unwrap_or_default();
+        let docker = value.docker.unwrap_or_default();
         Self {
             name: value.name,
+            runtime_name: docker.name,
             ty: value.ty,
+            pod_name: cri.pod_name,
+            pod_namespace: cri.pod_namespace,
+            pod_labels: cri.pod_labels,
+            image: cri.image.or(docker.image),
+            image_digest: cri.image_digest,
+            image_id: docker.image_id,
+            compose_project: docker.compose_project,
         }
     }
 }
@@ -86,11 +113,56 @@ impl From<&StdEventInfo> for EventSection {
 #[derive(Debug, FieldGetter, Serialize, Deserialize, Clone)]
 pub struct NamespaceInfo {
     pub mnt: u32,
+    #[serde(default)]
+    pub net: u32,
 }
 
 impl From<kunai_common::bpf_events::Namespaces> for NamespaceInfo {
     fn from(value: kunai_common::bpf_events::Namespaces) -> Self {
-        Self { mnt: value.mnt }
+        Self {
+            mnt: value.mnt,
+            net: value.net,
+        }
+    }
+}
+
+#[derive(Debug, FieldGetter, Serialize, Deserialize, Clone)]
+pub struct CapsSection {
+    #[serde(with = "u64_hex")]
+    pub effective: u64,
+    #[serde(with = "u64_hex")]
+    pub permitted: u64,
+    #[serde(with = "u64_hex")]
+    pub bounding: u64,
+}
+
+impl From<kunai_common::bpf_events::Capabilities> for CapsSection {
+    fn from(value: kunai_common::bpf_events::Capabilities) -> Self {
+        Self {
+            effective: value.effective,
+            permitted: value.permitted,
+            bounding: value.bounding,
+        }
+    }
+}
+
+// kernel names pseudo terminals ptsN while
+// userland tools display them as pts/N
+#[inline(always)]
+fn tty_name(name: &str) -> String {
+    match name.strip_prefix("pts") {
+        Some(n) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => format!("pts/{n}"),
+        _ => name.into(),
+    }
+}
+
+#[inline(always)]
+fn seccomp_mode_str(mode: u32) -> String {
+    match mode {
+        0 => "disabled".into(),
+        1 => "strict".into(),
+        2 => "filter".into(),
+        _ => "unknown".into(),
     }
 }
 
@@ -108,6 +180,17 @@ pub struct TaskSection {
     #[serde(with = "u32_hex")]
     pub flags: u32,
     pub zombie: bool,
+    pub caps: CapsSection,
+    pub no_new_privs: bool,
+    pub seccomp: String,
+    // best effort, unset if the process was gone when read
+    pub lsm_label: Option<String>,
+    pub loginuid: Option<u32>,
+    pub login_user: Option<String>,
+    pub sessionid: Option<u32>,
+    pub sid: i32,
+    pub tty: Option<String>,
+    pub session_leader_exe: Option<PathBuf>,
 }
 
 impl TaskSection {
@@ -124,13 +207,30 @@ impl TaskSection {
             namespaces: ti.namespaces.map(|ns| ns.into()),
             flags: ti.flags,
             zombie: ti.zombie,
+            caps: ti.caps.into(),
+            no_new_privs: ti.no_new_privs,
+            seccomp: seccomp_mode_str(ti.seccomp_mode),
+            lsm_label: add.lsm_label,
+            loginuid: (ti.loginuid != u32::MAX).then_some(ti.loginuid),
+            login_user: add.login_user.map(|u| u.name),
+            sessionid: (ti.sessionid != u32::MAX).then_some(ti.sessionid),
+            sid: ti.sid,
+            tty: (!ti.tty.is_empty()).then(|| tty_name(ti.tty.as_str())),
+            session_leader_exe: add.session_leader_exe,
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct UtcDateTime(DateTime<Utc>);
 
+impl UtcDateTime {
+    #[inline]
+    pub fn from_timestamp(secs: i64, nsecs: u32) -> Option<Self> {
+        DateTime::from_timestamp(secs, nsecs).map(Self)
+    }
+}
+
 impl From<DateTime<Utc>> for UtcDateTime {
     fn from(value: DateTime<Utc>) -> Self {
         Self(value)
@@ -151,6 +251,15 @@ where
     serializer.serialize_str(&ts.0.to_rfc3339_opts(SecondsFormat::Nanos, true))
 }
 
+impl Serialize for UtcDateTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_utc_ts(self, serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for UtcDateTime {
     fn deserialize<D>(deserializer: D) -> Result<UtcDateTime, D::Error>
     where
@@ -415,6 +524,30 @@ mod u32_hex {
     }
 }
 
+mod u32_octal {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[inline(always)]
+    pub fn serialize<S>(value: &u32, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0o{:o}", value))
+    }
+
+    #[inline(always)]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<u32, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u32::from_str_radix(
+            String::deserialize(deserializer)?.trim_start_matches("0o"),
+            8,
+        )
+        .map_err(serde::de::Error::custom)
+    }
+}
+
 mod u64_hex {
     use serde::{Deserialize, Deserializer, Serializer};
 
@@ -485,6 +618,18 @@ pub struct ExecveData {
     pub exe: Hashes,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub interpreter: Option<Hashes>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub truncated: bool,
+    #[serde(default)]
+    pub cwd: PathBuf,
+    #[serde(default)]
+    pub stdin: FdInfo,
+    #[serde(default)]
+    pub stdout: FdInfo,
+    #[serde(default)]
+    pub stderr: FdInfo,
 }
 
 impl Scannable for ExecveData {
@@ -515,6 +660,49 @@ impl IocGetter for ExecveData {
     }
 }
 
+def_user_data!(
+    pub struct ExecveFailedData {
+        pub path: PathBuf,
+        pub argv: String,
+        pub truncated: bool,
+        pub errno: i32,
+        pub error: Option<String>,
+    }
+);
+
+impl Scannable for ExecveFailedData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+impl IocGetter for ExecveFailedData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![self.exe.path.to_string_lossy(), self.path.to_string_lossy()]
+    }
+}
+
+def_user_data!(
+    pub struct ShellCommandData {
+        pub line: String,
+        pub redacted: bool,
+    }
+);
+
+impl Scannable for ShellCommandData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+impl IocGetter for ShellCommandData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![self.exe.path.to_string_lossy()]
+    }
+}
+
 def_user_data!(
     pub struct CloneData {
         #[serde(with = "u64_hex")]
@@ -595,6 +783,88 @@ impl Scannable for PtraceData {
 
 impl_std_iocs!(PtraceData);
 
+def_user_data!(
+    pub struct ProcessMemoryAccessData {
+        pub method: String,
+        pub direction: String,
+        pub bytes: u64,
+        pub success: bool,
+        pub target: TargetTask,
+    }
+);
+
+impl Scannable for ProcessMemoryAccessData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![
+            Cow::Borrowed(&self.exe.path),
+            Cow::Borrowed(&self.target.exe.path),
+        ]
+    }
+}
+
+impl IocGetter for ProcessMemoryAccessData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![
+            self.exe.path.to_string_lossy(),
+            self.target.exe.path.to_string_lossy(),
+        ]
+    }
+}
+
+def_user_data!(
+    pub struct SeccompData {
+        pub mode: String,
+        #[serde(with = "u32_hex")]
+        pub flags: u32,
+        pub filter: Option<FilterInfo>,
+        // filter hashes are computed on its first 256 instructions only
+        pub filter_truncated: bool,
+        pub success: bool,
+    }
+);
+
+impl Scannable for SeccompData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+impl IocGetter for SeccompData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        let mut v = vec![self.exe.path.to_string_lossy()];
+        if let Some(filter) = self.filter.as_ref() {
+            v.push(filter.md5.as_str().into());
+            v.push(filter.sha1.as_str().into());
+            v.push(filter.sha256.as_str().into());
+            v.push(filter.sha512.as_str().into());
+        }
+        v
+    }
+}
+
+def_user_data!(
+    pub struct IoUringSetupData {
+        pub entries: u32,
+        #[serde(with = "u32_hex")]
+        pub flags: u32,
+        pub sq_poll: bool,
+        pub sq_thread_cpu: u32,
+        pub sq_thread_idle: u32,
+        pub success: bool,
+    }
+);
+
+impl Scannable for IoUringSetupData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+impl_std_iocs!(IoUringSetupData);
+
 def_user_data!(
     pub struct MmapExecData {
         pub mapped: Hashes,
@@ -661,24 +931,56 @@ impl From<kunai_common::net::SockAddr> for SockAddr {
     }
 }
 
+/// Geolocation and ASN information of an IP address
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, FieldGetter)]
+pub struct GeoInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asn: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_org: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, FieldGetter)]
 pub struct NetworkInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hostname: Option<String>,
+    // whether hostname was resolved by the same process or by another one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname_source: Option<String>,
     pub ip: IpAddr,
     pub port: u16,
     pub public: bool,
     pub is_v6: bool,
+    // filled only if geoip databases are configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo: Option<GeoInfo>,
+}
+
+/// Information about the file behind a file descriptor
+#[derive(Debug, Default, Serialize, Deserialize, FieldGetter)]
+pub struct FdInfo {
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket: Option<SocketInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dst: Option<NetworkInfo>,
 }
 
 impl Default for NetworkInfo {
     fn default() -> Self {
         Self {
             hostname: None,
+            hostname_source: None,
             ip: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
             port: 0,
             public: false,
             is_v6: false,
+            geo: None,
         }
     }
 }
@@ -702,6 +1004,7 @@ def_user_data!(
         pub dst: NetworkInfo,
         pub community_id: String,
         pub connected: bool,
+        pub io_uring: bool,
     }
 );
 
@@ -718,6 +1021,32 @@ impl IocGetter for ConnectData {
     }
 }
 
+def_user_data!(
+    pub struct EncryptedDnsData {
+        pub socket: SocketInfo,
+        pub src: SockAddr,
+        pub dns_server: NetworkInfo,
+        pub community_id: String,
+        pub protocol: String,
+        pub connected: bool,
+    }
+);
+
+impl Scannable for EncryptedDnsData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+impl IocGetter for EncryptedDnsData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        let mut v = vec![self.exe.path.to_string_lossy()];
+        v.extend(self.dns_server.iocs());
+        v
+    }
+}
+
 def_user_data!(
     #[derive(Default)]
     pub struct DnsQueryData {
@@ -727,6 +1056,7 @@ def_user_data!(
         pub response: String,
         pub dns_server: NetworkInfo,
         pub community_id: String,
+        pub io_uring: bool,
         #[serde(skip)]
         #[getter(skip)]
         responses: Vec<String>,
@@ -798,6 +1128,7 @@ def_user_data!(
         pub community_id: String,
         pub data_entropy: f32,
         pub data_size: u64,
+        pub io_uring: bool,
     }
 );
 
@@ -816,6 +1147,86 @@ impl IocGetter for SendDataData {
     }
 }
 
+def_user_data!(
+    pub struct TlsClientHelloData {
+        pub socket: SocketInfo,
+        pub src: SockAddr,
+        pub dst: NetworkInfo,
+        pub community_id: String,
+        pub sni: Option<String>,
+        #[getter(skip)]
+        pub alpn: Vec<String>,
+        pub tls_version: String,
+        pub ja3: String,
+        pub ja4: String,
+        // the ClientHello was not entirely captured so
+        // fingerprints may be computed on partial data
+        pub partial: bool,
+        pub io_uring: bool,
+    }
+);
+
+impl Scannable for TlsClientHelloData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+impl IocGetter for TlsClientHelloData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        let mut v = vec![self.exe.path.to_string_lossy()];
+        v.extend(self.dst.iocs());
+        if let Some(sni) = self.sni.as_ref() {
+            v.push(sni.into());
+        }
+        v.push(self.ja3.as_str().into());
+        v.push(self.ja4.as_str().into());
+        v
+    }
+}
+
+def_user_data!(
+    pub struct HttpRequestData {
+        pub socket: SocketInfo,
+        pub src: SockAddr,
+        pub dst: NetworkInfo,
+        pub community_id: String,
+        pub method: String,
+        pub host: Option<String>,
+        pub path: String,
+        pub url: Option<String>,
+        pub user_agent: Option<String>,
+        pub version: String,
+        pub io_uring: bool,
+        #[serde(default)]
+        pub tls: bool,
+        #[serde(default)]
+        pub inbound: bool,
+    }
+);
+
+impl Scannable for HttpRequestData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+impl IocGetter for HttpRequestData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        let mut v = vec![self.exe.path.to_string_lossy()];
+        v.extend(self.dst.iocs());
+        if let Some(host) = self.host.as_ref() {
+            v.push(strip_port(host).into());
+        }
+        if let Some(url) = self.url.as_ref() {
+            v.push(url.into());
+        }
+        v
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, FieldGetter)]
 pub struct InitModuleData {
     pub ancestors: String,
@@ -840,9 +1251,106 @@ impl Scannable for InitModuleData {
     }
 }
 
+def_user_data!(
+    pub struct DeleteModuleData {
+        pub module_name: String,
+        #[serde(with = "u64_hex")]
+        pub flags: u64,
+        pub force: bool,
+        pub success: bool,
+    }
+);
+
+impl_std_iocs!(DeleteModuleData);
+
+impl Scannable for DeleteModuleData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+def_user_data!(
+    pub struct KexecLoadData {
+        pub syscall: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub kernel: Option<Hashes>,
+        pub cmdline: String,
+        pub nr_segments: u64,
+        #[serde(with = "u64_hex")]
+        pub flags: u64,
+        pub success: bool,
+    }
+);
+
+impl IocGetter for KexecLoadData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        let mut v = vec![self.exe.path.to_string_lossy()];
+        if let Some(h) = self.kernel.as_ref() {
+            v.extend(h.iocs())
+        }
+        v
+    }
+}
+
+impl Scannable for KexecLoadData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        let mut v = vec![Cow::Borrowed(&self.exe.path)];
+        if let Some(h) = self.kernel.as_ref() {
+            v.push(Cow::Borrowed(&h.path));
+        }
+        v
+    }
+}
+
+def_user_data!(
+    pub struct RebootData {
+        pub cmd: String,
+        #[serde(with = "u32_hex")]
+        pub magic1: u32,
+        #[serde(with = "u32_hex")]
+        pub magic2: u32,
+        pub valid_magic: bool,
+    }
+);
+
+impl_std_iocs!(RebootData);
+
+impl Scannable for RebootData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+def_user_data!(
+    pub struct SysctlWriteData {
+        pub name: String,
+        pub path: PathBuf,
+        pub value: String,
+        pub old_value: Option<String>,
+        pub success: bool,
+    }
+);
+
+impl IocGetter for SysctlWriteData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![self.exe.path.to_string_lossy(), self.value.as_str().into()]
+    }
+}
+
+impl Scannable for SysctlWriteData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
 def_user_data!(
     pub struct FileData {
         pub path: PathBuf,
+        pub io_uring: bool,
     }
 );
 
@@ -879,6 +1387,128 @@ impl Scannable for UnlinkData {
     }
 }
 
+def_user_data!(
+    pub struct MountData {
+        pub dev_name: String,
+        pub path: PathBuf,
+        pub fs_type: String,
+        #[serde(with = "u64_hex")]
+        pub flags: u64,
+        pub mnt_ns: u32,
+        pub success: bool,
+    }
+);
+
+impl IocGetter for MountData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![self.exe.path.to_string_lossy(), self.path.to_string_lossy()]
+    }
+}
+
+impl Scannable for MountData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+def_user_data!(
+    pub struct FileChmodData {
+        pub path: PathBuf,
+        #[serde(with = "u32_octal")]
+        pub old_mode: u32,
+        #[serde(with = "u32_octal")]
+        pub new_mode: u32,
+        pub became_executable: bool,
+    }
+);
+
+impl IocGetter for FileChmodData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![self.exe.path.to_string_lossy(), self.path.to_string_lossy()]
+    }
+}
+
+impl Scannable for FileChmodData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path), Cow::Borrowed(&self.path)]
+    }
+}
+
+def_user_data!(
+    pub struct FileChownData {
+        pub path: PathBuf,
+        pub old_uid: u32,
+        pub old_gid: u32,
+        // None if left unchanged
+        pub new_uid: Option<u32>,
+        // None if left unchanged
+        pub new_gid: Option<u32>,
+    }
+);
+
+impl IocGetter for FileChownData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![self.exe.path.to_string_lossy(), self.path.to_string_lossy()]
+    }
+}
+
+impl Scannable for FileChownData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path), Cow::Borrowed(&self.path)]
+    }
+}
+
+def_user_data!(
+    pub struct FileXattrData {
+        pub path: PathBuf,
+        pub name: String,
+        pub size: u64,
+        #[serde(with = "u32_hex")]
+        pub flags: u32,
+    }
+);
+
+impl IocGetter for FileXattrData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![self.exe.path.to_string_lossy(), self.path.to_string_lossy()]
+    }
+}
+
+impl Scannable for FileXattrData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path), Cow::Borrowed(&self.path)]
+    }
+}
+
+def_user_data!(
+    pub struct FileUtimesData {
+        pub path: PathBuf,
+        pub old_atime: Option<UtcDateTime>,
+        pub old_mtime: Option<UtcDateTime>,
+        // None if left unchanged
+        pub atime: Option<UtcDateTime>,
+        // None if left unchanged
+        pub mtime: Option<UtcDateTime>,
+    }
+);
+
+impl IocGetter for FileUtimesData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![self.exe.path.to_string_lossy(), self.path.to_string_lossy()]
+    }
+}
+
+impl Scannable for FileUtimesData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path), Cow::Borrowed(&self.path)]
+    }
+}
+
 def_user_data!(
     pub struct FileRenameData {
         pub old: PathBuf,
@@ -903,6 +1533,59 @@ impl Scannable for FileRenameData {
     }
 }
 
+def_user_data!(
+    pub struct FileLinkData {
+        pub link: PathBuf,
+        pub target: PathBuf,
+        pub target_uid: u32,
+        pub target_gid: u32,
+        #[serde(with = "u32_octal")]
+        pub target_mode: u32,
+    }
+);
+
+impl IocGetter for FileLinkData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![
+            self.exe.path.to_string_lossy(),
+            self.link.to_string_lossy(),
+            self.target.to_string_lossy(),
+        ]
+    }
+}
+
+impl Scannable for FileLinkData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path), Cow::Borrowed(&self.target)]
+    }
+}
+
+def_user_data!(
+    pub struct FileSymlinkData {
+        pub link: PathBuf,
+        // symlink content, may be relative to the link
+        pub target: PathBuf,
+    }
+);
+
+impl IocGetter for FileSymlinkData {
+    fn iocs(&mut self) -> Vec<Cow<'_, str>> {
+        vec![
+            self.exe.path.to_string_lossy(),
+            self.link.to_string_lossy(),
+            self.target.to_string_lossy(),
+        ]
+    }
+}
+
+impl Scannable for FileSymlinkData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
 #[derive(Debug, FieldGetter, Serialize, Deserialize)]
 pub struct BpfProgTypeInfo {
     pub id: u32,
@@ -957,6 +1640,12 @@ pub struct SocketInfo {
     #[serde(rename = "type")]
     pub ty: String,
     pub proto: String,
+    // inode of the network namespace
+    #[serde(default)]
+    pub netns: u32,
+    // egress interface name, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
 }
 
 impl From<net::SocketInfo> for SocketInfo {
@@ -965,6 +1654,8 @@ impl From<net::SocketInfo> for SocketInfo {
             domain: value.domain_to_string(),
             ty: value.type_to_string(),
             proto: value.proto_to_string(),
+            netns: value.netns,
+            interface: (!value.ifname.is_empty()).then(|| value.ifname.to_string()),
         }
     }
 }
@@ -1006,9 +1697,44 @@ impl IocGetter for BpfSocketFilterData {
     }
 }
 
+def_user_data!(
+    pub struct SocketCreateData {
+        pub socket: SocketInfo,
+    }
+);
+
+impl Scannable for SocketCreateData {
+    #[inline]
+    fn scannable_files(&self) -> Vec<Cow<'_, PathBuf>> {
+        vec![Cow::Borrowed(&self.exe.path)]
+    }
+}
+
+impl_std_iocs!(SocketCreateData);
+
 def_user_data!(
     pub struct ExitData {
         pub error_code: u64,
+        #[serde(default)]
+        pub exit_code: Option<i32>,
+        #[serde(default)]
+        pub signal: Option<String>,
+        #[serde(default)]
+        pub core_dumped: bool,
+        #[serde(default)]
+        pub lifetime_ms: u64,
+        #[serde(default)]
+        pub utime_ms: u64,
+        #[serde(default)]
+        pub stime_ms: u64,
+        #[serde(default)]
+        pub max_rss_kb: Option<u64>,
+        // connect events seen, only counted if connect events are enabled
+        #[serde(default)]
+        pub connections: u64,
+        // regular files written, counted in kernel whatever the file events enabled
+        #[serde(default)]
+        pub file_writes: u64,
     }
 );
 
@@ -1092,3 +1818,37 @@ impl From<&bpf_events::LossData> for LossData {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Mode {
+        #[serde(with = "u32_octal")]
+        mode: u32,
+    }
+
+    #[test]
+    fn test_u32_octal() {
+        let m = Mode { mode: 0o100755 };
+        let s = serde_json::to_string(&m).unwrap();
+        assert_eq!(s, r#"{"mode":"0o100755"}"#);
+        assert_eq!(serde_json::from_str::<Mode>(&s).unwrap(), m);
+
+        assert_eq!(
+            serde_json::to_string(&Mode { mode: 0o4755 }).unwrap(),
+            r#"{"mode":"0o4755"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Mode { mode: 0 }).unwrap(),
+            r#"{"mode":"0o0"}"#
+        );
+        // prefix is optional when parsing
+        assert_eq!(
+            serde_json::from_str::<Mode>(r#"{"mode":"644"}"#).unwrap(),
+            Mode { mode: 0o644 }
+        );
+        assert!(serde_json::from_str::<Mode>(r#"{"mode":"0o9"}"#).is_err());
+    }
+}
@@ -33,6 +33,11 @@ impl AgentEventInfo {
         let clk_tck = get_clk_tck()? as u64;
 
         let mnt = namespaces.0.get(&OsString::from("mnt")).unwrap();
+        let net = namespaces
+            .0
+            .get(&OsString::from("net"))
+            .map(|ns| ns.identifier as u32)
+            .unwrap_or_default();
 
         let comm_bytes = stat.comm.as_bytes();
         let mut comm = [0; COMM_SIZE];
@@ -54,10 +59,25 @@ impl AgentEventInfo {
             tg_uuid: ProcUuid::new(start_time, 0, status.tgid as u32),
             namespaces: Some(bpf_events::Namespaces {
                 mnt: mnt.identifier as u32,
+                net,
             }),
             comm,
             zombie: false,
             start_time,
+            caps: bpf_events::Capabilities {
+                effective: status.capeff,
+                permitted: status.capprm,
+                bounding: status.capbnd.unwrap_or_default(),
+            },
+            no_new_privs: status.nonewprivs.unwrap_or_default() == 1,
+            seccomp_mode: status.seccomp.unwrap_or_default(),
+            loginuid: p.loginuid().unwrap_or(u32::MAX),
+            sessionid: std::fs::read_to_string(format!("/proc/{}/sessionid", p.pid))
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(u32::MAX),
+            sid: stat.session,
+            tty: Default::default(),
         })
     }
 
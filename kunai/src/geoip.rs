@@ -0,0 +1,333 @@
+use std::{
+    fs, io,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use log::{info, warn};
+use maxminddb::{MaxMindDBError, Reader};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::events::GeoInfo;
+
+// how often we check if database files changed
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}: {1}")]
+    Open(PathBuf, MaxMindDBError),
+    #[error("{0}: {1}")]
+    Io(PathBuf, io::Error),
+    #[error("failed to spawn reload thread: {0}")]
+    Spawn(io::Error),
+}
+
+#[inline]
+fn is_empty(g: &GeoInfo) -> bool {
+    g.country.is_none() && g.asn.is_none() && g.as_org.is_none()
+}
+
+// fills the fields we don't know yet
+#[inline]
+fn merge(g: &mut GeoInfo, r: Record<'_>) {
+    if g.country.is_none() {
+        g.country = r.country.and_then(|c| c.iso_code).map(String::from);
+    }
+    if g.asn.is_none() {
+        g.asn = r.autonomous_system_number;
+    }
+    if g.as_org.is_none() {
+        g.as_org = r.autonomous_system_organization.map(String::from);
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Country<'a> {
+    iso_code: Option<&'a str>,
+}
+
+// subset of the fields found in GeoLite2 Country/City
+// and ASN databases (or compatible ones)
+#[derive(Debug, Deserialize)]
+struct Record<'a> {
+    #[serde(borrow)]
+    country: Option<Country<'a>>,
+    autonomous_system_number: Option<u32>,
+    autonomous_system_organization: Option<&'a str>,
+}
+
+// database file watched for modifications
+struct Database {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl Database {
+    fn open(path: &Path) -> Result<(Self, Reader<Vec<u8>>), Error> {
+        let modified = Self::modified(path)?;
+        let reader = Reader::open_readfile(path).map_err(|e| Error::Open(path.to_path_buf(), e))?;
+
+        Ok((
+            Self {
+                path: path.to_path_buf(),
+                modified,
+            },
+            reader,
+        ))
+    }
+
+    #[inline]
+    fn modified(path: &Path) -> Result<Option<SystemTime>, Error> {
+        fs::metadata(path)
+            .map(|m| m.modified().ok())
+            .map_err(|e| Error::Io(path.to_path_buf(), e))
+    }
+
+    // returns a new reader if the file has been modified. If the new file
+    // cannot be loaded we keep on using the one we already have.
+    fn reload_if_modified(&mut self) -> Option<Reader<Vec<u8>>> {
+        let modified = Self::modified(&self.path)
+            .inspect_err(|e| warn!("failed to check geoip database: {e}"))
+            .ok()?;
+
+        if modified == self.modified {
+            return None;
+        }
+
+        match Self::open(&self.path) {
+            Ok((db, reader)) => {
+                info!("reloaded geoip database: {}", self.path.to_string_lossy());
+                *self = db;
+                Some(reader)
+            }
+            Err(e) => {
+                warn!("failed to reload geoip database: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// Offline IP address enrichment from local MaxMind DB files. Database
+/// files are watched for modifications and reloaded in a background
+/// thread so that lookups never wait for it.
+pub struct GeoIp {
+    readers: Vec<Reader<Vec<u8>>>,
+    reloaded: Receiver<(usize, Reader<Vec<u8>>)>,
+}
+
+impl GeoIp {
+    /// Opens all the databases found at `paths`
+    pub fn open<P: AsRef<Path>>(paths: &[P]) -> Result<Self, Error> {
+        Self::open_with_interval(paths, RELOAD_CHECK_INTERVAL)
+    }
+
+    fn open_with_interval<P: AsRef<Path>>(paths: &[P], interval: Duration) -> Result<Self, Error> {
+        let (databases, readers): (Vec<_>, Vec<_>) = paths
+            .iter()
+            .map(|p| Database::open(p.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        let (sender, reloaded) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("kunai-geoip".into())
+            .spawn(move || Self::reloader(databases, sender, interval))
+            .map_err(Error::Spawn)?;
+
+        Ok(Self { readers, reloaded })
+    }
+
+    fn reloader(
+        mut databases: Vec<Database>,
+        reloaded: Sender<(usize, Reader<Vec<u8>>)>,
+        interval: Duration,
+    ) {
+        loop {
+            thread::sleep(interval);
+
+            for (i, db) in databases.iter_mut().enumerate() {
+                if let Some(reader) = db.reload_if_modified() {
+                    // GeoIp has been dropped
+                    if reloaded.send((i, reader)).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the information found about `ip` in the databases. Databases
+    /// are looked up in order and the first value found for a field is kept.
+    pub fn lookup(&mut self, ip: IpAddr) -> Option<GeoInfo> {
+        // swapping readers reloaded in background
+        while let Ok((i, reader)) = self.reloaded.try_recv() {
+            self.readers[i] = reader;
+        }
+
+        let mut out = GeoInfo::default();
+
+        for reader in self.readers.iter() {
+            if let Ok(r) = reader.lookup::<Record>(ip) {
+                merge(&mut out, r);
+            }
+        }
+
+        (!is_empty(&out)).then_some(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::*;
+
+    // encodes MaxMind DB data section control bytes
+    fn ctrl(ty: u8, size: usize, out: &mut Vec<u8>) {
+        assert!(size < 285);
+        let s = size.min(29) as u8;
+        if ty < 8 {
+            out.push(ty << 5 | s);
+        } else {
+            out.push(s);
+            out.push(ty - 7);
+        }
+        if size >= 29 {
+            out.push((size - 29) as u8);
+        }
+    }
+
+    fn string(s: &str, out: &mut Vec<u8>) {
+        ctrl(2, s.len(), out);
+        out.extend_from_slice(s.as_bytes());
+    }
+
+    fn uint(ty: u8, v: u64, out: &mut Vec<u8>) {
+        let b = v.to_be_bytes();
+        let b = &b[b.iter().take_while(|&&b| b == 0).count()..];
+        ctrl(ty, b.len(), out);
+        out.extend_from_slice(b);
+    }
+
+    // builds an IPv4 database where 128.0.0.0/1 has data and 0.0.0.0/1 has not
+    fn build_db(asn: u64, org: &str, country: &str) -> Vec<u8> {
+        let mut db = vec![];
+
+        // search tree: a single node with 24 bits records, left record
+        // is equal to node count (i.e. no data), right record points to
+        // data section offset 0 (node_count + 16 + 0)
+        db.extend_from_slice(&[0, 0, 1, 0, 0, 17]);
+        db.extend_from_slice(&[0; 16]);
+
+        // data section
+        ctrl(7, 3, &mut db);
+        string("autonomous_system_number", &mut db);
+        uint(6, asn, &mut db);
+        string("autonomous_system_organization", &mut db);
+        string(org, &mut db);
+        string("country", &mut db);
+        ctrl(7, 1, &mut db);
+        string("iso_code", &mut db);
+        string(country, &mut db);
+
+        // metadata
+        db.extend_from_slice(b"\xab\xcd\xefMaxMind.com");
+        ctrl(7, 9, &mut db);
+        string("binary_format_major_version", &mut db);
+        uint(5, 2, &mut db);
+        string("binary_format_minor_version", &mut db);
+        uint(5, 0, &mut db);
+        string("build_epoch", &mut db);
+        uint(9, 0, &mut db);
+        string("database_type", &mut db);
+        string("Kunai-Test", &mut db);
+        string("description", &mut db);
+        ctrl(7, 0, &mut db);
+        string("ip_version", &mut db);
+        uint(5, 4, &mut db);
+        string("languages", &mut db);
+        ctrl(11, 0, &mut db);
+        string("node_count", &mut db);
+        uint(6, 1, &mut db);
+        string("record_size", &mut db);
+        uint(5, 24, &mut db);
+
+        db
+    }
+
+    #[test]
+    fn test_geoip_lookup() {
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(&build_db(64496, "Example Org", "FR")).unwrap();
+
+        let mut g = GeoIp::open(&[f.path()]).unwrap();
+
+        assert_eq!(
+            g.lookup("203.0.113.1".parse().unwrap()),
+            Some(GeoInfo {
+                country: Some("FR".into()),
+                asn: Some(64496),
+                as_org: Some("Example Org".into()),
+            })
+        );
+        assert_eq!(g.lookup("10.0.0.1".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_geoip_reload() {
+        let f = tempfile::NamedTempFile::new().unwrap();
+        fs::write(f.path(), build_db(64496, "Example Org", "FR")).unwrap();
+
+        let (mut db, _) = Database::open(f.path()).unwrap();
+        assert!(db.reload_if_modified().is_none());
+
+        fs::write(f.path(), build_db(64511, "Other Org", "DE")).unwrap();
+        // make sure modification time is different
+        f.as_file()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert!(db.reload_if_modified().is_some());
+
+        // an invalid database does not replace the current one
+        fs::write(f.path(), b"not a database").unwrap();
+        f.as_file()
+            .set_modified(SystemTime::now() + Duration::from_secs(20))
+            .unwrap();
+        assert!(db.reload_if_modified().is_none());
+    }
+
+    #[test]
+    fn test_geoip_background_reload() {
+        let f = tempfile::NamedTempFile::new().unwrap();
+        fs::write(f.path(), build_db(64496, "Example Org", "FR")).unwrap();
+
+        let mut g = GeoIp::open_with_interval(&[f.path()], Duration::from_millis(10)).unwrap();
+        let ip = "203.0.113.1".parse().unwrap();
+        assert_eq!(g.lookup(ip).unwrap().asn, Some(64496));
+
+        fs::write(f.path(), build_db(64511, "Other Org", "DE")).unwrap();
+        f.as_file()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        while g.lookup(ip).unwrap().asn != Some(64511) {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(g.lookup(ip).unwrap().country, Some("DE".into()));
+    }
+
+    #[test]
+    fn test_geoip_missing_file() {
+        assert!(GeoIp::open(&["/nonexistent/kunai.mmdb"]).is_err());
+    }
+}
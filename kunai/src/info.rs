@@ -1,4 +1,4 @@
-use std::io;
+use std::{io, path::PathBuf};
 
 use chrono::{DateTime, Utc};
 use kunai_common::{
@@ -8,7 +8,7 @@ use kunai_common::{
 use thiserror::Error;
 
 use crate::{
-    containers::Container,
+    containers::{cri::CriInfo, docker::DockerInfo, Container},
     util::{
         account::{Group, User},
         get_clk_tck,
@@ -68,18 +68,17 @@ pub struct HostInfo {
 pub struct ContainerInfo {
     pub name: String,
     pub ty: Option<Container>,
+    pub cri: Option<CriInfo>,
+    pub docker: Option<DockerInfo>,
 }
 
 #[derive(Default, Debug, Clone)]
 pub struct TaskAdditionalInfo {
     pub user: Option<User>,
     pub group: Option<Group>,
-}
-
-impl TaskAdditionalInfo {
-    pub fn new(user: Option<User>, group: Option<Group>) -> Self {
-        Self { user, group }
-    }
+    pub lsm_label: Option<String>,
+    pub login_user: Option<User>,
+    pub session_leader_exe: Option<PathBuf>,
 }
 
 #[derive(Default, Debug, Clone)]
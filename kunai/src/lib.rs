@@ -8,7 +8,7 @@ use aya::{
 };
 use compat::Programs;
 use config::Config;
-use kunai_common::{config::BpfConfig, kernel, version::KernelVersion};
+use kunai_common::{bpf_events::Type, config::BpfConfig, kernel, version::KernelVersion};
 use log::{info, warn};
 use util::{page_shift, page_size};
 
@@ -16,9 +16,12 @@ pub mod cache;
 pub mod compat;
 pub mod config;
 pub mod containers;
+pub mod dns;
 pub mod events;
+pub mod geoip;
 pub mod info;
 pub mod ioc;
+pub mod uprobes;
 pub mod util;
 pub mod yara;
 
@@ -58,6 +61,38 @@ fn configure_probes(conf: &Config, programs: &mut Programs, target: KernelVersio
         .prio(0)
         .disable_if(!conf.harden);
 
+    // process memory access probes are optional, they only
+    // produce process_memory_access events
+    for p in [
+        "syscalls_sys_enter_process_vm_readv",
+        "syscalls_sys_enter_process_vm_writev",
+        "syscalls_sys_exit_process_vm_readv",
+        "syscalls_sys_exit_process_vm_writev",
+        "process_memory_security_file_open",
+        "process_memory_exit_ptrace_access_check",
+        "process_memory_enter_mem_read",
+        "process_memory_exit_mem_read",
+        "process_memory_enter_mem_write",
+        "process_memory_exit_mem_write",
+        "process_memory_mem_release",
+    ] {
+        programs
+            .expect_mut(p)
+            .disable_if(!conf.is_event_enabled(Type::ProcessMemoryAccess));
+    }
+
+    // proc_sys_write is static so we make sure it is there if we can
+    let proc_sys_write = sym.is_empty() || sym.contains("proc_sys_write");
+    if !proc_sys_write {
+        warn!("sysctl_write probes have been disabled: proc_sys_write symbol not found");
+    }
+
+    for p in ["sysctl_enter_proc_sys_write", "sysctl_exit_proc_sys_write"] {
+        programs
+            .expect_mut(p)
+            .disable_if(!proc_sys_write || !conf.is_event_enabled(Type::SysctlWrite));
+    }
+
     // Other probes
     programs.expect_mut("execve_security_bprm_check").prio(1);
 
@@ -77,6 +112,72 @@ fn configure_probes(conf: &Config, programs: &mut Programs, target: KernelVersio
         .expect_mut("syscalls_sys_exit_execveat")
         .max_kernel(kernel!(5, 9));
 
+    // failed executions are tracked at syscall level so we
+    // don't pay the cost of these probes if event is disabled
+    let execve_failed = conf.is_event_enabled(Type::ExecveFailed);
+    for p in [
+        "execve_failed_sys_enter_execve",
+        "execve_failed_sys_enter_execveat",
+        "execve_failed_sys_exit_execve",
+        "execve_failed_sys_exit_execveat",
+    ] {
+        programs.expect_mut(p).disable_if(!execve_failed);
+    }
+
+    // TLS libraries uprobes are opt-in and only report http_request events
+    let tls_uprobes = conf.tls_uprobes && conf.is_event_enabled(Type::HttpRequest);
+    for p in [
+        "tls_lib_enter_write",
+        "tls_lib_exit_write",
+        "tls_lib_enter_read",
+        "tls_lib_exit_read",
+        "tls_lib_enter_write_ex",
+        "tls_lib_enter_read_ex",
+        "tls_lib_security_socket_sendmsg",
+        "tls_lib_security_socket_recvmsg",
+    ] {
+        programs.expect_mut(p).disable_if(!tls_uprobes);
+    }
+
+    // shell uprobes are only attached if we want the event
+    for p in ["shell_exit_readline", "shell_exit_lib_readline"] {
+        programs
+            .expect_mut(p)
+            .disable_if(!conf.is_event_enabled(Type::ShellCommand));
+    }
+
+    // cgroup events are only used to keep container metadata up to date
+    let container_api = conf.cri_socket.is_some() || conf.docker_socket.is_some();
+    for p in ["enter_cgroup_mkdir", "enter_cgroup_rmdir"] {
+        programs.expect_mut(p).disable_if(!container_api);
+    }
+
+    // move_mount syscall (new mount API) exists since 5.2
+    for p in ["fs_security_move_mount", "fs_syscalls_sys_exit_move_mount"] {
+        programs.expect_mut(p).min_kernel(kernel!(5, 2));
+    }
+
+    // sock_recvmsg probes only catch DNS responses received through io_uring
+    // we don't want to pay the cost of the kretprobe if we don't need it
+    for p in ["net_dns_enter_sock_recvmsg", "net_dns_exit_sock_recvmsg"] {
+        programs
+            .expect_mut(p)
+            .disable_if(!conf.is_event_enabled(Type::DnsQuery));
+    }
+
+    // vfs_utimes does not exist before 5.10
+    programs
+        .expect_mut("fs_vfs_utimes")
+        .min_kernel(kernel!(5, 10));
+
+    // __sys_connect_file is used by io_uring since 5.5
+    programs
+        .expect_mut("net_enter_sys_connect_file")
+        .min_kernel(kernel!(5, 5));
+    programs
+        .expect_mut("net_exit_sys_connect_file")
+        .min_kernel(kernel!(5, 5));
+
     // bpf probes
     programs.expect_mut("entry_security_bpf_prog").prio(90);
     programs.expect_mut("exit_bpf_prog_load").prio(100);
@@ -181,6 +282,11 @@ pub fn load_and_attach_bpf<'a>(
 
         p.load(&btf)?;
 
+        // uprobes get attached when the libraries they target are found
+        if p.is_uprobe() {
+            continue;
+        }
+
         // this handles the very specific case where /proc/kallsyms
         // is not available to check if syscore_resume is present
         // In such case attach will fail with a SyscallError and
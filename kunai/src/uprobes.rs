@@ -0,0 +1,216 @@
+use std::{
+    collections::HashMap,
+    io,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+};
+
+use aya::{
+    programs::{ProgramError, UProbe},
+    Ebpf,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("unknown target: {0}")]
+    UnknownTarget(PathBuf),
+    #[error("missing program: {0}")]
+    MissingProgram(&'static str),
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Program(#[from] ProgramError),
+}
+
+/// Binaries and libraries we know how to hook into
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UprobeTarget {
+    OpenSsl,
+    GnuTls,
+    Bash,
+    // readline library, only hooked when mapped by bash
+    Readline,
+    // zsh line editor module
+    ZshZle,
+}
+
+impl UprobeTarget {
+    /// Returns the kind of target from its path
+    pub fn from_path<P: AsRef<Path>>(p: P) -> Option<Self> {
+        let p = p.as_ref();
+        let name = p.file_name()?.to_string_lossy();
+
+        if name.starts_with("libssl.so") {
+            Some(Self::OpenSsl)
+        } else if name.starts_with("libgnutls.so") {
+            Some(Self::GnuTls)
+        } else if name == "bash" {
+            Some(Self::Bash)
+        } else if name.starts_with("libreadline.so") {
+            Some(Self::Readline)
+        } else if name == "zle.so" && p.parent().is_some_and(|d| d.ends_with("zsh")) {
+            Some(Self::ZshZle)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub const fn is_tls_library(&self) -> bool {
+        matches!(self, Self::OpenSsl | Self::GnuTls)
+    }
+
+    #[inline]
+    pub const fn is_shell(&self) -> bool {
+        matches!(self, Self::Bash | Self::Readline | Self::ZshZle)
+    }
+
+    /// Returns the programs to attach and the function they must be attached to
+    #[inline]
+    const fn attach_points(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::OpenSsl => &[
+                ("tls_lib_enter_write", "SSL_write"),
+                ("tls_lib_exit_write", "SSL_write"),
+                ("tls_lib_enter_read", "SSL_read"),
+                ("tls_lib_exit_read", "SSL_read"),
+            ],
+            Self::GnuTls => &[
+                ("tls_lib_enter_write", "gnutls_record_send"),
+                ("tls_lib_exit_write", "gnutls_record_send"),
+                ("tls_lib_enter_read", "gnutls_record_recv"),
+                ("tls_lib_exit_read", "gnutls_record_recv"),
+            ],
+            // bash may use a shared readline library, see optional attach points
+            Self::Bash => &[],
+            Self::Readline => &[("shell_exit_lib_readline", "readline")],
+            Self::ZshZle => &[("shell_exit_readline", "zleread")],
+        }
+    }
+
+    /// Same as [UprobeTarget::attach_points] but for functions
+    /// which may not exist in all the versions of the target
+    #[inline]
+    const fn optional_attach_points(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            // available since OpenSSL 1.1.1
+            Self::OpenSsl => &[
+                ("tls_lib_enter_write_ex", "SSL_write_ex"),
+                ("tls_lib_exit_write", "SSL_write_ex"),
+                ("tls_lib_enter_read_ex", "SSL_read_ex"),
+                ("tls_lib_exit_read", "SSL_read_ex"),
+            ],
+            // only when readline is statically linked into bash
+            Self::Bash => &[("shell_exit_readline", "readline")],
+            _ => &[],
+        }
+    }
+}
+
+/// Keeps track of the binaries and libraries uprobes are attached to.
+/// Targets are identified by device and inode so that the same file
+/// found through different paths (i.e. from different mount namespaces)
+/// is hooked only once.
+#[derive(Debug, Default)]
+pub struct Uprobes {
+    attached: HashMap<(u64, u64), (UprobeTarget, PathBuf)>,
+}
+
+impl Uprobes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attaches uprobes to the target at `path`. It returns
+    /// `false` if probes were already attached to that target.
+    pub fn attach<P: AsRef<Path>>(&mut self, bpf: &mut Ebpf, path: P) -> Result<bool, Error> {
+        let path = path.as_ref();
+        let target = UprobeTarget::from_path(path)
+            .ok_or_else(|| Error::UnknownTarget(path.to_path_buf()))?;
+
+        let meta = path.metadata()?;
+        let key = (meta.dev(), meta.ino());
+
+        if self.attached.contains_key(&key) {
+            return Ok(false);
+        }
+
+        Self::attach_target(bpf, target, path)?;
+        self.attached.insert(key, (target, path.to_path_buf()));
+
+        Ok(true)
+    }
+
+    /// Attaches uprobes again to all the targets we know about.
+    /// This must be used after eBPF programs have been reloaded.
+    /// Targets which cannot be attached to anymore are forgotten.
+    pub fn reattach(&mut self, bpf: &mut Ebpf) -> Vec<(PathBuf, Error)> {
+        let mut errors = vec![];
+        self.attached.retain(
+            |_, (target, path)| match Self::attach_target(bpf, *target, path) {
+                Ok(_) => true,
+                Err(e) => {
+                    errors.push((path.clone(), e));
+                    false
+                }
+            },
+        );
+        errors
+    }
+
+    fn attach_target(bpf: &mut Ebpf, target: UprobeTarget, path: &Path) -> Result<(), Error> {
+        for &(name, func) in target.attach_points() {
+            let p: &mut UProbe = bpf
+                .program_mut(name)
+                .ok_or(Error::MissingProgram(name))?
+                .try_into()?;
+            p.attach(Some(func), 0, path, None)?;
+        }
+
+        for &(name, func) in target.optional_attach_points() {
+            let p: &mut UProbe = bpf
+                .program_mut(name)
+                .ok_or(Error::MissingProgram(name))?
+                .try_into()?;
+            // symbol is missing in older versions
+            let _ = p.attach(Some(func), 0, path, None);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_uprobe_target_from_path() {
+        assert_eq!(
+            UprobeTarget::from_path("/usr/lib/x86_64-linux-gnu/libssl.so.3"),
+            Some(UprobeTarget::OpenSsl)
+        );
+        assert_eq!(
+            UprobeTarget::from_path("/usr/lib64/libgnutls.so.30.37.1"),
+            Some(UprobeTarget::GnuTls)
+        );
+        assert_eq!(UprobeTarget::from_path("/usr/lib64/libcrypto.so.3"), None);
+        assert_eq!(UprobeTarget::from_path("/usr/lib64/libssl3.so"), None);
+
+        assert_eq!(
+            UprobeTarget::from_path("/usr/bin/bash"),
+            Some(UprobeTarget::Bash)
+        );
+        assert_eq!(
+            UprobeTarget::from_path("/usr/lib/x86_64-linux-gnu/zsh/5.9/zsh/zle.so"),
+            Some(UprobeTarget::ZshZle)
+        );
+        assert_eq!(
+            UprobeTarget::from_path("/usr/lib64/libreadline.so.8"),
+            Some(UprobeTarget::Readline)
+        );
+        assert_eq!(UprobeTarget::from_path("/usr/lib/zle.so"), None);
+        assert_eq!(UprobeTarget::from_path("/usr/bin/bashbug"), None);
+    }
+}
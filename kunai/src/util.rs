@@ -4,12 +4,22 @@ use libc::{clock_gettime, rlimit, timespec, CLOCK_MONOTONIC};
 use md5::{Digest, Md5};
 use sha1::Sha1;
 use sha2::{Sha256, Sha512};
-use std::{fs, io, net::IpAddr};
+use std::{
+    fs,
+    io::{self, Read},
+    net::IpAddr,
+};
 
 pub mod account;
 pub mod bpf;
 pub mod elf;
+pub mod http;
+pub mod lsm;
 pub mod namespace;
+pub mod netns;
+pub mod redact;
+pub mod sysctl;
+pub mod tls;
 pub mod uname;
 pub mod uptime;
 
@@ -157,6 +167,70 @@ pub fn is_bpf_lsm_enabled() -> Result<bool, io::Error> {
         .any(|s| s == "bpf"))
 }
 
+/// Reads the LSM label (SELinux context, AppArmor profile ...) of a task.
+/// Reading is done relative to the already opened `/proc/<pid>` directory
+/// so a recycled pid cannot be read in place of the process. Returns
+/// `None` if no LSM exposes a label or if the task is gone.
+#[inline]
+pub fn read_lsm_label(p: &procfs::process::Process) -> Option<String> {
+    let mut label = String::new();
+    p.open_relative("attr/current")
+        .ok()?
+        .read_to_string(&mut label)
+        .ok()?;
+    let label = label.trim_end_matches(['\0', '\n']);
+    // some LSMs are present but don't label tasks
+    if label.is_empty() {
+        return None;
+    }
+    Some(label.into())
+}
+
+/// Returns the name of the errors execve can fail with
+pub fn execve_errno_name(errno: i32) -> Option<&'static str> {
+    let name = match errno {
+        libc::E2BIG => "E2BIG",
+        libc::EACCES => "EACCES",
+        libc::EAGAIN => "EAGAIN",
+        libc::EFAULT => "EFAULT",
+        libc::EINVAL => "EINVAL",
+        libc::EIO => "EIO",
+        libc::EISDIR => "EISDIR",
+        libc::ELIBBAD => "ELIBBAD",
+        libc::ELOOP => "ELOOP",
+        libc::EMFILE => "EMFILE",
+        libc::ENAMETOOLONG => "ENAMETOOLONG",
+        libc::ENFILE => "ENFILE",
+        libc::ENOENT => "ENOENT",
+        libc::ENOEXEC => "ENOEXEC",
+        libc::ENOMEM => "ENOMEM",
+        libc::ENOTDIR => "ENOTDIR",
+        libc::EPERM => "EPERM",
+        libc::ETXTBSY => "ETXTBSY",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Reverts zsh metafication of a string. Zsh escapes NUL and
+/// bytes in 0x83..=0xa2 internally by prefixing them with a
+/// Meta (0x83) byte and xoring them with 32.
+pub fn zsh_unmetafy(b: &[u8]) -> Vec<u8> {
+    const META: u8 = 0x83;
+    let mut out = Vec::with_capacity(b.len());
+    let mut it = b.iter();
+    while let Some(&c) = it.next() {
+        if c == META {
+            if let Some(&n) = it.next() {
+                out.push(n ^ 32);
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
 #[cfg(test)]
 mod test {
     use crate::util::*;
@@ -166,4 +240,20 @@ mod test {
         println!("PAGE_SIZE: {}", page_size().unwrap());
         println!("PAGE_SHIFT: {}", page_shift().unwrap());
     }
+
+    #[test]
+    fn test_execve_errno_name() {
+        assert_eq!(execve_errno_name(libc::ENOEXEC), Some("ENOEXEC"));
+        assert_eq!(execve_errno_name(libc::EACCES), Some("EACCES"));
+        assert_eq!(execve_errno_name(libc::EADDRINUSE), None);
+    }
+
+    #[test]
+    fn test_zsh_unmetafy() {
+        assert_eq!(zsh_unmetafy(b"echo test"), b"echo test");
+        // à is 0xc3 0xa0, zsh metafies NUL and bytes in 0x83..=0xa2
+        // so 0xa0 is stored as Meta followed by 0xa0 ^ 32
+        assert_eq!(zsh_unmetafy(b"echo \xc3\x83\x80"), "echo à".as_bytes());
+        assert_eq!(zsh_unmetafy(b"a\x83\x20b"), b"a\0b");
+    }
 }
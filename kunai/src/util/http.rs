@@ -0,0 +1,182 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("missing request line")]
+    MissingRequestLine,
+    #[error("malformed request line")]
+    MalformedRequestLine,
+    #[error("not an HTTP/1.x request")]
+    NotHttp1,
+}
+
+/// Metadata of an HTTP/1.x request
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    // request target as found in request line
+    pub target: String,
+    pub version: String,
+    pub host: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl Request {
+    /// Parses the request line and headers from the beginning of an
+    /// HTTP/1.x request. Headers may be truncated, in which case only
+    /// the complete header lines are taken into account.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let text = String::from_utf8_lossy(data);
+        let mut lines = text.split("\r\n");
+
+        let line = lines.next().ok_or(Error::MissingRequestLine)?;
+        let mut parts = line.split(' ');
+        let (Some(method), Some(target), Some(version), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(Error::MalformedRequestLine);
+        };
+
+        if !version.starts_with("HTTP/1.") {
+            return Err(Error::NotHttp1);
+        }
+
+        let mut req = Request {
+            method: method.into(),
+            target: target.into(),
+            version: version.into(),
+            ..Default::default()
+        };
+
+        // the last line may be truncated so we skip it unless
+        // we reached the end of the headers
+        let lines: Vec<&str> = lines.take_while(|l| !l.is_empty()).collect();
+        let complete = text.contains("\r\n\r\n");
+        let n = if complete {
+            lines.len()
+        } else {
+            lines.len().saturating_sub(1)
+        };
+
+        for l in lines.iter().take(n) {
+            let Some((name, value)) = l.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            if name.eq_ignore_ascii_case("host") {
+                req.host = Some(value.into());
+            } else if name.eq_ignore_ascii_case("user-agent") {
+                req.user_agent = Some(value.into());
+            }
+        }
+
+        Ok(req)
+    }
+
+    /// Returns the path part of the request target
+    pub fn path(&self) -> &str {
+        match self.target.split_once("://") {
+            // absolute-form (i.e. when talking to a proxy)
+            Some((_, rest)) => rest.find('/').map(|i| &rest[i..]).unwrap_or("/"),
+            None => &self.target,
+        }
+    }
+
+    /// Returns the host of the request, taken from target
+    /// if in absolute-form or from Host header otherwise
+    pub fn host(&self) -> Option<&str> {
+        if let Some((_, rest)) = self.target.split_once("://") {
+            return rest.split('/').next();
+        }
+        self.host.as_deref()
+    }
+
+    /// Returns the URL requested, `default_host` is used if the request
+    /// has no host information and `tls` tells if the request got sent
+    /// over TLS. CONNECT requests have no URL.
+    pub fn url(&self, default_host: &str, tls: bool) -> Option<String> {
+        if self.method == "CONNECT" {
+            return None;
+        }
+
+        if self.target.contains("://") {
+            return Some(self.target.clone());
+        }
+
+        let scheme = if tls { "https" } else { "http" };
+        let host = self.host().unwrap_or(default_host);
+        Some(format!("{scheme}://{host}{}", self.path()))
+    }
+}
+
+/// Removes the port from an host (i.e. Host header value)
+pub fn strip_port(host: &str) -> &str {
+    // IPv6 literal
+    if let Some(rest) = host.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
+    }
+    host.rsplit_once(':').map(|(h, _)| h).unwrap_or(host)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let req = Request::parse(
+            b"GET /install.sh?v=1 HTTP/1.1\r\nHost: example.com:8080\r\nUser-Agent: curl/8.5.0\r\nAccept: */*\r\n\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.path(), "/install.sh?v=1");
+        assert_eq!(req.host(), Some("example.com:8080"));
+        assert_eq!(req.user_agent.as_deref(), Some("curl/8.5.0"));
+        assert_eq!(
+            req.url("127.0.0.1", false).as_deref(),
+            Some("http://example.com:8080/install.sh?v=1")
+        );
+        assert_eq!(
+            req.url("127.0.0.1", true).as_deref(),
+            Some("https://example.com:8080/install.sh?v=1")
+        );
+        assert_eq!(strip_port(req.host().unwrap()), "example.com");
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        // truncated User-Agent header must be ignored
+        let req = Request::parse(b"POST /api HTTP/1.0\r\nHost: example.com\r\nUser-Ag").unwrap();
+        assert_eq!(req.host.as_deref(), Some("example.com"));
+        assert_eq!(req.user_agent, None);
+    }
+
+    #[test]
+    fn test_absolute_form() {
+        let req = Request::parse(b"GET http://example.com/a HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(req.path(), "/a");
+        assert_eq!(req.host(), Some("example.com"));
+        assert_eq!(
+            req.url("10.0.0.1", true).as_deref(),
+            Some("http://example.com/a")
+        );
+
+        let req = Request::parse(b"CONNECT example.com:443 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(req.url("10.0.0.1", true), None);
+        assert_eq!(strip_port("[::1]:8080"), "::1");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Request::parse(b"GET /\r\n").unwrap_err(),
+            Error::MalformedRequestLine
+        );
+        assert_eq!(
+            Request::parse(b"GET / HTTP/2.0\r\n").unwrap_err(),
+            Error::NotHttp1
+        );
+    }
+}
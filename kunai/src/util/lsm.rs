@@ -0,0 +1,71 @@
+//! Non blocking reading of processes' LSM labels
+
+use std::{
+    io,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+use crate::info::ProcKey;
+
+use super::read_lsm_label;
+
+/// Reads the LSM labels of processes in a background thread so that
+/// the caller never waits on procfs. A label is returned only if the
+/// process read is the one identified by the requested key.
+pub struct LabelReader {
+    requests: Sender<(i32, ProcKey)>,
+    responses: Receiver<(ProcKey, Option<String>)>,
+}
+
+impl LabelReader {
+    /// Creates a new reader, `None` is returned if no
+    /// LSM exposes task labels on the system.
+    pub fn new() -> Result<Option<Self>, io::Error> {
+        if procfs::process::Process::myself()
+            .ok()
+            .and_then(|p| read_lsm_label(&p))
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let (requests, worker_requests) = mpsc::channel();
+        let (worker_responses, responses) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("kunai-lsm".into())
+            .spawn(move || Self::worker(worker_requests, worker_responses))?;
+
+        Ok(Some(Self {
+            requests,
+            responses,
+        }))
+    }
+
+    fn worker(requests: Receiver<(i32, ProcKey)>, responses: Sender<(ProcKey, Option<String>)>) {
+        while let Ok((pid, pk)) = requests.recv() {
+            // the process may already be gone or its pid recycled
+            let label = procfs::process::Process::new(pid)
+                .ok()
+                .filter(|p| ProcKey::try_from(p).is_ok_and(|k| k == pk))
+                .and_then(|p| read_lsm_label(&p));
+
+            if responses.send((pk, label)).is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Asks for the label of process `pid` identified by `pk` to be read
+    #[inline]
+    pub fn request(&self, pid: i32, pk: ProcKey) {
+        let _ = self.requests.send((pid, pk));
+    }
+
+    /// Returns the labels read so far without blocking
+    #[inline]
+    pub fn responses(&self) -> impl Iterator<Item = (ProcKey, Option<String>)> + '_ {
+        self.responses.try_iter()
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    net::{IpAddr, Ipv6Addr},
+    os::unix::fs::MetadataExt,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use lru_st::collections::LruHashMap;
+
+// interface addresses may change (DHCP, container restart ...)
+// so we refresh them periodically
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Returns the inode of the network namespace of process `pid`
+#[inline]
+pub fn net_ns_inode(pid: i32) -> Result<u32, io::Error> {
+    Ok(fs::metadata(format!("/proc/{pid}/ns/net"))?.ino() as u32)
+}
+
+/// Parses the content of /proc/net/fib_trie and returns
+/// the IPv4 addresses assigned to local interfaces
+pub fn parse_fib_trie_local(fib_trie: &str) -> HashSet<IpAddr> {
+    let mut out = HashSet::new();
+    let mut last = None;
+
+    for line in fib_trie.lines() {
+        let line = line.trim_start();
+        if let Some(ip) = line.strip_prefix("|-- ") {
+            last = ip.parse::<IpAddr>().ok();
+        } else if line.starts_with("/32 host LOCAL") {
+            if let Some(ip) = last.take() {
+                out.insert(ip);
+            }
+        }
+    }
+
+    out
+}
+
+/// Parses the content of /proc/net/if_inet6 and returns
+/// the IPv6 addresses assigned to local interfaces
+pub fn parse_if_inet6(if_inet6: &str) -> HashSet<IpAddr> {
+    if_inet6
+        .lines()
+        .filter_map(|l| l.split_whitespace().next())
+        .filter_map(|a| u128::from_str_radix(a, 16).ok())
+        .map(|a| IpAddr::V6(Ipv6Addr::from(a)))
+        .collect()
+}
+
+/// Keeps track of the addresses assigned to the interfaces of
+/// network namespaces. Those are found through the procfs entries
+/// of a process living in the namespace. Addresses are read, and
+/// refreshed once outdated, in a background thread so that the
+/// caller never waits.
+pub struct LocalAddrs {
+    requests: Sender<(u32, i32)>,
+    responses: Receiver<(u32, Option<HashSet<IpAddr>>)>,
+    namespaces: LruHashMap<u32, (Instant, HashSet<IpAddr>)>,
+    pending: HashSet<u32>,
+}
+
+impl LocalAddrs {
+    pub fn with_max_entries(max_entries: usize) -> Result<Self, io::Error> {
+        let (requests, worker_requests) = mpsc::channel();
+        let (worker_responses, responses) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("kunai-netns".into())
+            .spawn(move || Self::worker(worker_requests, worker_responses))?;
+
+        Ok(Self {
+            requests,
+            responses,
+            namespaces: LruHashMap::with_max_entries(max_entries),
+            pending: HashSet::new(),
+        })
+    }
+
+    fn worker(requests: Receiver<(u32, i32)>, responses: Sender<(u32, Option<HashSet<IpAddr>>)>) {
+        while let Ok((netns, pid)) = requests.recv() {
+            if responses
+                .send((netns, Self::read_netns_addrs(netns, pid)))
+                .is_err()
+            {
+                break;
+            }
+        }
+    }
+
+    fn read_addrs(pid: i32) -> Result<HashSet<IpAddr>, io::Error> {
+        let mut addrs =
+            parse_fib_trie_local(&fs::read_to_string(format!("/proc/{pid}/net/fib_trie"))?);
+        // if_inet6 does not exist if IPv6 is disabled
+        if let Ok(s) = fs::read_to_string(format!("/proc/{pid}/net/if_inet6")) {
+            addrs.extend(parse_if_inet6(&s));
+        }
+        Ok(addrs)
+    }
+
+    #[inline]
+    fn read_netns_addrs(netns: u32, pid: i32) -> Option<HashSet<IpAddr>> {
+        // the process might have exited or switched namespace
+        if !net_ns_inode(pid).is_ok_and(|ns| ns == netns) {
+            return None;
+        }
+        Self::read_addrs(pid).ok()
+    }
+
+    // processes all the refreshed addresses available without blocking
+    fn update(&mut self) {
+        while let Ok((netns, addrs)) = self.responses.try_recv() {
+            self.pending.remove(&netns);
+            if let Some(addrs) = addrs {
+                self.namespaces.insert(netns, (Instant::now(), addrs));
+            }
+        }
+    }
+
+    /// Returns true if `ip` is assigned to an interface of network
+    /// namespace `netns` and `None` if namespace's addresses are not
+    /// known yet. Process `pid` is used to retrieve namespace's
+    /// addresses if they are unknown or outdated.
+    pub fn is_local(&mut self, netns: u32, pid: i32, ip: IpAddr) -> Option<bool> {
+        self.update();
+
+        // outdated addresses are used until refreshed ones are received
+        let outdated = self
+            .namespaces
+            .get(&netns)
+            .map(|(t, _)| t.elapsed() > REFRESH_INTERVAL)
+            .unwrap_or(true);
+
+        if outdated && !self.pending.contains(&netns) && self.requests.send((netns, pid)).is_ok() {
+            self.pending.insert(netns);
+        }
+
+        self.namespaces
+            .get(&netns)
+            .map(|(_, addrs)| addrs.contains(&ip))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_fib_trie_local() {
+        let fib_trie = r#"Main:
+  +-- 0.0.0.0/0 3 0 5
+     |-- 0.0.0.0
+        /0 universe UNICAST
+     +-- 127.0.0.0/8 2 0 2
+        +-- 127.0.0.0/31 1 0 0
+           |-- 127.0.0.0
+              /8 host LOCAL
+           |-- 127.0.0.1
+              /32 host LOCAL
+        |-- 127.255.255.255
+           /32 link BROADCAST
+     +-- 203.0.113.0/24 2 0 2
+        |-- 203.0.113.0
+           /24 link UNICAST
+        |-- 203.0.113.7
+           /32 host LOCAL
+Local:
+  +-- 0.0.0.0/0 3 0 5
+     |-- 203.0.113.7
+        /32 host LOCAL
+"#;
+        let addrs = parse_fib_trie_local(fib_trie);
+        assert_eq!(addrs.len(), 2);
+        assert!(addrs.contains(&"127.0.0.1".parse().unwrap()));
+        assert!(addrs.contains(&"203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_parse_if_inet6() {
+        let if_inet6 = "00000000000000000000000000000001 01 80 10 80       lo
+2001067c2564a17000000000000000a1 02 40 00 00     eth0
+";
+        let addrs = parse_if_inet6(if_inet6);
+        assert_eq!(addrs.len(), 2);
+        assert!(addrs.contains(&"::1".parse().unwrap()));
+        assert!(addrs.contains(&"2001:67c:2564:a170::a1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_local_addrs() {
+        let pid = std::process::id() as i32;
+        let Ok(netns) = net_ns_inode(pid) else {
+            return;
+        };
+
+        let mut l = LocalAddrs::with_max_entries(8).unwrap();
+        let localhost = "127.0.0.1".parse().unwrap();
+
+        // addresses are read in background, first lookup cannot tell
+        assert_eq!(l.is_local(netns, pid, localhost), None);
+
+        let start = Instant::now();
+        while l.is_local(netns, pid, localhost).is_none() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(l.is_local(netns, pid, localhost), Some(true));
+        assert_eq!(
+            l.is_local(netns, pid, "192.0.2.1".parse().unwrap()),
+            Some(false)
+        );
+        // namespace mismatch, nothing can be read
+        assert_eq!(l.is_local(netns.wrapping_add(1), pid, localhost), None);
+    }
+}
@@ -0,0 +1,97 @@
+use std::{collections::HashMap, io, path::Path};
+
+use aya::util::kernel_symbols;
+
+/// Returns the address to name mapping of the sysctl
+/// handlers (proc_do* functions) exported by the kernel
+pub fn handlers() -> Result<HashMap<u64, String>, io::Error> {
+    Ok(kernel_symbols()?
+        .into_iter()
+        .filter(|(_, name)| name.starts_with("proc_do"))
+        .collect())
+}
+
+/// Converts a /proc/sys path into a sysctl name (i.e. kernel.core_pattern)
+pub fn name_from_path<P: AsRef<Path>>(path: P) -> String {
+    let path = path.as_ref().to_string_lossy();
+    match path.strip_prefix("/proc/sys/") {
+        Some(rel) => rel.replace('/', "."),
+        None => path.into(),
+    }
+}
+
+#[inline]
+fn join<T: ToString>(values: impl Iterator<Item = T>) -> String {
+    values
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join("\t")
+}
+
+/// Decodes raw sysctl data according to the name of the handler
+/// used by the kernel to read/write it. Values are formatted the
+/// way they are read from /proc/sys. Returns `None` if the handler
+/// is not known or if data cannot be decoded.
+pub fn decode_value(handler: &str, data: &[u8]) -> Option<String> {
+    if data.is_empty() {
+        return None;
+    }
+
+    let value = match handler {
+        "proc_dostring" | "proc_dostring_coredump" => {
+            let s = data.split(|&b| b == 0).next().unwrap_or_default();
+            String::from_utf8_lossy(s).into()
+        }
+        "proc_dobool" => u8::from(data[0] != 0).to_string(),
+        "proc_dou8vec_minmax" => join(data.iter()),
+        "proc_dointvec" | "proc_dointvec_minmax" => join(
+            data.chunks_exact(4)
+                .map(|c| i32::from_ne_bytes(c.try_into().unwrap())),
+        ),
+        "proc_douintvec" | "proc_douintvec_minmax" => join(
+            data.chunks_exact(4)
+                .map(|c| u32::from_ne_bytes(c.try_into().unwrap())),
+        ),
+        "proc_doulongvec_minmax" => join(
+            data.chunks_exact(8)
+                .map(|c| u64::from_ne_bytes(c.try_into().unwrap())),
+        ),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_name_from_path() {
+        assert_eq!(
+            name_from_path("/proc/sys/kernel/randomize_va_space"),
+            "kernel.randomize_va_space"
+        );
+        assert_eq!(name_from_path("/some/path"), "/some/path");
+    }
+
+    #[test]
+    fn test_decode_value() {
+        assert_eq!(
+            decode_value("proc_dostring", b"|/usr/lib/systemd/systemd-coredump\0\0\0"),
+            Some("|/usr/lib/systemd/systemd-coredump".into())
+        );
+        assert_eq!(
+            decode_value("proc_dointvec", &2i32.to_ne_bytes()),
+            Some("2".into())
+        );
+        let mut v = (-1i32).to_ne_bytes().to_vec();
+        v.extend_from_slice(&4i32.to_ne_bytes());
+        assert_eq!(
+            decode_value("proc_dointvec_minmax", &v),
+            Some("-1\t4".into())
+        );
+        assert_eq!(decode_value("proc_dobool", &[1]), Some("1".into()));
+        assert_eq!(decode_value("proc_dointvec_jiffies", &[1, 0, 0, 0]), None);
+    }
+}
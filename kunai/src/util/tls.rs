@@ -0,0 +1,546 @@
+use std::fmt::Write;
+
+use thiserror::Error;
+
+use super::{md5_data, sha256_data};
+
+const CONTENT_TYPE_HANDSHAKE: u8 = 0x16;
+const HANDSHAKE_CLIENT_HELLO: u8 = 0x01;
+
+const EXT_SERVER_NAME: u16 = 0x0000;
+const EXT_SUPPORTED_GROUPS: u16 = 0x000a;
+const EXT_EC_POINT_FORMATS: u16 = 0x000b;
+const EXT_SIGNATURE_ALGORITHMS: u16 = 0x000d;
+const EXT_ALPN: u16 = 0x0010;
+const EXT_SUPPORTED_VERSIONS: u16 = 0x002b;
+
+// SNI name type for DNS hostnames
+const SERVER_NAME_HOST_NAME: u8 = 0x00;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("not a tls handshake record")]
+    NotHandshake,
+    #[error("not a client hello")]
+    NotClientHello,
+    #[error("unexpected end of data")]
+    Truncated,
+}
+
+/// Minimal big endian reader over a byte slice
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if n > self.data.len() {
+            return Err(Error::Truncated);
+        }
+        let (b, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(b)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u24(&mut self) -> Result<usize, Error> {
+        let b = self.bytes(3)?;
+        Ok(u32::from_be_bytes([0, b[0], b[1], b[2]]) as usize)
+    }
+
+    /// Returns a reader over a vector prefixed by a u8 length
+    fn vec_u8(&mut self) -> Result<Reader<'a>, Error> {
+        let len = self.u8()? as usize;
+        Ok(Reader::new(self.bytes(len)?))
+    }
+
+    /// Returns a reader over a vector prefixed by a u16 length
+    fn vec_u16(&mut self) -> Result<Reader<'a>, Error> {
+        let len = self.u16()? as usize;
+        Ok(Reader::new(self.bytes(len)?))
+    }
+
+    fn u16_list(mut self) -> Result<Vec<u16>, Error> {
+        let mut v = Vec::with_capacity(self.data.len() / 2);
+        while !self.is_empty() {
+            v.push(self.u16()?);
+        }
+        Ok(v)
+    }
+}
+
+/// Returns `true` if `v` is a GREASE value (RFC 8701)
+#[inline]
+fn is_grease(v: u16) -> bool {
+    v & 0x0f0f == 0x0a0a && v >> 8 == v & 0xff
+}
+
+/// Reassembles the handshake message found at the beginning of a TLS
+/// stream from the records it spans. The boolean returned is `true` if
+/// the message is incomplete because data was cut by the capture.
+fn handshake_message(data: &[u8]) -> Result<(Vec<u8>, bool), Error> {
+    let mut r = Reader::new(data);
+    let mut msg = vec![];
+
+    loop {
+        // the capture may end in the middle of a record header
+        let Ok(header) = r.bytes(5) else {
+            break;
+        };
+
+        if header[0] != CONTENT_TYPE_HANDSHAKE {
+            if msg.is_empty() {
+                return Err(Error::NotHandshake);
+            }
+            break;
+        }
+
+        let record_len = u16::from_be_bytes([header[3], header[4]]) as usize;
+        // last record may be cut by the capture
+        msg.extend_from_slice(r.bytes(record_len.min(r.data.len()))?);
+
+        if msg.len() >= 4 {
+            if msg[0] != HANDSHAKE_CLIENT_HELLO {
+                return Err(Error::NotClientHello);
+            }
+
+            let len = u32::from_be_bytes([0, msg[1], msg[2], msg[3]]) as usize + 4;
+            if msg.len() >= len {
+                msg.truncate(len);
+                return Ok((msg, false));
+            }
+        }
+    }
+
+    if msg.is_empty() {
+        return Err(Error::Truncated);
+    }
+
+    Ok((msg, true))
+}
+
+/// Information extracted from a TLS ClientHello
+#[derive(Debug, Default, Clone)]
+pub struct ClientHello {
+    pub legacy_version: u16,
+    pub supported_versions: Vec<u16>,
+    pub ciphers: Vec<u16>,
+    // extension types in the order they appear
+    pub extensions: Vec<u16>,
+    pub sni: Option<String>,
+    pub alpn: Vec<String>,
+    pub groups: Vec<u16>,
+    pub point_formats: Vec<u8>,
+    pub signature_algorithms: Vec<u16>,
+    // the ClientHello was not entirely captured so extensions
+    // (and fingerprints computed from them) may be incomplete
+    pub partial: bool,
+}
+
+impl ClientHello {
+    /// Parses a ClientHello from the beginning of a TLS stream. A message
+    /// spanning several records is reassembled and a message cut after
+    /// the cipher suites is parsed as far as possible and flagged partial.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let (msg, partial) = handshake_message(data)?;
+        let mut r = Reader::new(&msg);
+
+        if r.u8()? != HANDSHAKE_CLIENT_HELLO {
+            return Err(Error::NotClientHello);
+        }
+        let len = r.u24()?;
+        let mut r = if partial {
+            Reader::new(r.data)
+        } else {
+            Reader::new(r.bytes(len)?)
+        };
+
+        let mut ch = ClientHello {
+            legacy_version: r.u16()?,
+            partial,
+            ..Default::default()
+        };
+
+        // random
+        r.bytes(32)?;
+        // session id
+        r.vec_u8()?;
+
+        ch.ciphers = r.vec_u16()?.u16_list()?;
+
+        // compression methods
+        r.vec_u8()?;
+
+        // extensions are optional
+        if r.is_empty() {
+            return Ok(ch);
+        }
+
+        let mut exts = if partial {
+            r.u16()?;
+            r
+        } else {
+            r.vec_u16()?
+        };
+
+        while !exts.is_empty() {
+            match ch.parse_extension(&mut exts) {
+                Ok(_) => {}
+                // we stop at the first extension cut by the capture
+                Err(Error::Truncated) if partial => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(ch)
+    }
+
+    fn parse_extension(&mut self, exts: &mut Reader<'_>) -> Result<(), Error> {
+        let ty = exts.u16()?;
+        let mut ext = exts.vec_u16()?;
+        self.extensions.push(ty);
+
+        match ty {
+            EXT_SERVER_NAME => {
+                let mut names = ext.vec_u16()?;
+                while !names.is_empty() {
+                    let name_type = names.u8()?;
+                    let name = names.vec_u16()?;
+                    if name_type == SERVER_NAME_HOST_NAME && self.sni.is_none() {
+                        self.sni = Some(String::from_utf8_lossy(name.data).into());
+                    }
+                }
+            }
+            EXT_ALPN => {
+                let mut protos = ext.vec_u16()?;
+                while !protos.is_empty() {
+                    let p = protos.vec_u8()?;
+                    self.alpn.push(String::from_utf8_lossy(p.data).into());
+                }
+            }
+            EXT_SUPPORTED_GROUPS => self.groups = ext.vec_u16()?.u16_list()?,
+            EXT_EC_POINT_FORMATS => self.point_formats = ext.vec_u8()?.data.to_vec(),
+            EXT_SIGNATURE_ALGORITHMS => self.signature_algorithms = ext.vec_u16()?.u16_list()?,
+            EXT_SUPPORTED_VERSIONS => self.supported_versions = ext.vec_u8()?.u16_list()?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns the highest TLS version offered by the client
+    pub fn version(&self) -> u16 {
+        self.supported_versions
+            .iter()
+            .copied()
+            .filter(|&v| !is_grease(v))
+            .max()
+            .unwrap_or(self.legacy_version)
+    }
+
+    /// Returns the highest TLS version offered by the client as a string
+    pub fn version_str(&self) -> String {
+        match self.version() {
+            0x0304 => "1.3".into(),
+            0x0303 => "1.2".into(),
+            0x0302 => "1.1".into(),
+            0x0301 => "1.0".into(),
+            0x0300 => "ssl3".into(),
+            v => format!("{v:#06x}"),
+        }
+    }
+
+    /// Returns the JA3 string of the ClientHello
+    pub fn ja3_string(&self) -> String {
+        fn join<T: ToString>(v: impl Iterator<Item = T>) -> String {
+            v.map(|x| x.to_string()).collect::<Vec<String>>().join("-")
+        }
+
+        let no_grease = |v: &&u16| !is_grease(**v);
+
+        format!(
+            "{},{},{},{},{}",
+            self.legacy_version,
+            join(self.ciphers.iter().filter(no_grease)),
+            join(self.extensions.iter().filter(no_grease)),
+            join(self.groups.iter().filter(no_grease)),
+            join(self.point_formats.iter()),
+        )
+    }
+
+    /// Returns the JA3 hash of the ClientHello
+    pub fn ja3(&self) -> String {
+        md5_data(self.ja3_string())
+    }
+
+    /// Returns the JA4 fingerprint of the ClientHello, the TLS
+    /// stream is assumed to be carried over TCP
+    pub fn ja4(&self) -> String {
+        fn hex_list(v: &[u16]) -> String {
+            v.iter()
+                .map(|x| format!("{x:04x}"))
+                .collect::<Vec<String>>()
+                .join(",")
+        }
+
+        fn truncated_hash(s: &str) -> String {
+            if s.is_empty() {
+                return "000000000000".into();
+            }
+            sha256_data(s)[..12].into()
+        }
+
+        let version = match self.version() {
+            0x0304 => "13",
+            0x0303 => "12",
+            0x0302 => "11",
+            0x0301 => "10",
+            0x0300 => "s3",
+            0x0002 => "s2",
+            0xfeff => "d1",
+            0xfefd => "d2",
+            0xfefc => "d3",
+            _ => "00",
+        };
+
+        let mut ciphers: Vec<u16> = self
+            .ciphers
+            .iter()
+            .copied()
+            .filter(|&c| !is_grease(c))
+            .collect();
+        let mut extensions: Vec<u16> = self
+            .extensions
+            .iter()
+            .copied()
+            .filter(|&e| !is_grease(e))
+            .collect();
+
+        let alpn = match self.alpn.first().map(|a| a.as_bytes()) {
+            Some([first, .., last]) | Some([first @ last]) => {
+                if first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric() {
+                    format!("{}{}", *first as char, *last as char)
+                } else {
+                    let first = format!("{first:02x}");
+                    let last = format!("{last:02x}");
+                    format!("{}{}", &first[..1], &last[1..])
+                }
+            }
+            _ => "00".into(),
+        };
+
+        let mut a = String::with_capacity(10);
+        let _ = write!(
+            a,
+            "t{version}{}{:02}{:02}{alpn}",
+            if self.sni.is_some() { 'd' } else { 'i' },
+            ciphers.len().min(99),
+            extensions.len().min(99),
+        );
+
+        ciphers.sort_unstable();
+        let b = truncated_hash(&hex_list(&ciphers));
+
+        // SNI and ALPN are not part of the hashed extensions
+        extensions.retain(|&e| e != EXT_SERVER_NAME && e != EXT_ALPN);
+        extensions.sort_unstable();
+        let mut c = hex_list(&extensions);
+        if !self.signature_algorithms.is_empty() {
+            let _ = write!(c, "_{}", hex_list(&self.signature_algorithms));
+        }
+        let c = truncated_hash(&c);
+
+        format!("{a}_{b}_{c}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // builds a ClientHello record with the given extensions
+    fn client_hello(ciphers: &[u16], exts: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0u8; 32]);
+        // empty session id
+        body.push(0);
+        body.extend_from_slice(&((ciphers.len() * 2) as u16).to_be_bytes());
+        for c in ciphers {
+            body.extend_from_slice(&c.to_be_bytes());
+        }
+        // null compression
+        body.extend_from_slice(&[1, 0]);
+
+        let mut ext_data = vec![];
+        for (ty, data) in exts {
+            ext_data.extend_from_slice(&ty.to_be_bytes());
+            ext_data.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            ext_data.extend_from_slice(data);
+        }
+        body.extend_from_slice(&(ext_data.len() as u16).to_be_bytes());
+        body.extend_from_slice(&ext_data);
+
+        let mut hs = vec![HANDSHAKE_CLIENT_HELLO];
+        hs.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        hs.extend_from_slice(&body);
+
+        let mut rec = vec![CONTENT_TYPE_HANDSHAKE, 0x03, 0x01];
+        rec.extend_from_slice(&(hs.len() as u16).to_be_bytes());
+        rec.extend_from_slice(&hs);
+        rec
+    }
+
+    fn sni(name: &str) -> Vec<u8> {
+        let mut v = ((name.len() + 3) as u16).to_be_bytes().to_vec();
+        v.push(SERVER_NAME_HOST_NAME);
+        v.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        v.extend_from_slice(name.as_bytes());
+        v
+    }
+
+    fn alpn(protos: &[&str]) -> Vec<u8> {
+        let mut l = vec![];
+        for p in protos {
+            l.push(p.len() as u8);
+            l.extend_from_slice(p.as_bytes());
+        }
+        let mut v = (l.len() as u16).to_be_bytes().to_vec();
+        v.extend_from_slice(&l);
+        v
+    }
+
+    #[test]
+    fn test_parse() {
+        let data = client_hello(
+            &[0x0a0a, 0x1301, 0x1302, 0xc02b],
+            &[
+                (0x1a1a, vec![]),
+                (EXT_SERVER_NAME, sni("example.com")),
+                (EXT_ALPN, alpn(&["h2", "http/1.1"])),
+                (EXT_SUPPORTED_GROUPS, vec![0, 4, 0x2a, 0x2a, 0, 0x1d]),
+                (EXT_EC_POINT_FORMATS, vec![1, 0]),
+                (EXT_SIGNATURE_ALGORITHMS, vec![0, 4, 0x04, 0x03, 0x08, 0x04]),
+                (EXT_SUPPORTED_VERSIONS, vec![4, 0x03, 0x04, 0x03, 0x03]),
+            ],
+        );
+
+        let ch = ClientHello::parse(&data).unwrap();
+        assert_eq!(ch.sni.as_deref(), Some("example.com"));
+        assert_eq!(ch.alpn, vec!["h2", "http/1.1"]);
+        assert_eq!(ch.version_str(), "1.3");
+        assert_eq!(ch.ja3_string(), "771,4865-4866-49195,0-16-10-11-13-43,29,0");
+        assert_eq!(ch.ja3(), md5_data(ch.ja3_string()));
+        assert!(!ch.partial);
+    }
+
+    // Chrome ClientHello used as example in the JA4 specification
+    // https://github.com/FoxIO-LLC/ja4/blob/main/technical_details/JA4.md
+    fn chrome_client_hello() -> Vec<u8> {
+        client_hello(
+            &[
+                0x8a8a, 0x1301, 0x1302, 0x1303, 0xc02b, 0xc02f, 0xc02c, 0xc030, 0xcca9, 0xcca8,
+                0xc013, 0xc014, 0x009c, 0x009d, 0x002f, 0x0035,
+            ],
+            &[
+                (0x3a3a, vec![]),
+                (EXT_SERVER_NAME, sni("example.com")),
+                (0x0017, vec![]),
+                (0xff01, vec![0]),
+                (
+                    EXT_SUPPORTED_GROUPS,
+                    vec![0, 8, 0x4a, 0x4a, 0, 0x1d, 0, 0x17, 0, 0x18],
+                ),
+                (EXT_EC_POINT_FORMATS, vec![1, 0]),
+                (0x0023, vec![]),
+                (EXT_ALPN, alpn(&["h2", "http/1.1"])),
+                (0x0005, vec![1, 0, 0, 0, 0]),
+                (
+                    EXT_SIGNATURE_ALGORITHMS,
+                    vec![
+                        0, 16, 0x04, 0x03, 0x08, 0x04, 0x04, 0x01, 0x05, 0x03, 0x08, 0x05, 0x05,
+                        0x01, 0x08, 0x06, 0x06, 0x01,
+                    ],
+                ),
+                (0x0012, vec![]),
+                (0x0033, vec![0, 5, 0x4a, 0x4a, 0, 1, 0]),
+                (0x002d, vec![1, 1]),
+                (
+                    EXT_SUPPORTED_VERSIONS,
+                    vec![6, 0x6a, 0x6a, 0x03, 0x04, 0x03, 0x03],
+                ),
+                (0x001b, vec![2, 0, 2]),
+                (0x4469, vec![0, 3, 2, b'h', b'2']),
+                (0x0015, vec![0; 16]),
+                (0x2a2a, vec![0]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_ja4() {
+        let ch = ClientHello::parse(&chrome_client_hello()).unwrap();
+        assert_eq!(ch.ja4(), "t13d1516h2_8daaf6152771_e5627efa2ab1");
+    }
+
+    #[test]
+    fn test_parse_multi_records() {
+        let data = chrome_client_hello();
+        let (header, msg) = data.split_at(5);
+
+        // the handshake message is split across two records
+        let (first, second) = msg.split_at(128);
+        let mut split = vec![];
+        for frag in [first, second] {
+            split.extend_from_slice(&header[..3]);
+            split.extend_from_slice(&(frag.len() as u16).to_be_bytes());
+            split.extend_from_slice(frag);
+        }
+
+        let ch = ClientHello::parse(&split).unwrap();
+        assert!(!ch.partial);
+        assert_eq!(ch.sni.as_deref(), Some("example.com"));
+        assert_eq!(ch.ja4(), "t13d1516h2_8daaf6152771_e5627efa2ab1");
+
+        // only the first record got captured
+        let ch = ClientHello::parse(&split[..5 + first.len()]).unwrap();
+        assert!(ch.partial);
+        assert_eq!(ch.ciphers.len(), 16);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            ClientHello::parse(b"GET / HTTP/1.1").unwrap_err(),
+            Error::NotHandshake
+        );
+
+        let data = client_hello(&[0x1301], &[(EXT_SERVER_NAME, sni("example.com"))]);
+        // the hello is cut in the middle of the SNI extension
+        let ch = ClientHello::parse(&data[..data.len() - 4]).unwrap();
+        assert!(ch.partial);
+        assert_eq!(ch.ciphers, vec![0x1301]);
+        assert_eq!(ch.sni, None);
+
+        // the hello is cut before the end of the cipher suites
+        assert_eq!(
+            ClientHello::parse(&data[..5 + 4 + 2 + 32 + 1 + 3]).unwrap_err(),
+            Error::Truncated
+        );
+    }
+}